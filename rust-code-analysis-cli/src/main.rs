@@ -2,9 +2,10 @@ mod formats;
 
 use std::cmp::Ordering;
 use std::collections::{HashMap, hash_map};
+use std::io;
 use std::path::{Path, PathBuf};
 use std::process;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::thread::available_parallelism;
 
 use clap::Parser;
@@ -14,35 +15,53 @@ use globset::{Glob, GlobSet, GlobSetBuilder};
 use formats::Format;
 
 // Enums
-use rust_code_analysis::LANG;
+use rust_code_analysis::{Case, Error, IdentifierKind, LANG};
+
+// Halstead and Mi
+use rust_code_analysis::halstead::HalsteadTable;
+use rust_code_analysis::mi::{MiComplexity, MiFormula};
 
 // Structs
 use rust_code_analysis::{
-    CommentRm, CommentRmCfg, ConcurrentRunner, Count, CountCfg, Dump, DumpCfg, FilesData, Find,
-    FindCfg, Function, FunctionCfg, Metrics, MetricsCfg, OpsCfg, OpsCode, PreprocParser,
-    PreprocResults,
+    CloneDetector, Clones, ClonesCfg, CommentRm, CommentRmCfg, ConcurrentRunner, Count, CountCfg,
+    DebtCfg, DebtCode, DebtMarkers, Dependencies, DependencyGraphBuilder, DependencyGraphCfg, Dump,
+    DumpCfg, Explain, ExplainCfg, FilesData, Find, FindCfg, FuncSpace, Function, FunctionCfg,
+    ImportsCfg, ImportsCode, MarkerPatterns, MetricSet, Metrics, MetricsCfg, MetricsOptions,
+    Naming, NamingCfg, NamingCode, NamingConventions, OpsCfg, OpsCode, PreprocParser,
+    PreprocResults, Query, Rewrite, RewriteCfg, RewriteMatcher, RewriteRule, Smells, SmellsCfg,
+    SmellsCode,
 };
 
 // Functions
 use rust_code_analysis::{
-    action, fix_includes, get_from_ext, get_function_spaces, get_ops, guess_language, preprocess,
-    read_file, read_file_with_eol, write_file,
+    DEFAULT_ALLOWED_NUMBERS, DEFAULT_MARKERS, action, debt_markers, dump_clones, dump_root,
+    fix_includes, get_from_ext, get_function_spaces, get_ops, guess_language, imports,
+    metrics_with_increments, metrics_with_options, naming, preprocess, read_file,
+    read_file_with_eol, smells, try_action, write_file,
 };
 
 // Traits
-use rust_code_analysis::ParserTrait;
+use rust_code_analysis::{Callback, ParserTrait};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct Config {
     dump: bool,
     in_place: bool,
     comments: bool,
     find_filter: Vec<String>,
     count_filter: Vec<String>,
+    queries: Option<HashMap<LANG, Arc<Query>>>,
+    rewrite: Option<RewriteRule>,
     language: Option<LANG>,
     function: bool,
     metrics: bool,
+    metrics_options: Option<Arc<MetricsOptions>>,
     ops: bool,
+    explain: bool,
+    markers: Option<MarkerPatterns>,
+    smells: Option<Vec<f64>>,
+    naming: Option<NamingOptions>,
+    imports: bool,
     output_format: Option<Format>,
     output: Option<PathBuf>,
     pretty: bool,
@@ -51,6 +70,116 @@ struct Config {
     preproc_lock: Option<Arc<Mutex<PreprocResults>>>,
     preproc: Option<Arc<PreprocResults>>,
     count_lock: Option<Arc<Mutex<Count>>>,
+    clones_lock: Option<Arc<Mutex<CloneDetector>>>,
+    clones: Option<Arc<Clones>>,
+    dependencies_lock: Option<Arc<Mutex<DependencyGraphBuilder>>>,
+}
+
+struct SpacesWithIncrements;
+
+impl Callback for SpacesWithIncrements {
+    type Res = Result<FuncSpace, Error>;
+    type Cfg = PathBuf;
+
+    fn call<T: ParserTrait>(path: Self::Cfg, parser: &T) -> Self::Res {
+        metrics_with_increments(parser, &path)
+    }
+}
+
+struct SpacesWithOptions;
+
+impl Callback for SpacesWithOptions {
+    type Res = Result<FuncSpace, Error>;
+    type Cfg = (PathBuf, Arc<MetricsOptions>);
+
+    fn call<T: ParserTrait>((path, options): Self::Cfg, parser: &T) -> Self::Res {
+        metrics_with_options(parser, &path, &options)
+    }
+}
+
+struct DebtMarkersList;
+
+impl Callback for DebtMarkersList {
+    type Res = DebtMarkers;
+    type Cfg = DebtCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        debt_markers(parser, &cfg.path, &cfg.patterns)
+    }
+}
+
+struct SmellsList;
+
+impl Callback for SmellsList {
+    type Res = Smells;
+    type Cfg = SmellsCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        smells(parser, &cfg.path, &cfg.allowed_numbers)
+    }
+}
+
+struct ImportsList;
+
+impl Callback for ImportsList {
+    type Res = Dependencies;
+    type Cfg = ImportsCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        imports(parser, &cfg.path)
+    }
+}
+
+struct NamingList;
+
+impl Callback for NamingList {
+    type Res = Result<Naming, Error>;
+    type Cfg = NamingCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        naming(parser, &cfg.path, &cfg.conventions)
+    }
+}
+
+// The naming conventions set on the command line,
+// replacing the usual ones of a language
+#[derive(Debug, Clone)]
+struct NamingOptions {
+    cases: Vec<(IdentifierKind, Case)>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+impl NamingOptions {
+    fn conventions(&self, language: LANG) -> NamingConventions {
+        let mut conventions = NamingConventions::new(language);
+        for (kind, case) in &self.cases {
+            conventions.get_mut(*kind).case = Some(*case);
+        }
+        for kind in [
+            IdentifierKind::Function,
+            IdentifierKind::Type,
+            IdentifierKind::Variable,
+            IdentifierKind::Constant,
+            IdentifierKind::Parameter,
+        ] {
+            let convention = conventions.get_mut(kind);
+            if let Some(min_length) = self.min_length {
+                convention.min_length = min_length;
+            }
+            if self.max_length.is_some() {
+                convention.max_length = self.max_length;
+            }
+        }
+        conventions
+    }
+}
+
+fn parse_naming_case(value: &str) -> Result<(IdentifierKind, Case), String> {
+    let (kind, case) = value
+        .split_once('=')
+        .ok_or_else(|| format!("{value:?} is not in the form KIND=CASE"))?;
+    Ok((kind.parse()?, case.parse()?))
 }
 
 fn mk_globset(elems: Vec<String>) -> GlobSet {
@@ -67,7 +196,7 @@ fn mk_globset(elems: Vec<String>) -> GlobSet {
     globset.build().map_or(GlobSet::empty(), |globset| globset)
 }
 
-fn act_on_file(path: PathBuf, cfg: &Config) -> std::io::Result<()> {
+fn act_on_file(path: PathBuf, cfg: &Config) -> Result<(), Error> {
     let source = if let Some(source) = read_file_with_eol(&path)? {
         source
     } else {
@@ -82,33 +211,126 @@ fn act_on_file(path: PathBuf, cfg: &Config) -> std::io::Result<()> {
         return Ok(());
     };
 
+    // The query is compiled only for the languages whose grammar accepts it,
+    // so the files of the other languages are skipped
+    let query = match &cfg.queries {
+        Some(queries) => match queries.get(&language) {
+            Some(query) => Some(query.clone()),
+            None => return Ok(()),
+        },
+        None => None,
+    };
+
     let pr = cfg.preproc.clone();
     if cfg.dump {
         let cfg = DumpCfg {
             line_start: cfg.line_start,
             line_end: cfg.line_end,
         };
-        action::<Dump>(&language, source, &path, pr, cfg)
+        try_action::<Dump, _>(&language, source, &path, pr, cfg)
+    } else if let Some(detector) = &cfg.clones_lock {
+        let cfg = ClonesCfg {
+            path: path.clone(),
+            detector: detector.clone(),
+        };
+        try_action::<CloneDetector, _>(&language, source, &path, pr, cfg)
+    } else if let Some(builder) = &cfg.dependencies_lock {
+        let cfg = DependencyGraphCfg {
+            path: path.clone(),
+            builder: builder.clone(),
+        };
+        try_action::<DependencyGraphBuilder, _>(&language, source, &path, pr, cfg)
     } else if cfg.metrics {
-        if let Some(output_format) = &cfg.output_format {
-            if let Some(space) = get_function_spaces(&language, source, &path, pr) {
-                output_format.dump_formats(space, path, cfg.output.as_ref(), cfg.pretty);
+        if cfg.output_format.is_none() && cfg.clones.is_none() && cfg.metrics_options.is_none() {
+            let cfg = MetricsCfg {
+                path,
+                selection: MetricSet::all(),
+            };
+            let path = cfg.path.clone();
+            return try_action::<Metrics, _>(&language, source, &path, pr, cfg);
+        }
+
+        let mut space = match &cfg.metrics_options {
+            Some(options) => {
+                let options_cfg = (path.clone(), options.clone());
+                try_action::<SpacesWithOptions, _>(&language, source, &path, pr, options_cfg)?
             }
+            None => get_function_spaces(&language, source, &path, pr)?,
+        };
+        if let Some(clones) = &cfg.clones {
+            clones.annotate(&path, &mut space);
+        }
+        if let Some(output_format) = &cfg.output_format {
+            output_format.dump_formats(space, path, cfg.output.as_ref(), cfg.pretty);
             Ok(())
         } else {
-            let cfg = MetricsCfg { path };
-            let path = cfg.path.clone();
-            action::<Metrics>(&language, source, &path, pr, cfg)
+            Ok(dump_root(&space)?)
         }
     } else if cfg.ops {
         if let Some(output_format) = &cfg.output_format {
-            let ops = get_ops(&language, source, &path, pr).unwrap();
+            let ops = get_ops(&language, source, &path, pr)?;
             output_format.dump_formats(ops, path, cfg.output.as_ref(), cfg.pretty);
             Ok(())
         } else {
             let cfg = OpsCfg { path };
             let path = cfg.path.clone();
-            action::<OpsCode>(&language, source, &path, pr, cfg)
+            try_action::<OpsCode, _>(&language, source, &path, pr, cfg)
+        }
+    } else if cfg.explain {
+        if let Some(output_format) = &cfg.output_format {
+            let space =
+                try_action::<SpacesWithIncrements, _>(&language, source, &path, pr, path.clone())?;
+            output_format.dump_formats(space, path, cfg.output.as_ref(), cfg.pretty);
+            Ok(())
+        } else {
+            let cfg = ExplainCfg { path };
+            let path = cfg.path.clone();
+            try_action::<Explain, _>(&language, source, &path, pr, cfg)
+        }
+    } else if let Some(patterns) = &cfg.markers {
+        let cfg_debt = DebtCfg {
+            path: path.clone(),
+            patterns: patterns.clone(),
+        };
+        if let Some(output_format) = &cfg.output_format {
+            let debt = action::<DebtMarkersList>(&language, source, &path, pr, cfg_debt)?;
+            output_format.dump_formats(debt, path, cfg.output.as_ref(), cfg.pretty);
+            Ok(())
+        } else {
+            try_action::<DebtCode, _>(&language, source, &path, pr, cfg_debt)
+        }
+    } else if let Some(allowed_numbers) = &cfg.smells {
+        let cfg_smells = SmellsCfg {
+            path: path.clone(),
+            allowed_numbers: allowed_numbers.clone(),
+        };
+        if let Some(output_format) = &cfg.output_format {
+            let smells = action::<SmellsList>(&language, source, &path, pr, cfg_smells)?;
+            output_format.dump_formats(smells, path, cfg.output.as_ref(), cfg.pretty);
+            Ok(())
+        } else {
+            try_action::<SmellsCode, _>(&language, source, &path, pr, cfg_smells)
+        }
+    } else if let Some(naming_options) = &cfg.naming {
+        let cfg_naming = NamingCfg {
+            path: path.clone(),
+            conventions: naming_options.conventions(language),
+        };
+        if let Some(output_format) = &cfg.output_format {
+            let naming = try_action::<NamingList, _>(&language, source, &path, pr, cfg_naming)?;
+            output_format.dump_formats(naming, path, cfg.output.as_ref(), cfg.pretty);
+            Ok(())
+        } else {
+            try_action::<NamingCode, _>(&language, source, &path, pr, cfg_naming)
+        }
+    } else if cfg.imports {
+        let cfg_imports = ImportsCfg { path: path.clone() };
+        if let Some(output_format) = &cfg.output_format {
+            let dependencies = action::<ImportsList>(&language, source, &path, pr, cfg_imports)?;
+            output_format.dump_formats(dependencies, path, cfg.output.as_ref(), cfg.pretty);
+            Ok(())
+        } else {
+            try_action::<ImportsCode, _>(&language, source, &path, pr, cfg_imports)
         }
     } else if cfg.comments {
         let cfg = CommentRmCfg {
@@ -117,33 +339,44 @@ fn act_on_file(path: PathBuf, cfg: &Config) -> std::io::Result<()> {
         };
         let path = cfg.path.clone();
         if language == LANG::Cpp {
-            action::<CommentRm>(&LANG::Ccomment, source, &path, pr, cfg)
+            try_action::<CommentRm, _>(&LANG::Ccomment, source, &path, pr, cfg)
         } else {
-            action::<CommentRm>(&language, source, &path, pr, cfg)
+            try_action::<CommentRm, _>(&language, source, &path, pr, cfg)
         }
+    } else if let Some(rule) = &cfg.rewrite {
+        let cfg = RewriteCfg {
+            path: path.clone(),
+            rule: rule.clone(),
+            in_place: cfg.in_place,
+        };
+        // The edits are applied to the parsed code, so it must not
+        // contain the macros masked with the preprocessor data
+        try_action::<Rewrite, _>(&language, source, &path, None, cfg)
     } else if cfg.function {
         let cfg = FunctionCfg { path: path.clone() };
-        action::<Function>(&language, source, &path, pr, cfg)
-    } else if !cfg.find_filter.is_empty() {
+        try_action::<Function, _>(&language, source, &path, pr, cfg)
+    } else if !cfg.find_filter.is_empty() || (query.is_some() && cfg.count_lock.is_none()) {
         let cfg = FindCfg {
             path: path.clone(),
             filters: cfg.find_filter.clone(),
+            query,
             line_start: cfg.line_start,
             line_end: cfg.line_end,
         };
-        action::<Find>(&language, source, &path, pr, cfg)
+        try_action::<Find, _>(&language, source, &path, pr, cfg)
     } else if cfg.count_lock.is_some() {
         let cfg = CountCfg {
             filters: cfg.count_filter.clone(),
+            query,
             stats: cfg.count_lock.as_ref().unwrap().clone(),
         };
-        action::<Count>(&language, source, &path, pr, cfg)
+        try_action::<Count, _>(&language, source, &path, pr, cfg)
     } else if cfg.preproc_lock.is_some() {
         if let Some(language) = guess_language(&source, &path).0 {
             if language == LANG::Cpp {
                 let mut results = cfg.preproc_lock.as_ref().unwrap().lock().unwrap();
                 preprocess(
-                    &PreprocParser::new(source, &path, None),
+                    &PreprocParser::new(source, &path, None)?,
                     &path,
                     &mut results,
                 );
@@ -195,12 +428,134 @@ struct Opts {
     /// Count nodes of the given type: comma separated list.
     #[clap(long, short = 'C', number_of_values = 1)]
     count: Vec<String>,
-    /// Compute different metrics.
-    #[clap(long, short)]
-    metrics: bool,
+    /// Find the nodes captured by the tree-sitter query in the given file.
+    #[clap(long, value_parser, conflicts_with_all = ["find", "count"])]
+    query: Option<PathBuf>,
+    /// Count the nodes captured by the query instead of printing them.
+    #[clap(long, requires = "query")]
+    query_count: bool,
+    /// Code matched by the rewrite, written in the language of the files,
+    /// with `$NAME` metavariables matching any node and `$$` standing for `$`.
+    #[clap(long, conflicts_with = "query", requires = "rewrite")]
+    pattern: Option<String>,
+    /// Replace the code matched by the pattern, or captured by the query
+    /// with `@match`, by the given template, printing the diff of the changes
+    /// or saving them with `--in-place`.
+    #[clap(long, conflicts_with_all = ["query_count", "find", "count"])]
+    rewrite: Option<String>,
+    /// Compute different metrics, all of them or the given ones:
+    /// comma separated list, e.g. loc,cyclomatic.
+    #[clap(long, short, num_args = 0..=1)]
+    metrics: Option<Option<MetricSet>>,
+    /// File classifying the node kinds of each language as Halstead
+    /// operators or operands, in TOML, JSON or YAML format.
+    #[clap(long, value_parser, requires = "metrics")]
+    halstead_table: Option<PathBuf>,
+    /// Formula of the custom maintainability index, rated from A to F:
+    /// original, sei or visual-studio.
+    #[clap(long, value_parser = parse_mi_formula, requires = "metrics")]
+    mi_formula: Option<MiFormula>,
+    /// Coefficients of the custom maintainability index: the constant and
+    /// the weights of volume, complexity, sloc and comments.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        allow_negative_numbers = true,
+        requires = "metrics"
+    )]
+    mi_coefficients: Vec<f64>,
+    /// Use the cognitive complexity in the custom maintainability index.
+    #[clap(long, requires = "metrics")]
+    mi_cognitive: bool,
+    /// Exclude the comments from the custom maintainability index.
+    #[clap(long, requires = "metrics")]
+    mi_no_comments: bool,
+    /// Leave the spaces containing syntax errors out of the metrics
+    /// of their parents.
+    #[clap(long, requires = "metrics")]
+    exclude_syntax_errors: bool,
+    /// The root directory of the project, from which the modules
+    /// of the qualified names of the spaces are derived.
+    #[clap(long, requires = "metrics", value_parser)]
+    root: Option<PathBuf>,
+    /// Minimum values of the custom maintainability index rated from A to E.
+    #[clap(
+        long,
+        value_delimiter = ',',
+        allow_negative_numbers = true,
+        requires = "metrics"
+    )]
+    mi_ratings: Vec<f64>,
     /// Retrieve all operands and operators in a code.
     #[clap(long, conflicts_with = "metrics")]
     ops: bool,
+    /// Explain the cognitive and cyclomatic complexity of each function.
+    #[clap(long, conflicts_with_all = ["metrics", "ops"])]
+    explain: bool,
+    /// Retrieve the technical-debt markers contained in the comments.
+    #[clap(long, conflicts_with_all = ["metrics", "ops", "explain"])]
+    markers: bool,
+    /// Marker to search in the comments, replacing the default ones.
+    /// It is also counted by the markers metric.
+    #[clap(long = "marker", number_of_values = 1)]
+    marker: Vec<String>,
+    /// Regular expression of a custom marker to search in the comments.
+    /// It is also counted by the markers metric.
+    #[clap(long = "marker-regex", number_of_values = 1)]
+    marker_regex: Vec<String>,
+    /// Retrieve the code smells, as empty or catch-all exception handlers,
+    /// magic numbers and repeated strings.
+    #[clap(long, conflicts_with_all = ["metrics", "ops", "explain", "markers"])]
+    smells: bool,
+    /// Number not reported as magic number, replacing the default ones.
+    /// It is also allowed by the literals metric.
+    #[clap(
+        long = "allowed-number",
+        number_of_values = 1,
+        allow_negative_numbers = true
+    )]
+    allowed_number: Vec<f64>,
+    /// Check the names of the declared identifiers against
+    /// the naming conventions of their language.
+    #[clap(long, conflicts_with_all = ["metrics", "ops", "explain", "markers", "smells"])]
+    naming: bool,
+    /// Case of a kind of identifiers, as `function=camelCase`,
+    /// replacing the usual one of the language.
+    #[clap(
+        long = "naming-case",
+        number_of_values = 1,
+        value_parser = parse_naming_case,
+        requires = "naming"
+    )]
+    naming_case: Vec<(IdentifierKind, Case)>,
+    /// Minimum length of the identifiers.
+    #[clap(long, requires = "naming")]
+    naming_min_length: Option<usize>,
+    /// Maximum length of the identifiers.
+    #[clap(long, requires = "naming")]
+    naming_max_length: Option<usize>,
+    /// Retrieve the imports, as `use` declarations, `import` statements,
+    /// `require()` calls and `#include` directives.
+    #[clap(
+        long,
+        conflicts_with_all = ["metrics", "ops", "explain", "markers", "smells", "naming"]
+    )]
+    imports: bool,
+    /// Search the code clones among all files.
+    #[clap(long, conflicts_with_all = ["ops", "explain", "markers", "smells", "naming", "imports"])]
+    clones: bool,
+    /// Minimum number of lines of a clone.
+    #[clap(long, default_value_t = 6, requires = "clones")]
+    clones_min_lines: usize,
+    /// Minimum number of tokens of a clone.
+    #[clap(long, default_value_t = 50, requires = "clones")]
+    clones_min_tokens: usize,
+    /// Build the graph of the dependencies among the modules of all files.
+    #[clap(
+        long,
+        conflicts_with_all = ["metrics", "ops", "explain", "markers", "smells", "naming", "imports", "clones"]
+    )]
+    dependencies: bool,
     /// Do action in place.
     #[clap(long, short)]
     in_place: bool,
@@ -240,10 +595,44 @@ struct Opts {
     warning: bool,
 }
 
+fn parse_mi_formula(value: &str) -> Result<MiFormula, String> {
+    match value {
+        "original" => Ok(MiFormula::original()),
+        "sei" => Ok(MiFormula::sei()),
+        "visual-studio" => Ok(MiFormula::visual_studio()),
+        _ => Err("expected original, sei or visual-studio".to_string()),
+    }
+}
+
+fn read_halstead_table(path: &Path) -> Result<HalsteadTable, String> {
+    let data = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => serde_json::from_str(&data).map_err(|e| e.to_string()),
+        Some("yaml" | "yml") => serde_yaml::from_str(&data).map_err(|e| e.to_string()),
+        _ => toml::from_str(&data).map_err(|e| e.to_string()),
+    }
+}
+
+// Reads the results of the preprocessor saved by a previous run
+fn load_preproc(path: &Path) -> Result<PreprocResults, Error> {
+    let data = read_file(path)?;
+    serde_json::from_slice(&data).map_err(|e| {
+        Error::InvalidInput(format!(
+            "Invalid preprocessor data in {}: {e}",
+            path.display()
+        ))
+    })
+}
+
 fn main() {
-    let opts = Opts::parse();
+    if let Err(e) = run(Opts::parse()) {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
 
-    let count_lock = if !opts.count.is_empty() {
+fn run(opts: Opts) -> Result<(), Error> {
+    let count_lock = if !opts.count.is_empty() || opts.query_count {
         Some(Arc::new(Mutex::new(Count::default())))
     } else {
         None
@@ -251,14 +640,8 @@ fn main() {
 
     let (preproc_lock, preproc) = match opts.preproc.len().cmp(&1) {
         Ordering::Equal => {
-            let data = read_file(&opts.preproc[0]).unwrap();
             eprintln!("Load preproc data");
-            let x = (
-                None,
-                Some(Arc::new(
-                    serde_json::from_slice::<PreprocResults>(&data).unwrap(),
-                )),
-            );
+            let x = (None, Some(Arc::new(load_preproc(&opts.preproc[0])?)));
             eprintln!("Load preproc data: finished");
             x
         }
@@ -267,11 +650,160 @@ fn main() {
     };
 
     let output_is_dir = opts.output.as_ref().map(|p| p.is_dir()).unwrap_or(false);
-    if (opts.metrics || opts.ops) && opts.output.is_some() && !output_is_dir {
+    if (opts.metrics.is_some()
+        || opts.ops
+        || opts.explain
+        || opts.markers
+        || opts.smells
+        || opts.naming
+        || opts.imports
+        || opts.clones
+        || opts.dependencies)
+        && opts.output.is_some()
+        && !output_is_dir
+    {
         eprintln!("Error: The output parameter must be a directory");
         process::exit(1);
     }
 
+    let custom_markers = !opts.marker.is_empty() || !opts.marker_regex.is_empty();
+    let marker_patterns = if opts.markers || custom_markers {
+        let keywords = if opts.marker.is_empty() {
+            DEFAULT_MARKERS.iter().map(|m| m.to_string()).collect()
+        } else {
+            opts.marker
+        };
+        match MarkerPatterns::new(&keywords, &opts.marker_regex) {
+            Ok(patterns) => Some(patterns),
+            Err(e) => {
+                eprintln!("Error: Invalid marker regex: {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+    let markers = if opts.markers {
+        marker_patterns.clone()
+    } else {
+        None
+    };
+
+    let query = opts.query.map(|path| match std::fs::read_to_string(&path) {
+        Ok(query) => query,
+        Err(e) => {
+            eprintln!("Error: Invalid query {}: {e}", path.display());
+            process::exit(1);
+        }
+    });
+
+    let rewrite = opts.rewrite.map(|template| {
+        let matcher = match (opts.pattern, query.clone()) {
+            (Some(pattern), _) => RewriteMatcher::Pattern(pattern),
+            (None, Some(query)) => RewriteMatcher::Query(query),
+            (None, None) => {
+                eprintln!("Error: The rewrite requires a pattern or a query");
+                process::exit(1);
+            }
+        };
+        RewriteRule { matcher, template }
+    });
+
+    let allowed_numbers = (!opts.allowed_number.is_empty()).then_some(opts.allowed_number);
+    let smells = opts.smells.then(|| {
+        allowed_numbers
+            .clone()
+            .unwrap_or_else(|| DEFAULT_ALLOWED_NUMBERS.to_vec())
+    });
+
+    let halstead_table = opts
+        .halstead_table
+        .map(|path| match read_halstead_table(&path) {
+            Ok(table) => table,
+            Err(e) => {
+                eprintln!("Error: Invalid Halstead table {}: {e}", path.display());
+                process::exit(1);
+            }
+        });
+
+    for (name, values) in [
+        ("coefficients", &opts.mi_coefficients),
+        ("ratings", &opts.mi_ratings),
+    ] {
+        if !values.is_empty() && values.len() != 5 {
+            eprintln!("Error: The maintainability index {name} must be 5 values");
+            process::exit(1);
+        }
+    }
+
+    let mi_formula = if opts.mi_formula.is_some()
+        || !opts.mi_coefficients.is_empty()
+        || opts.mi_cognitive
+        || opts.mi_no_comments
+        || !opts.mi_ratings.is_empty()
+    {
+        let mut formula = opts.mi_formula.unwrap_or_default();
+        if let [constant, volume, complexity, sloc, comments] = opts.mi_coefficients[..] {
+            formula.constant = constant;
+            formula.volume = volume;
+            formula.complexity = complexity;
+            formula.sloc = sloc;
+            formula.comments = comments;
+        }
+        if opts.mi_cognitive {
+            formula.complexity_metric = MiComplexity::Cognitive;
+        }
+        if opts.mi_no_comments {
+            formula.comments = 0.;
+        }
+        if let Ok(ratings) = opts.mi_ratings[..].try_into() {
+            formula.ratings = ratings;
+        }
+        Some(formula)
+    } else {
+        None
+    };
+
+    let selection = opts.metrics.flatten();
+
+    let metrics_options = (halstead_table.is_some()
+        || mi_formula.is_some()
+        || selection.is_some()
+        || opts.exclude_syntax_errors
+        || opts.root.is_some()
+        || custom_markers)
+        .then(|| {
+            Arc::new(MetricsOptions {
+                halstead_table: halstead_table.unwrap_or_default(),
+                mi_formula,
+                selection: selection.unwrap_or_default(),
+                exclude_syntax_errors: opts.exclude_syntax_errors,
+                markers: marker_patterns,
+                allowed_numbers,
+                root: opts.root.clone(),
+                ..MetricsOptions::default()
+            })
+        });
+
+    let naming = opts.naming.then_some(NamingOptions {
+        cases: opts.naming_case,
+        min_length: opts.naming_min_length,
+        max_length: opts.naming_max_length,
+    });
+
+    let clones_lock = if opts.clones {
+        Some(Arc::new(Mutex::new(CloneDetector::new(
+            opts.clones_min_lines,
+            opts.clones_min_tokens,
+        ))))
+    } else {
+        None
+    };
+
+    let dependencies_lock = opts
+        .dependencies
+        .then(|| Arc::new(Mutex::new(DependencyGraphBuilder::new())));
+
     let typ = opts.language_type.unwrap_or_default();
     let language = if preproc_lock.is_some() {
         Some(LANG::Preproc)
@@ -285,6 +817,31 @@ fn main() {
         get_from_ext(&typ)
     };
 
+    // A rewrite compiles its own query
+    let queries = query.filter(|_| rewrite.is_none()).map(|query| {
+        let queries = match language {
+            Some(language) => match Query::new(language, &query) {
+                Ok(compiled) => vec![(language, Arc::new(compiled))],
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    process::exit(1);
+                }
+            },
+            None => LANG::into_enum_iter()
+                .filter_map(|language| {
+                    Query::new(language, &query)
+                        .ok()
+                        .map(|compiled| (language, Arc::new(compiled)))
+                })
+                .collect(),
+        };
+        if queries.is_empty() {
+            eprintln!("Error: The query is not valid for any language");
+            process::exit(1);
+        }
+        queries.into_iter().collect::<HashMap<_, _>>()
+    });
+
     let num_jobs = opts
         .num_jobs
         .map(|num_jobs| std::cmp::max(2, num_jobs) - 1)
@@ -306,10 +863,18 @@ fn main() {
         comments: opts.comments,
         find_filter: opts.find,
         count_filter: opts.count,
+        queries,
+        rewrite,
         language,
         function: opts.function,
-        metrics: opts.metrics,
+        metrics: opts.metrics.is_some(),
+        metrics_options,
         ops: opts.ops,
+        explain: opts.explain,
+        markers,
+        smells,
+        naming,
+        imports: opts.imports,
         output_format: opts.output_format,
         pretty: opts.pretty,
         output: opts.output.clone(),
@@ -318,17 +883,20 @@ fn main() {
         preproc_lock: preproc_lock.clone(),
         preproc,
         count_lock: count_lock.clone(),
+        clones_lock: clones_lock.clone(),
+        clones: None,
+        dependencies_lock: dependencies_lock.clone(),
     };
 
     let files_data = FilesData {
-        include,
-        exclude,
-        paths: opts.paths,
+        include: include.clone(),
+        exclude: exclude.clone(),
+        paths: opts.paths.clone(),
     };
 
     let all_files = match ConcurrentRunner::new(num_jobs, act_on_file)
         .set_proc_dir_paths(process_dir_path)
-        .run(cfg, files_data)
+        .run(cfg.clone(), files_data)
     {
         Ok(all_files) => all_files,
         Err(e) => {
@@ -337,20 +905,77 @@ fn main() {
         }
     };
 
+    if let Some(builder) = dependencies_lock {
+        let graph = builder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .build();
+
+        if let Some(output_format) = &cfg.output_format {
+            output_format.dump_formats(
+                graph,
+                PathBuf::from("dependencies"),
+                cfg.output.as_ref(),
+                cfg.pretty,
+            );
+        } else {
+            print!("{}", graph.to_dot());
+        }
+    }
+
+    if let Some(detector) = clones_lock {
+        // The configuration still shares the detector, so it cannot be unwrapped
+        let clones = detector
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clones();
+
+        if opts.metrics.is_some() {
+            // Run again on all files to annotate their metrics with the clones
+            let cfg = Config {
+                clones_lock: None,
+                clones: Some(Arc::new(clones)),
+                ..cfg
+            };
+            let files_data = FilesData {
+                include,
+                exclude,
+                paths: opts.paths,
+            };
+            if let Err(e) = ConcurrentRunner::new(num_jobs, act_on_file).run(cfg, files_data) {
+                eprintln!("{e:?}");
+                process::exit(1);
+            }
+        } else if let Some(output_format) = &cfg.output_format {
+            output_format.dump_formats(
+                clones,
+                PathBuf::from("clones"),
+                cfg.output.as_ref(),
+                cfg.pretty,
+            );
+        } else {
+            dump_clones(&clones)?;
+        }
+    }
+
     if let Some(count) = count_lock {
-        let count = Arc::try_unwrap(count).unwrap().into_inner().unwrap();
+        // The configuration still shares the count, so it cannot be unwrapped
+        let count = count.lock().unwrap_or_else(PoisonError::into_inner);
         println!("{count}");
     }
 
     if let Some(preproc) = preproc_lock {
-        let mut data = Arc::try_unwrap(preproc).unwrap().into_inner().unwrap();
+        // The configuration still shares the results, so they cannot be unwrapped
+        let mut data = std::mem::take(&mut *preproc.lock().unwrap_or_else(PoisonError::into_inner));
         fix_includes(&mut data.files, &all_files);
 
-        let data = serde_json::to_string(&data).unwrap();
+        let data = serde_json::to_string(&data).map_err(io::Error::from)?;
         if let Some(output_path) = opts.output {
-            write_file(&output_path, data.as_bytes()).unwrap();
+            write_file(&output_path, data.as_bytes())?;
         } else {
             println!("{data}");
         }
     }
+
+    Ok(())
 }
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use rust_code_analysis::{Callback, ParserTrait, rm_comments};
+use rust_code_analysis::{Callback, Error, ParserTrait, rm_comments};
 
 /// Payload containing source code with comments to be removed.
 #[derive(Debug, Deserialize, Serialize)]
@@ -20,7 +20,7 @@ pub struct WebCommentResponse {
     pub id: String,
     /// Source code without comments.
     ///
-    /// If `None`, an error occurred processing the request.
+    /// If `None`, the source code contains no comment to remove.
     pub code: Option<Vec<u8>>,
 }
 
@@ -43,13 +43,13 @@ pub struct WebCommentCfg {
 pub struct WebCommentCallback;
 
 impl Callback for WebCommentCallback {
-    type Res = WebCommentResponse;
+    type Res = Result<WebCommentResponse, Error>;
     type Cfg = WebCommentCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        WebCommentResponse {
+        Ok(WebCommentResponse {
             id: cfg.id,
-            code: rm_comments(parser),
-        }
+            code: rm_comments(parser)?,
+        })
     }
 }
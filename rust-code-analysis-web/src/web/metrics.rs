@@ -2,7 +2,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::{self, Value};
 use std::path::PathBuf;
 
-use rust_code_analysis::{Callback, FuncSpace, ParserTrait, metrics};
+use rust_code_analysis::{
+    Callback, FuncSpace, MetricSet, MetricsOptions, ParserTrait, metrics_with_options,
+};
 
 /// Payload containing source code used to compute metrics.
 #[derive(Debug, Deserialize, Serialize)]
@@ -15,6 +17,9 @@ pub struct WebMetricsPayload {
     pub code: String,
     /// Flag to consider only unit space metrics.
     pub unit: bool,
+    /// Metrics to compute, all of them if missing.
+    #[serde(default)]
+    pub metrics: MetricSet,
 }
 
 /// Server response containing metrics for every space present in
@@ -41,6 +46,10 @@ pub struct WebMetricsInfo {
     ///
     /// If `None`, the entire code is considered.
     pub unit: Option<String>,
+    /// Metrics to compute: comma separated list.
+    ///
+    /// If `None`, all metrics are computed.
+    pub metrics: Option<String>,
 }
 
 /// Server request configuration.
@@ -54,6 +63,8 @@ pub struct WebMetricsCfg {
     pub unit: bool,
     /// Source code programming language.
     pub language: String,
+    /// Metrics to compute.
+    pub selection: MetricSet,
 }
 
 /// Unit structure to implement the `Callback` trait.
@@ -64,7 +75,11 @@ impl Callback for WebMetricsCallback {
     type Cfg = WebMetricsCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        let spaces = metrics(parser, &cfg.path);
+        let options = MetricsOptions {
+            selection: cfg.selection,
+            ..MetricsOptions::default()
+        };
+        let spaces = metrics_with_options(parser, &cfg.path, &options).ok();
         let spaces = if cfg.unit {
             if let Some(mut spaces) = spaces {
                 spaces.spaces.clear();
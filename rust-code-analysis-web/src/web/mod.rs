@@ -1,4 +1,5 @@
 pub mod comment;
 pub mod function;
 pub mod metrics;
+pub mod query;
 pub mod server;
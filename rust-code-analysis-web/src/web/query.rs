@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{self, Value};
+
+use rust_code_analysis::{Callback, Error, ParserTrait, Query, find_query};
+
+/// Payload containing source code to be searched with a tree-sitter query.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WebQueryPayload {
+    /// Payload identifier.
+    pub id: String,
+    /// Source code filename.
+    pub file_name: String,
+    /// Source code to be searched.
+    pub code: String,
+    /// Tree-sitter query capturing the nodes to be retrieved.
+    pub query: String,
+}
+
+/// A node captured by a query.
+#[derive(Debug, Serialize)]
+pub struct WebQueryCapture {
+    /// Capture name, without the `@`.
+    pub name: String,
+    /// Node kind.
+    pub kind: &'static str,
+    /// First line of the node, starting from 1.
+    pub start_line: usize,
+    /// First column of the node, starting from 1.
+    pub start_column: usize,
+    /// Last line of the node, starting from 1.
+    pub end_line: usize,
+    /// Column following the node, starting from 1.
+    pub end_column: usize,
+    /// Node text.
+    pub text: String,
+}
+
+/// Server response containing the nodes captured by the query.
+#[derive(Debug, Serialize)]
+pub struct WebQueryResponse {
+    /// Server response identifier.
+    pub id: String,
+    /// Captured nodes, in the order of the code.
+    pub captures: Vec<WebQueryCapture>,
+}
+
+/// Server request configuration.
+#[derive(Debug)]
+pub struct WebQueryCfg {
+    /// Request identifier.
+    pub id: String,
+    /// Tree-sitter query.
+    pub query: String,
+}
+
+/// Unit structure to implement the `Callback` trait.
+pub struct WebQueryCallback;
+
+impl Callback for WebQueryCallback {
+    type Res = Result<Value, Error>;
+    type Cfg = WebQueryCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        let query = Query::new(parser.get_language(), &cfg.query)?;
+        let code = parser.get_code();
+        let captures = find_query(parser, &query)?
+            .into_iter()
+            .map(|capture| {
+                let (start_line, start_column) = capture.node.start_position();
+                let (end_line, end_column) = capture.node.end_position();
+                WebQueryCapture {
+                    name: capture.name,
+                    kind: capture.node.kind(),
+                    start_line: start_line + 1,
+                    start_column: start_column + 1,
+                    end_line: end_line + 1,
+                    end_column: end_column + 1,
+                    text: String::from_utf8_lossy(&code[capture.node.byte_range()]).into_owned(),
+                }
+            })
+            .collect();
+        Ok(serde_json::to_value(WebQueryResponse {
+            id: cfg.id,
+            captures,
+        })
+        .unwrap())
+    }
+}
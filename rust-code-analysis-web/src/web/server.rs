@@ -9,15 +9,18 @@ use std::path::PathBuf;
 use super::comment::{WebCommentCallback, WebCommentCfg, WebCommentInfo, WebCommentPayload};
 use super::function::{WebFunctionCallback, WebFunctionCfg, WebFunctionInfo, WebFunctionPayload};
 use super::metrics::{WebMetricsCallback, WebMetricsCfg, WebMetricsInfo, WebMetricsPayload};
+use super::query::{WebQueryCallback, WebQueryCfg, WebQueryPayload};
 
-use rust_code_analysis::{AstCallback, AstCfg, AstPayload, LANG, action, guess_language};
+use rust_code_analysis::{
+    AstCallback, AstCfg, AstPayload, LANG, action, guess_language, try_action,
+};
 
 const INVALID_LANGUAGE: &str = "The file extension doesn't correspond to a valid language";
 
 #[derive(Debug, Deserialize, Serialize)]
 struct Error {
     id: String,
-    error: &'static str,
+    error: String,
 }
 
 async fn get_code(mut body: web::Payload) -> Result<Vec<u8>, actix_web::Error> {
@@ -35,6 +38,7 @@ async fn ast_parser(item: web::Json<AstPayload>) -> HttpResponse {
     let buf = payload.code.into_bytes();
     let (language, _) = guess_language(&buf, path);
     if let Some(language) = language {
+        let id = payload.id.clone();
         let cfg = AstCfg {
             id: payload.id,
             comment: payload.comment,
@@ -42,17 +46,17 @@ async fn ast_parser(item: web::Json<AstPayload>) -> HttpResponse {
         };
 
         // TODO: the 4th arg should be preproc data
-        HttpResponse::Ok().json(action::<AstCallback>(
-            &language,
-            buf,
-            &PathBuf::from(""),
-            None,
-            cfg,
-        ))
+        match action::<AstCallback>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => HttpResponse::Ok().json(res),
+            Err(e) => HttpResponse::UnprocessableEntity().json(Error {
+                id,
+                error: e.to_string(),
+            }),
+        }
     } else {
         HttpResponse::NotFound().json(Error {
             id: payload.id,
-            error: INVALID_LANGUAGE,
+            error: INVALID_LANGUAGE.to_string(),
         })
     }
 }
@@ -63,23 +67,24 @@ async fn comment_removal_json(item: web::Json<WebCommentPayload>) -> HttpRespons
     let buf = payload.code.into_bytes();
     let (language, _) = guess_language(&buf, path);
     if let Some(language) = language {
+        let id = payload.id.clone();
         let cfg = WebCommentCfg { id: payload.id };
         let language = if language == LANG::Cpp {
             LANG::Ccomment
         } else {
             language
         };
-        HttpResponse::Ok().json(action::<WebCommentCallback>(
-            &language,
-            buf,
-            &PathBuf::from(""),
-            None,
-            cfg,
-        ))
+        match try_action::<WebCommentCallback, _>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => HttpResponse::Ok().json(res),
+            Err(e) => HttpResponse::UnprocessableEntity().json(Error {
+                id,
+                error: e.to_string(),
+            }),
+        }
     } else {
         HttpResponse::NotFound().json(Error {
             id: payload.id,
-            error: INVALID_LANGUAGE,
+            error: INVALID_LANGUAGE.to_string(),
         })
     }
 }
@@ -93,7 +98,20 @@ async fn comment_removal_plain(
     let (language, _) = guess_language(&buf, path);
     if let Some(language) = language {
         let cfg = WebCommentCfg { id: "".to_string() };
-        let res = action::<WebCommentCallback>(&language, buf, &PathBuf::from(""), None, cfg);
+        let res = match try_action::<WebCommentCallback, _>(
+            &language,
+            buf,
+            &PathBuf::from(""),
+            None,
+            cfg,
+        ) {
+            Ok(res) => res,
+            Err(e) => {
+                return Ok(HttpResponse::UnprocessableEntity()
+                    .append_header((http::header::CONTENT_TYPE, "text/plain"))
+                    .body(format!("error: {e}")));
+            }
+        };
         if let Some(res_code) = res.code {
             Ok(HttpResponse::Ok()
                 .append_header((http::header::CONTENT_TYPE, "application/octet-stream"))
@@ -116,23 +134,25 @@ async fn metrics_json(item: web::Json<WebMetricsPayload>) -> HttpResponse {
     let buf = payload.code.into_bytes();
     let (language, name) = guess_language(&buf, &path);
     if let Some(language) = language {
+        let id = payload.id.clone();
         let cfg = WebMetricsCfg {
             id: payload.id,
             path,
             unit: payload.unit,
             language: name.to_string(),
+            selection: payload.metrics,
         };
-        HttpResponse::Ok().json(action::<WebMetricsCallback>(
-            &language,
-            buf,
-            &PathBuf::from(""),
-            None,
-            cfg,
-        ))
+        match action::<WebMetricsCallback>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => HttpResponse::Ok().json(res),
+            Err(e) => HttpResponse::UnprocessableEntity().json(Error {
+                id,
+                error: e.to_string(),
+            }),
+        }
     } else {
         HttpResponse::NotFound().json(Error {
             id: payload.id,
-            error: INVALID_LANGUAGE,
+            error: INVALID_LANGUAGE.to_string(),
         })
     }
 }
@@ -143,6 +163,14 @@ async fn metrics_plain(
 ) -> Result<HttpResponse, actix_web::Error> {
     let buf = get_code(body).await?;
     let path = PathBuf::from(&info.file_name);
+    let selection = match info.metrics.as_deref().map(str::parse).transpose() {
+        Ok(selection) => selection.unwrap_or_default(),
+        Err(e) => {
+            return Ok(HttpResponse::BadRequest()
+                .append_header((http::header::CONTENT_TYPE, "text/plain"))
+                .body(format!("error: {e}")));
+        }
+    };
     let (language, name) = guess_language(&buf, &path);
     if let Some(language) = language {
         let cfg = WebMetricsCfg {
@@ -150,14 +178,14 @@ async fn metrics_plain(
             path,
             unit: info.unit.as_ref().is_some_and(|s| s == "1" || s == "true"),
             language: name.to_string(),
+            selection,
         };
-        Ok(HttpResponse::Ok().json(action::<WebMetricsCallback>(
-            &language,
-            buf,
-            &PathBuf::from(""),
-            None,
-            cfg,
-        )))
+        match action::<WebMetricsCallback>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => Ok(HttpResponse::Ok().json(res)),
+            Err(e) => Ok(HttpResponse::UnprocessableEntity()
+                .append_header((http::header::CONTENT_TYPE, "text/plain"))
+                .body(format!("error: {e}"))),
+        }
     } else {
         Ok(HttpResponse::NotFound()
             .append_header((http::header::CONTENT_TYPE, "text/plain"))
@@ -171,18 +199,19 @@ async fn function_json(item: web::Json<WebFunctionPayload>, _req: HttpRequest) -
     let buf = payload.code.into_bytes();
     let (language, _) = guess_language(&buf, path);
     if let Some(language) = language {
+        let id = payload.id.clone();
         let cfg = WebFunctionCfg { id: payload.id };
-        HttpResponse::Ok().json(action::<WebFunctionCallback>(
-            &language,
-            buf,
-            &PathBuf::from(""),
-            None,
-            cfg,
-        ))
+        match action::<WebFunctionCallback>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => HttpResponse::Ok().json(res),
+            Err(e) => HttpResponse::UnprocessableEntity().json(Error {
+                id,
+                error: e.to_string(),
+            }),
+        }
     } else {
         HttpResponse::NotFound().json(Error {
             id: payload.id,
-            error: INVALID_LANGUAGE,
+            error: INVALID_LANGUAGE.to_string(),
         })
     }
 }
@@ -196,13 +225,12 @@ async fn function_plain(
     let (language, _) = guess_language(&buf, path);
     if let Some(language) = language {
         let cfg = WebFunctionCfg { id: "".to_string() };
-        Ok(HttpResponse::Ok().json(action::<WebFunctionCallback>(
-            &language,
-            buf,
-            &PathBuf::from(""),
-            None,
-            cfg,
-        )))
+        match action::<WebFunctionCallback>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => Ok(HttpResponse::Ok().json(res)),
+            Err(e) => Ok(HttpResponse::UnprocessableEntity()
+                .append_header((http::header::CONTENT_TYPE, "text/plain"))
+                .body(format!("error: {e}"))),
+        }
     } else {
         Ok(HttpResponse::NotFound()
             .append_header((http::header::CONTENT_TYPE, "text/plain"))
@@ -210,6 +238,31 @@ async fn function_plain(
     }
 }
 
+async fn query_json(item: web::Json<WebQueryPayload>) -> HttpResponse {
+    let path = PathBuf::from(&item.file_name);
+    let payload = item.into_inner();
+    let buf = payload.code.into_bytes();
+    let (language, _) = guess_language(&buf, path);
+    if let Some(language) = language {
+        let cfg = WebQueryCfg {
+            id: payload.id.clone(),
+            query: payload.query,
+        };
+        match try_action::<WebQueryCallback, _>(&language, buf, &PathBuf::from(""), None, cfg) {
+            Ok(res) => HttpResponse::Ok().json(res),
+            Err(e) => HttpResponse::BadRequest().json(Error {
+                id: payload.id,
+                error: e.to_string(),
+            }),
+        }
+    } else {
+        HttpResponse::NotFound().json(Error {
+            id: payload.id,
+            error: INVALID_LANGUAGE.to_string(),
+        })
+    }
+}
+
 async fn ping() -> HttpResponse {
     HttpResponse::Ok().body(())
 }
@@ -286,6 +339,12 @@ pub async fn run(host: &str, port: u16, n_threads: usize) -> std::io::Result<()>
                     .app_data(web::PayloadConfig::default().limit(max_size))
                     .route(web::post().to(function_plain)),
             )
+            .service(
+                web::resource("/query")
+                    .guard(guard::Header("content-type", "application/json"))
+                    .app_data(web::Json::<WebQueryPayload>)
+                    .route(web::post().to(query_json)),
+            )
             .service(web::resource("/ping").route(web::get().to(ping)))
     })
     .workers(n_threads)
@@ -304,6 +363,8 @@ mod tests {
     use serde_json::json;
     use serde_json::value::Value;
 
+    use rust_code_analysis::MetricSet;
+
     use super::*;
 
     #[actix_rt::test]
@@ -630,6 +691,7 @@ mod tests {
                 file_name: "test.py".to_string(),
                 code: "# -*- Mode: Objective-C++; tab-width: 2; indent-tabs-mode: nil; c-basic-offset: 2 -*-\n\ndef foo():\n    pass\n".to_string(),
                 unit: false,
+                metrics: MetricSet::default(),
             })
             .to_request();
 
@@ -638,8 +700,10 @@ mod tests {
             "id": "1234",
             "language": "python",
             "spaces": {"kind": "unit",
+                       "qualified_name": "test",
                        "start_line": 1,
                        "end_line": 4,
+                       "start_column": 1, "end_column": 9, "start_byte": 0, "end_byte": 107, "name_range": null,
                        "metrics": {"cyclomatic": {"sum": 2.0, "average": 1.0, "min":1.0, "max":1.0},
                                    "cognitive": {"sum": 0.0, "average": 0.0, "min":0.0, "max":0.0},
                                    "nargs": {"total_functions": 0.0, "average_functions": 0.0, "total_closures": 0.0, "average_closures": 0.0, "total": 0.0, "average": 0.0, "closures_max": 0.0, "closures_min": 0.0, "functions_max": 0.0, "functions_min": 0.0},
@@ -663,11 +727,22 @@ mod tests {
                                    "mi": {"mi_original": 139.974_331_558_152_1,
                                           "mi_sei": 161.414_455_240_662_22,
                                           "mi_visual_studio": 81.856_334_244_533_39},
-                                   "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0}},
+                                   "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0},
+                                   "markers": {"todo": 0.0, "fixme": 0.0, "hack": 0.0, "xxx": 0.0, "total": 0.0},
+                                   "doc_coverage": {"documented": 0.0, "total": 1.0, "coverage": 0.0},
+                                   "exceptions": {"tries": 0.0, "catches": 0.0, "throws": 0.0, "catch_alls": 0.0, "empty_catches": 0.0},
+                                   "literals": {"magic_numbers": 0.0, "repeated_strings": 0.0},
+                                   "booleans": {"max_operands": 0.0, "complex_conditions": 0.0, "max_negation_depth": 0.0},
+                                   "imports": {"static": 0.0, "dynamic": 0.0, "total": 0.0},
+                                   "variables": {"span_average": 0.0, "span_max": 0.0, "live_average": 0.0, "live_max": 0.0},
+                                   "statements": {"declarations": 0.0, "assignments": 0.0, "calls": 0.0, "control_flow": 0.0, "returns": 0.0, "loops": 0.0, "total": 0.0},
+                                   "naming": {"identifiers": 1.0, "average_length": 3.0, "violations": 0.0}},
                        "name": "test.py",
                        "spaces": [{"kind": "function",
                                    "start_line": 3,
                                    "end_line": 4,
+                                   "start_column": 1, "end_column": 9, "start_byte": 87, "end_byte": 106,
+                                   "name_range": {"start_line": 3, "end_line": 3, "start_column": 5, "end_column": 8, "start_byte": 91, "end_byte": 94},
                                    "metrics": {"cyclomatic": {"sum": 1.0, "average": 1.0, "min":1.0, "max":1.0},
                                                "cognitive": {"sum": 0.0, "average": 0.0 , "min":0.0, "max":0.0},
                                                "nargs": {"total_functions": 0.0, "average_functions": 0.0, "total_closures": 0.0, "average_closures": 0.0, "total": 0.0, "average": 0.0, "closures_max": 0.0, "closures_min": 0.0, "functions_max": 0.0, "functions_min": 0.0},
@@ -691,8 +766,18 @@ mod tests {
                                                "mi": {"mi_original": 151.433_315_883_223_23,
                                                       "mi_sei": 142.873_061_717_489_78,
                                                       "mi_visual_studio": 88.557_494_668_551_6},
-                                               "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0}},
+                                               "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0},
+                                               "markers": {"todo": 0.0, "fixme": 0.0, "hack": 0.0, "xxx": 0.0, "total": 0.0},
+                                               "doc_coverage": {"documented": 0.0, "total": 1.0, "coverage": 0.0},
+                                               "exceptions": {"tries": 0.0, "catches": 0.0, "throws": 0.0, "catch_alls": 0.0, "empty_catches": 0.0},
+                                               "literals": {"magic_numbers": 0.0, "repeated_strings": 0.0},
+                                               "booleans": {"max_operands": 0.0, "complex_conditions": 0.0, "max_negation_depth": 0.0},
+                                               "imports": {"static": 0.0, "dynamic": 0.0, "total": 0.0},
+                                               "variables": {"span_average": 0.0, "span_max": 0.0, "live_average": 0.0, "live_max": 0.0},
+                                               "statements": {"declarations": 0.0, "assignments": 0.0, "calls": 0.0, "control_flow": 0.0, "returns": 0.0, "loops": 0.0, "total": 0.0},
+                                               "naming": {"identifiers": 0.0, "average_length": null, "violations": 0.0}},
                                    "name": "foo",
+                                   "qualified_name": "test.foo",
                                    "spaces": []}]}
         });
 
@@ -712,6 +797,7 @@ mod tests {
                 file_name: "test.py".to_string(),
                 code: "def foo():\n    pass\n".to_string(),
                 unit: true,
+                metrics: MetricSet::default(),
             })
             .to_request();
 
@@ -720,8 +806,10 @@ mod tests {
             "id": "1234",
             "language": "python",
             "spaces": {"kind": "unit",
+                       "qualified_name": "test",
                        "start_line": 1,
                        "end_line": 2,
+                       "start_column": 1, "end_column": 9, "start_byte": 0, "end_byte": 20, "name_range": null,
                        "metrics": {"cyclomatic": {"sum": 2.0, "average": 1.0, "min":1.0, "max":1.0},
                                    "cognitive": {"sum": 0.0, "average": 0.0 , "min":0.0, "max":0.0},
                                    "nargs": {"total_functions": 0.0, "average_functions": 0.0, "total_closures": 0.0, "average_closures": 0.0, "total": 0.0, "average": 0.0, "closures_max": 0.0, "closures_min": 0.0, "functions_max": 0.0, "functions_min": 0.0},
@@ -745,7 +833,51 @@ mod tests {
                                    "mi": {"mi_original": 151.203_315_883_223_2,
                                           "mi_sei": 142.643_061_717_489_76,
                                           "mi_visual_studio": 88.422_991_744_574_97},
-                                   "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0}},
+                                   "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0},
+                                   "markers": {"todo": 0.0, "fixme": 0.0, "hack": 0.0, "xxx": 0.0, "total": 0.0},
+                                   "doc_coverage": {"documented": 0.0, "total": 1.0, "coverage": 0.0},
+                                   "exceptions": {"tries": 0.0, "catches": 0.0, "throws": 0.0, "catch_alls": 0.0, "empty_catches": 0.0},
+                                   "literals": {"magic_numbers": 0.0, "repeated_strings": 0.0},
+                                   "booleans": {"max_operands": 0.0, "complex_conditions": 0.0, "max_negation_depth": 0.0},
+                                   "imports": {"static": 0.0, "dynamic": 0.0, "total": 0.0},
+                                   "variables": {"span_average": 0.0, "span_max": 0.0, "live_average": 0.0, "live_max": 0.0},
+                                   "statements": {"declarations": 0.0, "assignments": 0.0, "calls": 0.0, "control_flow": 0.0, "returns": 0.0, "loops": 0.0, "total": 0.0},
+                                   "naming": {"identifiers": 1.0, "average_length": 3.0, "violations": 0.0}},
+                       "name": "test.py",
+                       "spaces": []}
+        });
+
+        assert_eq!(res, expected);
+    }
+
+    #[actix_rt::test]
+    async fn test_web_metrics_json_selection() {
+        let app = test::init_service(
+            App::new().service(web::resource("/metrics").route(web::post().to(metrics_json))),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/metrics")
+            .set_json(json!({
+                "id": "1234",
+                "file_name": "test.py",
+                "code": "def foo():\n    pass\n",
+                "unit": true,
+                "metrics": ["loc", "cyclomatic"],
+            }))
+            .to_request();
+
+        let res: Value = test::call_and_read_body_json(&app, req).await;
+        let expected = json!({
+            "id": "1234",
+            "language": "python",
+            "spaces": {"kind": "unit",
+                       "qualified_name": "test",
+                       "start_line": 1,
+                       "end_line": 2,
+                       "start_column": 1, "end_column": 9, "start_byte": 0, "end_byte": 20, "name_range": null,
+                       "metrics": {"cyclomatic": {"sum": 2.0, "average": 1.0, "min":1.0, "max":1.0},
+                                   "loc": {"cloc": 0.0, "ploc": 2.0, "lloc": 1.0, "sloc": 2.0, "blank": 0.0, "cloc_average": 0.0, "ploc_average": 1.0, "lloc_average": 0.5, "sloc_average": 1.0, "blank_average": 0.0, "cloc_min": 0.0, "ploc_min": 2.0, "lloc_min": 1.0, "sloc_min": 2.0, "blank_min": 0.0, "cloc_max": 0.0, "ploc_max": 2.0, "lloc_max": 1.0, "sloc_max": 2.0, "blank_max": 0.0}},
                        "name": "test.py",
                        "spaces": []}
         });
@@ -753,6 +885,25 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    #[actix_rt::test]
+    async fn test_web_metrics_plain_invalid_selection() {
+        let app = test::init_service(
+            App::new().service(web::resource("/metrics").route(web::post().to(metrics_plain))),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/metrics?file_name=test.py&metrics=loc,foo")
+            .insert_header(ContentType::plaintext())
+            .set_payload("def foo():\n    pass\n")
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let res = test::read_body(resp).await;
+        assert_eq!(res, Bytes::from_static(b"error: Unknown metric `foo`"));
+    }
+
     #[actix_rt::test]
     async fn test_web_metrics_plain() {
         let app = test::init_service(
@@ -770,8 +921,10 @@ mod tests {
             "id": "",
             "language": "python",
             "spaces": {"kind": "unit",
+                       "qualified_name": "test",
                        "start_line": 1,
                        "end_line": 2,
+                       "start_column": 1, "end_column": 9, "start_byte": 0, "end_byte": 20, "name_range": null,
                        "metrics": {"cyclomatic": {"sum": 2.0, "average": 1.0, "min": 1.0,"max": 1.0},
                                    "cognitive": {"sum": 0.0, "average": 0.0 , "min":0.0, "max":0.0},
                                    "nargs": {"total_functions": 0.0, "average_functions": 0.0, "total_closures": 0.0, "average_closures": 0.0, "total": 0.0, "average": 0.0, "closures_max": 0.0, "closures_min": 0.0, "functions_max": 0.0, "functions_min": 0.0},
@@ -795,11 +948,22 @@ mod tests {
                                    "mi": {"mi_original": 151.203_315_883_223_2,
                                           "mi_sei": 142.643_061_717_489_76,
                                           "mi_visual_studio": 88.422_991_744_574_97},
-                                   "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0}},
+                                   "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0},
+                                   "markers": {"todo": 0.0, "fixme": 0.0, "hack": 0.0, "xxx": 0.0, "total": 0.0},
+                                   "doc_coverage": {"documented": 0.0, "total": 1.0, "coverage": 0.0},
+                                   "exceptions": {"tries": 0.0, "catches": 0.0, "throws": 0.0, "catch_alls": 0.0, "empty_catches": 0.0},
+                                   "literals": {"magic_numbers": 0.0, "repeated_strings": 0.0},
+                                   "booleans": {"max_operands": 0.0, "complex_conditions": 0.0, "max_negation_depth": 0.0},
+                                   "imports": {"static": 0.0, "dynamic": 0.0, "total": 0.0},
+                                   "variables": {"span_average": 0.0, "span_max": 0.0, "live_average": 0.0, "live_max": 0.0},
+                                   "statements": {"declarations": 0.0, "assignments": 0.0, "calls": 0.0, "control_flow": 0.0, "returns": 0.0, "loops": 0.0, "total": 0.0},
+                                   "naming": {"identifiers": 1.0, "average_length": 3.0, "violations": 0.0}},
                        "name": "test.py",
                        "spaces": [{"kind": "function",
                                    "start_line": 1,
                                    "end_line": 2,
+                                   "start_column": 1, "end_column": 9, "start_byte": 0, "end_byte": 19,
+                                   "name_range": {"start_line": 1, "end_line": 1, "start_column": 5, "end_column": 8, "start_byte": 4, "end_byte": 7},
                                    "metrics": {"cyclomatic": {"sum": 1.0, "average": 1.0, "min": 1.0,"max": 1.0},
                                                "cognitive": {"sum": 0.0, "average": 0.0 , "min":0.0, "max":0.0},
                                                "nargs": {"total_functions": 0.0, "average_functions": 0.0, "total_closures": 0.0, "average_closures": 0.0, "total": 0.0, "average": 0.0, "closures_max": 0.0, "closures_min": 0.0, "functions_max": 0.0, "functions_min": 0.0},
@@ -823,8 +987,18 @@ mod tests {
                                                "mi": {"mi_original": 151.433_315_883_223_23,
                                                       "mi_sei": 142.873_061_717_489_78,
                                                       "mi_visual_studio": 88.557_494_668_551_6},
-                                               "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0}},
+                                               "abc": {"assignments": 0.0, "branches": 0.0, "conditions": 0.0, "magnitude": 0.0, "assignments_average": 0.0, "branches_average": 0.0, "conditions_average": 0.0, "assignments_min": 0.0, "assignments_max": 0.0, "branches_min": 0.0, "branches_max": 0.0, "conditions_min": 0.0, "conditions_max": 0.0},
+                                               "markers": {"todo": 0.0, "fixme": 0.0, "hack": 0.0, "xxx": 0.0, "total": 0.0},
+                                               "doc_coverage": {"documented": 0.0, "total": 1.0, "coverage": 0.0},
+                                               "exceptions": {"tries": 0.0, "catches": 0.0, "throws": 0.0, "catch_alls": 0.0, "empty_catches": 0.0},
+                                               "literals": {"magic_numbers": 0.0, "repeated_strings": 0.0},
+                                               "booleans": {"max_operands": 0.0, "complex_conditions": 0.0, "max_negation_depth": 0.0},
+                                               "imports": {"static": 0.0, "dynamic": 0.0, "total": 0.0},
+                                               "variables": {"span_average": 0.0, "span_max": 0.0, "live_average": 0.0, "live_max": 0.0},
+                                               "statements": {"declarations": 0.0, "assignments": 0.0, "calls": 0.0, "control_flow": 0.0, "returns": 0.0, "loops": 0.0, "total": 0.0},
+                                               "naming": {"identifiers": 0.0, "average_length": null, "violations": 0.0}},
                                    "name": "foo",
+                                   "qualified_name": "test.foo",
                                    "spaces": []}]}
         });
 
@@ -901,4 +1075,59 @@ mod tests {
 
         assert_eq!(res, expected);
     }
+
+    #[actix_rt::test]
+    async fn test_web_query_json() {
+        let app = test::init_service(
+            App::new().service(web::resource("/query").route(web::post().to(query_json))),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/query")
+            .set_json(WebQueryPayload {
+                id: "1234".to_string(),
+                file_name: "test.py".to_string(),
+                code: "if a:\n    pass\nelif b:\n    pass".to_string(),
+                query: "(if_statement condition: (_) @condition)".to_string(),
+            })
+            .to_request();
+
+        let res: Value = test::call_and_read_body_json(&app, req).await;
+        let expected = json!({
+            "id": "1234",
+            "captures": [
+                {
+                    "name": "condition",
+                    "kind": "identifier",
+                    "start_line": 1,
+                    "start_column": 4,
+                    "end_line": 1,
+                    "end_column": 5,
+                    "text": "a"
+                }
+            ]
+        });
+
+        assert_eq!(res, expected);
+    }
+
+    #[actix_rt::test]
+    async fn test_web_query_invalid() {
+        let app = test::init_service(
+            App::new().service(web::resource("/query").route(web::post().to(query_json))),
+        )
+        .await;
+        let req = test::TestRequest::post()
+            .uri("/query")
+            .set_json(WebQueryPayload {
+                id: "1234".to_string(),
+                file_name: "test.py".to_string(),
+                code: "pass".to_string(),
+                query: "(foo_statement)".to_string(),
+            })
+            .to_request();
+
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
 }
@@ -16,9 +16,12 @@ where
     }
 
     /// Gets the code as text and the span associated to a node.
+    ///
+    /// The invalid UTF-8 sequences of the code are replaced
+    /// by `U+FFFD REPLACEMENT CHARACTER`.
     fn get_text_span(node: &Node, code: &[u8], span: bool, text: bool) -> (String, Span) {
         let text = if text {
-            String::from_utf8(code[node.start_byte()..node.end_byte()].to_vec()).unwrap()
+            String::from_utf8_lossy(&code[node.start_byte()..node.end_byte()]).into_owned()
         } else {
             "".to_string()
         };
@@ -15,7 +15,7 @@ mod tests {
         let path = PathBuf::from("foo.c");
         for (n, sample) in samples.iter().enumerate() {
             let v_sample = sample.as_bytes().to_vec();
-            let parser = CppParser::new(v_sample.clone(), &path, None);
+            let parser = CppParser::new(v_sample.clone(), &path, None).unwrap();
             let root = parser.get_root();
             if debug || root.has_error() {
                 eprintln!("Sample (CPP) {n}: {sample}");
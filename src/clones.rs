@@ -0,0 +1,540 @@
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::checker::Checker;
+use crate::error::Error;
+use crate::spaces::FuncSpace;
+
+use crate::traits::*;
+
+/// A file added to a clone detector.
+#[derive(Debug, Clone)]
+struct File {
+    path: PathBuf,
+    // The start byte and the hash of the text of each token
+    leaves: Vec<(usize, u64)>,
+}
+
+/// A candidate clone: a subtree large enough to be reported.
+#[derive(Debug, Clone)]
+struct Fragment {
+    file: usize,
+    start_line: usize,
+    end_line: usize,
+    start_byte: usize,
+    end_byte: usize,
+    tokens: usize,
+    // The hash of the nearest ancestor which is a candidate too
+    parent: Option<u64>,
+}
+
+/// Searches for code clones across a set of files.
+///
+/// Each subtree of a file is hashed after having abstracted away
+/// its identifiers and literals, so subtrees with the same hash have
+/// the same structure, differing at most in names and values
+/// (`Type-2` clones). Comments are ignored, and subtrees
+/// of different languages are never clones.
+///
+/// Files are added one by one, possibly from several threads through the
+/// [`Callback`] implementation, then the clones are retrieved
+/// with [`clones`](Self::clones).
+///
+/// [`Callback`]: trait.Callback.html
+#[derive(Debug)]
+pub struct CloneDetector {
+    min_lines: usize,
+    min_tokens: usize,
+    files: Vec<File>,
+    fragments: HashMap<u64, Vec<Fragment>>,
+}
+
+impl Default for CloneDetector {
+    fn default() -> Self {
+        Self::new(6, 50)
+    }
+}
+
+impl CloneDetector {
+    /// Creates a new clone detector which reports only the clones
+    /// having at least `min_lines` lines and `min_tokens` tokens.
+    pub fn new(min_lines: usize, min_tokens: usize) -> Self {
+        Self {
+            min_lines: min_lines.max(1),
+            min_tokens: min_tokens.max(1),
+            files: Vec::new(),
+            fragments: HashMap::new(),
+        }
+    }
+
+    /// Adds the code of a file to the detector.
+    pub fn add<T: ParserTrait>(&mut self, parser: &T, path: &Path) {
+        let fragments = fingerprint(parser, self.min_lines, self.min_tokens);
+        self.insert(path, leaves(parser), fragments);
+    }
+
+    fn insert(&mut self, path: &Path, leaves: Vec<(usize, u64)>, fragments: Vec<(u64, Fragment)>) {
+        let file = self.files.len();
+        self.files.push(File {
+            path: path.to_path_buf(),
+            leaves,
+        });
+        for (hash, mut fragment) in fragments {
+            fragment.file = file;
+            self.fragments.entry(hash).or_default().push(fragment);
+        }
+    }
+
+    /// Returns the groups of clones found in the added files.
+    ///
+    /// The groups are sorted by decreasing size, then by the position
+    /// of their first fragment.
+    pub fn clones(&self) -> Clones {
+        let is_clone = |hash: &u64| self.fragments.get(hash).is_some_and(|f| f.len() > 1);
+
+        // A group is not reported when all its fragments are contained
+        // in the fragments of a larger group
+        let mut groups: Vec<Vec<&Fragment>> = self
+            .fragments
+            .values()
+            .filter(|fragments| fragments.len() > 1)
+            .filter(|fragments| {
+                !fragments
+                    .iter()
+                    .all(|f| f.parent.as_ref().is_some_and(is_clone))
+            })
+            .map(|fragments| {
+                let mut fragments: Vec<_> = fragments.iter().collect();
+                fragments.sort_by(|a, b| self.position(a).cmp(&self.position(b)));
+                fragments
+            })
+            .collect();
+        groups.sort_by(|a, b| {
+            b[0].tokens
+                .cmp(&a[0].tokens)
+                .then_with(|| self.position(a[0]).cmp(&self.position(b[0])))
+        });
+
+        let groups = groups
+            .into_iter()
+            .map(|fragments| {
+                let instances = fragments
+                    .iter()
+                    .map(|f| CloneInstance {
+                        path: self.files[f.file].path.clone(),
+                        start_line: f.start_line,
+                        end_line: f.end_line,
+                    })
+                    .collect();
+
+                let first = self.tokens_of(fragments[0]);
+                let similarity = fragments[1..]
+                    .iter()
+                    .filter_map(|f| similarity(&first, &self.tokens_of(f)))
+                    .reduce(f64::min);
+
+                CloneGroup {
+                    tokens: fragments[0].tokens,
+                    lines: fragments[0].end_line - fragments[0].start_line + 1,
+                    similarity,
+                    instances,
+                }
+            })
+            .collect();
+
+        Clones { groups }
+    }
+
+    // The position of a fragment, used to sort the clones
+    #[inline(always)]
+    fn position(&self, fragment: &Fragment) -> (&Path, usize, usize) {
+        let path = self.files[fragment.file].path.as_path();
+        (path, fragment.start_line, fragment.start_byte)
+    }
+
+    // Retrieves the hashes of the tokens of a fragment
+    fn tokens_of(&self, fragment: &Fragment) -> Vec<u64> {
+        let leaves = &self.files[fragment.file].leaves;
+        let first = leaves.partition_point(|(pos, _)| *pos < fragment.start_byte);
+        let last = leaves.partition_point(|(pos, _)| *pos < fragment.end_byte);
+        leaves[first..last].iter().map(|(_, h)| *h).collect()
+    }
+}
+
+// The share of tokens which are equal in the same position,
+// if there are tokens to compare
+fn similarity(first: &[u64], second: &[u64]) -> Option<f64> {
+    let len = first.len().max(second.len());
+    if len == 0 {
+        return None;
+    }
+    let equal = first.iter().zip(second).filter(|(a, b)| a == b).count();
+    Some(equal as f64 / len as f64)
+}
+
+struct Frame {
+    hasher: DefaultHasher,
+    tokens: usize,
+    children: usize,
+    // The nearest candidates contained in a node, waiting for their parent
+    pending: Vec<usize>,
+}
+
+// Computes the normalized hash of each subtree and returns
+// the ones large enough to be reported as clones.
+fn fingerprint<T: ParserTrait>(
+    parser: &T,
+    min_lines: usize,
+    min_tokens: usize,
+) -> Vec<(u64, Fragment)> {
+    let lang = parser.get_language();
+    let root = parser.get_root();
+    let mut cursor = root.cursor();
+    let mut stack = vec![(root, false)];
+    let mut children = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut fragments: Vec<(u64, Fragment)> = Vec::new();
+
+    while let Some((node, visited)) = stack.pop() {
+        if visited {
+            let frame = frames.pop().unwrap();
+            let hash = frame.hasher.finish();
+            let tokens = frame.tokens.max(1);
+            let mut pending = frame.pending;
+            let (start_line, end_line) = (node.start_row() + 1, node.end_row() + 1);
+
+            // A node wrapping a single node, e.g. a file containing only a function,
+            // is represented by its child
+            if node.is_named()
+                && frame.children != 1
+                && tokens >= min_tokens
+                && end_line - start_line + 1 >= min_lines
+            {
+                for i in pending.drain(..) {
+                    fragments[i].1.parent = Some(hash);
+                }
+                pending.push(fragments.len());
+                fragments.push((
+                    hash,
+                    Fragment {
+                        file: 0,
+                        start_line,
+                        end_line,
+                        start_byte: node.start_byte(),
+                        end_byte: node.end_byte(),
+                        tokens,
+                        parent: None,
+                    },
+                ));
+            }
+
+            if let Some(parent) = frames.last_mut() {
+                parent.hasher.write_u64(hash);
+                parent.tokens += tokens;
+                parent.children += 1;
+                parent.pending.append(&mut pending);
+            }
+            continue;
+        }
+
+        if T::Checker::is_comment(&node) {
+            continue;
+        }
+
+        // Only the kind of a node is hashed, so identifiers
+        // and literals are abstracted away. The language is hashed too,
+        // since the same kind can belong to several grammars
+        let mut hasher = DefaultHasher::new();
+        lang.hash(&mut hasher);
+        node.kind_id().hash(&mut hasher);
+        frames.push(Frame {
+            hasher,
+            tokens: 0,
+            children: 0,
+            pending: Vec::new(),
+        });
+        stack.push((node, true));
+
+        cursor.reset(&node);
+        if cursor.goto_first_child() {
+            loop {
+                children.push((cursor.node(), false));
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+            for child in children.drain(..).rev() {
+                stack.push(child);
+            }
+        }
+    }
+
+    fragments
+}
+
+// Returns the start byte and the hash of the text of each token of a code,
+// comments excluded.
+fn leaves<T: ParserTrait>(parser: &T) -> Vec<(usize, u64)> {
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let mut cursor = root.cursor();
+    let mut stack = vec![root];
+    let mut children = Vec::new();
+    let mut leaves = Vec::new();
+
+    while let Some(node) = stack.pop() {
+        if T::Checker::is_comment(&node) {
+            continue;
+        }
+        if node.child_count() == 0 {
+            let mut hasher = DefaultHasher::new();
+            code[node.start_byte()..node.end_byte()].hash(&mut hasher);
+            leaves.push((node.start_byte(), hasher.finish()));
+            continue;
+        }
+        cursor.reset(&node);
+        if cursor.goto_first_child() {
+            loop {
+                children.push(cursor.node());
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+            for child in children.drain(..).rev() {
+                stack.push(child);
+            }
+        }
+    }
+
+    leaves
+}
+
+/// Configuration options for adding a file to a clone detector.
+#[derive(Debug)]
+pub struct ClonesCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+    /// The clone detector shared among threads
+    pub detector: Arc<Mutex<CloneDetector>>,
+}
+
+impl Callback for CloneDetector {
+    type Res = Result<(), Error>;
+    type Cfg = ClonesCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        let (min_lines, min_tokens) = {
+            let detector = cfg.detector.lock().unwrap();
+            (detector.min_lines, detector.min_tokens)
+        };
+        // The code is hashed outside the lock, so files are processed in parallel
+        let fragments = fingerprint(parser, min_lines, min_tokens);
+        let leaves = leaves(parser);
+        cfg.detector
+            .lock()
+            .unwrap()
+            .insert(&cfg.path, leaves, fragments);
+        Ok(())
+    }
+}
+
+/// A fragment of code belonging to a group of clones.
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneInstance {
+    /// The file containing the fragment
+    pub path: PathBuf,
+    /// The first line of the fragment
+    pub start_line: usize,
+    /// The last line of the fragment
+    pub end_line: usize,
+}
+
+/// A group of fragments of code which are clones of each other.
+#[derive(Debug, Clone, Serialize)]
+pub struct CloneGroup {
+    /// The number of tokens of each fragment
+    pub tokens: usize,
+    /// The number of lines of the first fragment
+    pub lines: usize,
+    /// The lowest share of tokens equal between the first fragment and
+    /// the other ones, `1` when the fragments are identical.
+    ///
+    /// It is `None` when the fragments contain no token to compare.
+    pub similarity: Option<f64>,
+    /// The fragments of the group
+    pub instances: Vec<CloneInstance>,
+}
+
+/// All groups of clones found by a clone detector.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Clones {
+    /// The groups of clones, the largest first
+    pub groups: Vec<CloneGroup>,
+}
+
+impl Clones {
+    /// Returns the sorted and disjoint ranges of lines of a file
+    /// which belong to a clone.
+    pub fn duplicated_lines(&self, path: &Path) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<_> = self
+            .groups
+            .iter()
+            .flat_map(|group| group.instances.iter())
+            .filter(|instance| instance.path == path)
+            .map(|instance| (instance.start_line, instance.end_line))
+            .collect();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(usize, usize)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 + 1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+    }
+
+    /// Sets the `Duplication` metric of a space of a file and of its subspaces.
+    pub fn annotate(&self, path: &Path, space: &mut FuncSpace) {
+        annotate_space(&self.duplicated_lines(path), space);
+    }
+}
+
+fn annotate_space(ranges: &[(usize, usize)], space: &mut FuncSpace) {
+    let (start, end) = (space.start_line.max(1), space.end_line);
+    let duplicated = ranges
+        .iter()
+        .map(|&(s, e)| (e.min(end) + 1).saturating_sub(s.max(start)))
+        .sum();
+    space
+        .metrics
+        .duplication
+        .set(duplicated, (end + 1).saturating_sub(start));
+    for space in space.spaces.iter_mut() {
+        annotate_space(ranges, space);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::tools::trimmed_parser;
+    use crate::{JavaParser, PythonParser, RustParser, metrics};
+
+    use super::*;
+
+    // The files are not written on disk, so the clones
+    // are found from the added code only
+    fn add_file<T: ParserTrait>(
+        detector: &mut CloneDetector,
+        source: &str,
+        filename: &str,
+    ) -> PathBuf {
+        let (parser, path) = trimmed_parser::<T>(source, filename);
+        detector.add(&parser, &path);
+        path
+    }
+
+    #[test]
+    fn rust_clones_across_files() {
+        let mut detector = CloneDetector::new(3, 10);
+        let a = add_file::<RustParser>(
+            &mut detector,
+            "fn f(v: &[i32]) -> i32 {
+                 let mut sum = 0;
+                 for x in v {
+                     sum += x * 2;
+                 }
+                 sum
+             }",
+            "a.rs",
+        );
+        let b = add_file::<RustParser>(
+            &mut detector,
+            "// Same structure, other names
+             fn g(items: &[i32]) -> i32 {
+                 let mut total = 0;
+                 for item in items {
+                     total += item * 3;
+                 }
+                 total
+             }",
+            "b.rs",
+        );
+
+        let clones = detector.clones();
+        assert_eq!(clones.groups.len(), 1);
+
+        let group = &clones.groups[0];
+        assert!(group.similarity.is_some_and(|s| s > 0.5 && s < 1.));
+        assert_eq!(group.lines, 7);
+        let instances: Vec<_> = group
+            .instances
+            .iter()
+            .map(|i| (i.path.clone(), i.start_line, i.end_line))
+            .collect();
+        assert_eq!(instances, [(a, 1, 7), (b, 2, 8)]);
+    }
+
+    #[test]
+    fn python_identical_clones() {
+        let mut detector = CloneDetector::new(2, 5);
+        let code = "def f(a):
+                        if a > 0:
+                            return a + 1
+                        return 0";
+        let b = add_file::<PythonParser>(&mut detector, code, "b.py");
+        let a = add_file::<PythonParser>(&mut detector, code, "a.py");
+
+        let clones = detector.clones();
+
+        // Only the functions are reported, not the modules wrapping them
+        assert_eq!(clones.groups.len(), 1);
+        assert_eq!(clones.groups[0].similarity, Some(1.));
+        let paths: Vec<_> = clones.groups[0].instances.iter().map(|i| &i.path).collect();
+        assert_eq!(paths, [&a, &b]);
+    }
+
+    #[test]
+    fn small_fragments_are_not_clones() {
+        let mut detector = CloneDetector::default();
+        add_file::<JavaParser>(&mut detector, "class A { int f() { return 1; } }", "A.java");
+        add_file::<JavaParser>(&mut detector, "class B { int g() { return 2; } }", "B.java");
+
+        assert!(detector.clones().groups.is_empty());
+    }
+
+    #[test]
+    fn duplicated_lines_per_space() {
+        let mut detector = CloneDetector::new(3, 10);
+        let clone = "fn f(v: &[i32]) -> i32 {
+                         let mut sum = 0;
+                         for x in v {
+                             sum += x;
+                         }
+                         sum
+                     }";
+        let a = add_file::<RustParser>(&mut detector, clone, "c.rs");
+        let b_source = format!("fn g() {{}}\n{clone}");
+        let b = add_file::<RustParser>(&mut detector, &b_source, "d.rs");
+
+        let clones = detector.clones();
+        assert_eq!(clones.duplicated_lines(&a), [(1, 7)]);
+        assert_eq!(clones.duplicated_lines(&b), [(2, 8)]);
+
+        let (parser, _) = trimmed_parser::<RustParser>(&b_source, "d.rs");
+        let mut space = metrics(&parser, &b).unwrap();
+        clones.annotate(&b, &mut space);
+
+        assert_eq!(space.metrics.duplication.duplicated_lines(), 7.);
+        assert_eq!(space.spaces[0].metrics.duplication.duplicated_lines(), 0.);
+        assert_eq!(space.spaces[1].metrics.duplication.duplicated_lines(), 7.);
+        assert_eq!(space.spaces[1].metrics.duplication.ratio(), 1.);
+    }
+}
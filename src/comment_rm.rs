@@ -2,26 +2,34 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 
 use crate::checker::Checker;
+use crate::document::InputEdit;
+use crate::error::Error;
+use crate::rewrite::apply_edits;
 
 use crate::tools::*;
 use crate::traits::*;
 
-const CR: [u8; 8192] = [b'\n'; 8192];
-
 /// Removes comments from a code.
-pub fn rm_comments<T: ParserTrait>(parser: &T) -> Option<Vec<u8>> {
+///
+/// Returns `None` if the code contains no comment to remove.
+pub fn rm_comments<T: ParserTrait>(parser: &T) -> Result<Option<Vec<u8>>, Error> {
     let node = parser.get_root();
     let mut stack = Vec::new();
     let mut cursor = node.cursor();
-    let mut spans = Vec::new();
+    let mut edits = Vec::new();
 
     stack.push(node);
 
     while let Some(node) = stack.pop() {
         if T::Checker::is_comment(&node) && !T::Checker::is_useful_comment(&node, parser.get_code())
         {
+            // Keep the lines of the comment
             let lines = node.end_row() - node.start_row();
-            spans.push((node.start_byte(), node.end_byte(), lines));
+            edits.push(InputEdit {
+                start_byte: node.start_byte(),
+                old_end_byte: node.end_byte(),
+                text: "\n".repeat(lines),
+            });
         } else {
             cursor.reset(&node);
             if cursor.goto_first_child() {
@@ -34,31 +42,11 @@ pub fn rm_comments<T: ParserTrait>(parser: &T) -> Option<Vec<u8>> {
             }
         }
     }
-    if !spans.is_empty() {
-        Some(remove_from_code(parser.get_code(), spans))
+    if !edits.is_empty() {
+        apply_edits(parser.get_code(), &edits).map(Some)
     } else {
-        None
-    }
-}
-
-fn remove_from_code(code: &[u8], mut spans: Vec<(usize, usize, usize)>) -> Vec<u8> {
-    let mut new_code = Vec::with_capacity(code.len());
-    let mut code_start = 0;
-    for (start, end, lines) in spans.drain(..).rev() {
-        new_code.extend(&code[code_start..start]);
-        if lines != 0 {
-            if lines <= CR.len() {
-                new_code.extend(&CR[..lines]);
-            } else {
-                new_code.resize_with(new_code.len() + lines, || b'\n');
-            }
-        }
-        code_start = end;
-    }
-    if code_start < code.len() {
-        new_code.extend(&code[code_start..]);
+        Ok(None)
     }
-    new_code
 }
 
 /// Configuration options for removing comments from a code.
@@ -75,11 +63,11 @@ pub struct CommentRm {
 }
 
 impl Callback for CommentRm {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = CommentRmCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        if let Some(new_source) = rm_comments(parser) {
+        if let Some(new_source) = rm_comments(parser)? {
             if cfg.in_place {
                 write_file(&cfg.path, &new_source)?;
             } else if let Ok(new_source) = std::str::from_utf8(&new_source) {
@@ -123,9 +111,9 @@ mod tests {
         let path = PathBuf::from("foo.c");
         let mut trimmed_bytes = SOURCE_CODE.as_bytes().to_vec();
         trimmed_bytes.push(b'\n');
-        let parser = CcommentParser::new(trimmed_bytes, &path, None);
+        let parser = CcommentParser::new(trimmed_bytes, &path, None).unwrap();
 
-        let no_comments = rm_comments(&parser).unwrap();
+        let no_comments = rm_comments(&parser).unwrap().unwrap();
 
         assert_eq!(no_comments.as_slice(), SOURCE_CODE_NO_COMMENTS.as_bytes());
     }
@@ -7,7 +7,9 @@ use crossbeam::channel::{Receiver, Sender, unbounded};
 use globset::GlobSet;
 use walkdir::{DirEntry, WalkDir};
 
-type ProcFilesFunction<Config> = dyn Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync;
+use crate::error::Error;
+
+type ProcFilesFunction<Config> = dyn Fn(PathBuf, &Config) -> Result<(), Error> + Send + Sync;
 
 type ProcDirPathsFunction<Config> =
     dyn Fn(&mut HashMap<String, Vec<PathBuf>>, &Path, &Config) + Send + Sync;
@@ -29,7 +31,7 @@ type JobSender<Config> = Sender<Option<JobItem<Config>>>;
 
 fn consumer<Config, ProcFiles>(receiver: JobReceiver<Config>, func: Arc<ProcFiles>)
 where
-    ProcFiles: Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync,
+    ProcFiles: Fn(PathBuf, &Config) -> Result<(), Error> + Send + Sync,
 {
     while let Ok(job) = receiver.recv() {
         if job.is_none() {
@@ -40,7 +42,7 @@ where
         let path = job.path.clone();
 
         if let Err(err) = func(job.path, &job.cfg) {
-            eprintln!("{err:?} for file {path:?}");
+            eprintln!("{err} for file {path:?}");
         }
     }
 }
@@ -168,7 +170,7 @@ impl<Config: 'static + Send + Sync> ConcurrentRunner<Config> {
     ///   the search.
     pub fn new<ProcFiles>(num_jobs: usize, proc_files: ProcFiles) -> Self
     where
-        ProcFiles: 'static + Fn(PathBuf, &Config) -> std::io::Result<()> + Send + Sync,
+        ProcFiles: 'static + Fn(PathBuf, &Config) -> Result<(), Error> + Send + Sync,
     {
         let num_jobs = std::cmp::max(2, num_jobs) - 1;
         Self {
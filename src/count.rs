@@ -4,6 +4,8 @@ use num_format::{Locale, ToFormattedString};
 use std::fmt;
 use std::sync::{Arc, Mutex};
 
+use crate::error::Error;
+use crate::query::*;
 use crate::traits::*;
 
 /// Counts the types of nodes specified in the input slice
@@ -42,6 +44,10 @@ pub fn count<T: ParserTrait>(parser: &T, filters: &[String]) -> (usize, usize) {
 pub struct CountCfg {
     /// Types of nodes to count
     pub filters: Vec<String>,
+    /// The tree-sitter query capturing the nodes to count, replacing the filters
+    ///
+    /// The query must be compiled for the language of the code
+    pub query: Option<Arc<Query>>,
     /// Number of nodes of a certain type counted by each thread
     pub stats: Arc<Mutex<Count>>,
 }
@@ -56,11 +62,14 @@ pub struct Count {
 }
 
 impl Callback for Count {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = CountCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        let (good, total) = count(parser, &cfg.filters);
+        let (good, total) = match &cfg.query {
+            Some(query) => count_query(parser, query)?,
+            None => count(parser, &cfg.filters),
+        };
         let mut results = cfg.stats.lock().unwrap();
         results.good += good;
         results.total += total;
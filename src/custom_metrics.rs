@@ -0,0 +1,450 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use serde::Serialize;
+use serde::ser::{SerializeMap, Serializer};
+
+use crate::error::Error;
+use crate::langs::LANG;
+use crate::node::Node;
+
+/// A metric defined outside of this crate.
+///
+/// A metric is computed on every node of a space during the traversal
+/// of the code made by [`metrics_with_options`]. When a space ends,
+/// its stats are finalized and merged into the stats of the space
+/// containing it, as done for the metrics of this crate.
+///
+/// The metrics are registered in a [`MetricRegistry`] and their stats
+/// are stored in the [`CodeMetrics::custom`] field.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{
+///     metrics_with_options, Metric, MetricRegistry, MetricsOptions, Node, ParserTrait,
+///     PythonParser, LANG,
+/// };
+///
+/// // Counts the string literals of a space
+/// struct Strings;
+///
+/// impl Metric for Strings {
+///     type Stats = usize;
+///
+///     fn name(&self) -> &'static str {
+///         "strings"
+///     }
+///
+///     fn compute(&self, node: &Node, _code: &[u8], _lang: LANG, stats: &mut usize) {
+///         if node.kind() == "string" {
+///             *stats += 1;
+///         }
+///     }
+///
+///     fn merge(&self, stats: &mut usize, other: &usize) {
+///         *stats += *other;
+///     }
+/// }
+///
+/// let source_code = "def f():\n    return 'a' + 'b'";
+/// let path = Path::new("foo.py");
+/// let parser = PythonParser::new(source_code.as_bytes().to_vec(), &path, None).unwrap();
+///
+/// let mut registry = MetricRegistry::new();
+/// registry.register(Strings).unwrap();
+/// let options = MetricsOptions {
+///     registry,
+///     ..MetricsOptions::default()
+/// };
+///
+/// let space = metrics_with_options(&parser, &path, &options).unwrap();
+/// assert_eq!(space.metrics.custom.get::<usize>("strings"), Some(&2));
+/// ```
+///
+/// [`metrics_with_options`]: fn.metrics_with_options.html
+/// [`MetricRegistry`]: struct.MetricRegistry.html
+/// [`CodeMetrics::custom`]: struct.CodeMetrics.html#structfield.custom
+pub trait Metric: Send + Sync + 'static {
+    /// The stats of a space
+    type Stats: Clone + fmt::Debug + Default + Serialize + Send + Sync + 'static;
+
+    /// Returns the name of the metric, used as its key in the output
+    fn name(&self) -> &'static str;
+
+    /// Computes the metric on a node of a space
+    fn compute(&self, node: &Node, code: &[u8], lang: LANG, stats: &mut Self::Stats);
+
+    /// Finalizes the stats of a space, once all its nodes
+    /// and subspaces have been visited
+    fn finalize(&self, _stats: &mut Self::Stats) {}
+
+    /// Merges the stats of a subspace into the stats of its parent space
+    fn merge(&self, stats: &mut Self::Stats, other: &Self::Stats);
+}
+
+// An object-safe version of the `Metric` trait, used to store
+// metrics with different stats in the same registry
+trait DynMetric: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn new_stats(&self) -> Box<dyn Any + Send + Sync>;
+    fn clone_stats(&self, stats: &dyn Any) -> Box<dyn Any + Send + Sync>;
+    fn compute(&self, node: &Node, code: &[u8], lang: LANG, stats: &mut dyn Any);
+    fn finalize(&self, stats: &mut dyn Any);
+    fn merge(&self, stats: &mut dyn Any, other: &dyn Any);
+    fn serialize_stats<'a>(&self, stats: &'a dyn Any) -> &'a dyn erased_serde::Serialize;
+    fn fmt_stats(&self, stats: &dyn Any, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+// The stats of a metric are only created by the metric itself,
+// so the downcasts cannot fail
+fn stats_ref<M: Metric>(stats: &dyn Any) -> &M::Stats {
+    stats.downcast_ref().unwrap()
+}
+
+fn stats_mut<M: Metric>(stats: &mut dyn Any) -> &mut M::Stats {
+    stats.downcast_mut().unwrap()
+}
+
+impl<M: Metric> DynMetric for M {
+    fn name(&self) -> &'static str {
+        Metric::name(self)
+    }
+
+    fn new_stats(&self) -> Box<dyn Any + Send + Sync> {
+        Box::new(M::Stats::default())
+    }
+
+    fn clone_stats(&self, stats: &dyn Any) -> Box<dyn Any + Send + Sync> {
+        Box::new(stats_ref::<M>(stats).clone())
+    }
+
+    fn compute(&self, node: &Node, code: &[u8], lang: LANG, stats: &mut dyn Any) {
+        Metric::compute(self, node, code, lang, stats_mut::<M>(stats));
+    }
+
+    fn finalize(&self, stats: &mut dyn Any) {
+        Metric::finalize(self, stats_mut::<M>(stats));
+    }
+
+    fn merge(&self, stats: &mut dyn Any, other: &dyn Any) {
+        Metric::merge(self, stats_mut::<M>(stats), stats_ref::<M>(other));
+    }
+
+    fn serialize_stats<'a>(&self, stats: &'a dyn Any) -> &'a dyn erased_serde::Serialize {
+        stats_ref::<M>(stats)
+    }
+
+    fn fmt_stats(&self, stats: &dyn Any, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", stats_ref::<M>(stats))
+    }
+}
+
+/// A set of metrics defined outside of this crate.
+///
+/// The registered metrics are computed by [`metrics_with_options`]
+/// when the registry is set in the [`MetricsOptions::registry`] field.
+///
+/// [`metrics_with_options`]: fn.metrics_with_options.html
+/// [`MetricsOptions::registry`]: struct.MetricsOptions.html#structfield.registry
+#[derive(Clone, Default)]
+pub struct MetricRegistry {
+    metrics: Vec<Arc<dyn DynMetric>>,
+}
+
+impl fmt::Debug for MetricRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.metrics.iter().map(|metric| metric.name()))
+            .finish()
+    }
+}
+
+impl MetricRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a metric.
+    ///
+    /// Returns an error if a metric with the same name
+    /// has already been registered.
+    pub fn register<M: Metric>(&mut self, metric: M) -> Result<(), Error> {
+        let name = Metric::name(&metric);
+        if self.metrics.iter().any(|metric| metric.name() == name) {
+            return Err(Error::InvalidInput(format!(
+                "The metric `{name}` is already registered"
+            )));
+        }
+        self.metrics.push(Arc::new(metric));
+        Ok(())
+    }
+
+    /// Returns the names of the registered metrics.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.metrics.iter().map(|metric| metric.name())
+    }
+
+    /// Checks whether no metric has been registered.
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+
+    // Creates the stats of the registered metrics for a new space
+    pub(crate) fn new_stats(&self) -> CustomMetrics {
+        CustomMetrics {
+            stats: self
+                .metrics
+                .iter()
+                .map(|metric| {
+                    (
+                        metric.name(),
+                        CustomStats {
+                            metric: Arc::clone(metric),
+                            stats: metric.new_stats(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+struct CustomStats {
+    metric: Arc<dyn DynMetric>,
+    stats: Box<dyn Any + Send + Sync>,
+}
+
+impl Clone for CustomStats {
+    fn clone(&self) -> Self {
+        Self {
+            metric: Arc::clone(&self.metric),
+            stats: self.metric.clone_stats(self.stats.as_ref()),
+        }
+    }
+}
+
+impl CustomStats {
+    fn to_value(&self) -> serde_json::Value {
+        serde_json::to_value(self.metric.serialize_stats(self.stats.as_ref())).unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for CustomStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.metric.fmt_stats(self.stats.as_ref(), f)
+    }
+}
+
+/// The stats of the metrics defined outside of this crate,
+/// indexed by the names of the metrics.
+#[derive(Clone, Debug, Default)]
+pub struct CustomMetrics {
+    stats: BTreeMap<&'static str, CustomStats>,
+}
+
+impl Serialize for CustomMetrics {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.stats.len()))?;
+        for (name, custom) in &self.stats {
+            map.serialize_entry(name, custom.metric.serialize_stats(custom.stats.as_ref()))?;
+        }
+        map.end()
+    }
+}
+
+impl CustomMetrics {
+    /// Returns the stats of a metric, if the metric has been registered
+    /// and its stats have the given type.
+    pub fn get<S: 'static>(&self, name: &str) -> Option<&S> {
+        self.stats
+            .get(name)
+            .and_then(|custom| custom.stats.downcast_ref())
+    }
+
+    /// Returns the serialized stats of a metric,
+    /// if the metric has been registered.
+    pub fn value(&self, name: &str) -> Option<serde_json::Value> {
+        self.stats.get(name).map(CustomStats::to_value)
+    }
+
+    /// Returns the names of the metrics with their serialized stats.
+    pub fn values(&self) -> impl Iterator<Item = (&'static str, serde_json::Value)> + '_ {
+        self.stats
+            .iter()
+            .map(|(name, custom)| (*name, custom.to_value()))
+    }
+
+    /// Checks whether there are no stats.
+    pub fn is_empty(&self) -> bool {
+        self.stats.is_empty()
+    }
+
+    /// Merges the stats of a subspace into the stats of its parent space
+    pub fn merge(&mut self, other: &CustomMetrics) {
+        for (name, other) in &other.stats {
+            if let Some(custom) = self.stats.get_mut(name) {
+                custom
+                    .metric
+                    .merge(custom.stats.as_mut(), other.stats.as_ref());
+            }
+        }
+    }
+
+    pub(crate) fn compute(&mut self, node: &Node, code: &[u8], lang: LANG) {
+        for custom in self.stats.values_mut() {
+            custom
+                .metric
+                .compute(node, code, lang, custom.stats.as_mut());
+        }
+    }
+
+    pub(crate) fn finalize(&mut self) {
+        for custom in self.stats.values_mut() {
+            custom.metric.finalize(custom.stats.as_mut());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde::Serialize;
+
+    use crate::{
+        MetricKind, MetricSet, MetricsOptions, ParserTrait, RustParser, metrics_with_options,
+    };
+
+    use super::*;
+
+    #[derive(Clone, Debug, Default, Serialize)]
+    struct IdentifiersStats {
+        identifiers: usize,
+        identifiers_sum: usize,
+        #[serde(skip)]
+        spaces: usize,
+    }
+
+    // Counts the identifiers of a space and its subspaces
+    struct Identifiers;
+
+    impl Metric for Identifiers {
+        type Stats = IdentifiersStats;
+
+        fn name(&self) -> &'static str {
+            "identifiers"
+        }
+
+        fn compute(&self, node: &Node, _code: &[u8], lang: LANG, stats: &mut Self::Stats) {
+            if lang == LANG::Rust && node.kind() == "identifier" {
+                stats.identifiers += 1;
+            }
+        }
+
+        fn finalize(&self, stats: &mut Self::Stats) {
+            stats.identifiers_sum += stats.identifiers;
+            stats.spaces += 1;
+        }
+
+        fn merge(&self, stats: &mut Self::Stats, other: &Self::Stats) {
+            stats.identifiers_sum += other.identifiers_sum;
+            stats.spaces += other.spaces;
+        }
+    }
+
+    fn rust_metrics(source: &str, registry: MetricRegistry) -> crate::FuncSpace {
+        let path = Path::new("foo.rs");
+        let parser = RustParser::new(source.as_bytes().to_vec(), path, None).unwrap();
+        let options = MetricsOptions {
+            registry,
+            ..MetricsOptions::default()
+        };
+        metrics_with_options(&parser, path, &options).unwrap()
+    }
+
+    #[test]
+    fn custom_metric_per_space() {
+        let mut registry = MetricRegistry::new();
+        registry.register(Identifiers).unwrap();
+
+        let space = rust_metrics(
+            "fn f(a: u32) -> u32 {
+                 a
+             }
+             fn g(b: u32, c: u32) -> u32 {
+                 b + c
+             }",
+            registry,
+        );
+
+        // f, a, a
+        let f = space.spaces[0]
+            .metrics
+            .custom
+            .get::<IdentifiersStats>("identifiers");
+        assert_eq!(f.map(|stats| stats.identifiers_sum), Some(3));
+        // The unit and the functions
+        let unit = space.metrics.custom.get::<IdentifiersStats>("identifiers");
+        assert_eq!(unit.map(|stats| stats.spaces), Some(3));
+
+        insta::assert_json_snapshot!(
+            space.metrics.custom,
+            @r###"
+        {
+          "identifiers": {
+            "identifiers": 0,
+            "identifiers_sum": 8
+          }
+        }"###
+        );
+    }
+
+    #[test]
+    fn custom_metric_not_selected() {
+        let mut registry = MetricRegistry::new();
+        registry.register(Identifiers).unwrap();
+
+        let path = Path::new("foo.rs");
+        let parser = RustParser::new(b"fn f(a: u32) {}".to_vec(), path, None).unwrap();
+        let options = MetricsOptions {
+            registry,
+            selection: MetricSet::empty().with(MetricKind::Loc),
+            ..MetricsOptions::default()
+        };
+        let space = metrics_with_options(&parser, path, &options).unwrap();
+
+        assert!(space.metrics.custom.is_empty());
+        assert!(space.spaces[0].metrics.custom.is_empty());
+    }
+
+    #[test]
+    fn custom_metric_errors() {
+        let mut registry = MetricRegistry::new();
+        registry.register(Identifiers).unwrap();
+
+        assert_eq!(
+            registry.register(Identifiers).unwrap_err().to_string(),
+            "The metric `identifiers` is already registered"
+        );
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["identifiers"]);
+
+        let space = rust_metrics("fn f() {}", MetricRegistry::new());
+        assert!(space.metrics.custom.is_empty());
+        assert!(
+            space
+                .metrics
+                .custom
+                .get::<IdentifiersStats>("identifiers")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Serialize;
+
+use crate::checker::Checker;
+use crate::error::Error;
+use crate::node::Node;
+use crate::spaces::{SpaceKind, WalkedSpace, walk_spaces};
+
+use crate::dump_debt::*;
+use crate::traits::*;
+
+/// The markers searched by default in the comments of a code.
+pub const DEFAULT_MARKERS: &[&str] = &["TODO", "FIXME", "HACK", "XXX"];
+
+static TICKET: OnceLock<Regex> = OnceLock::new();
+static DEFAULT_PATTERNS: OnceLock<MarkerPatterns> = OnceLock::new();
+
+#[inline(always)]
+fn ticket_regex() -> &'static Regex {
+    TICKET.get_or_init(|| Regex::new(r"\b[A-Z][A-Z0-9]+-[0-9]+\b|#[0-9]+\b").unwrap())
+}
+
+/// The patterns used to find technical-debt markers in comments.
+#[derive(Debug, Clone)]
+pub struct MarkerPatterns {
+    keywords: Option<Regex>,
+    custom: Vec<Regex>,
+}
+
+impl Default for MarkerPatterns {
+    fn default() -> Self {
+        Self::new(DEFAULT_MARKERS, &[] as &[&str]).unwrap()
+    }
+}
+
+impl MarkerPatterns {
+    /// Creates the patterns from a list of keywords, matched as whole words,
+    /// and a list of custom regular expressions.
+    ///
+    /// When a custom regular expression contains a capture group,
+    /// the first group is used as marker, otherwise the whole match.
+    ///
+    /// Returns an error if a custom regular expression is not valid.
+    pub fn new<K: AsRef<str>, C: AsRef<str>>(
+        keywords: &[K],
+        custom: &[C],
+    ) -> Result<Self, regex::Error> {
+        let keywords = if keywords.is_empty() {
+            None
+        } else {
+            let alternatives = keywords
+                .iter()
+                .map(|k| regex::escape(k.as_ref()))
+                .collect::<Vec<_>>()
+                .join("|");
+            Some(Regex::new(&format!(r"\b(?:{alternatives})\b"))?)
+        };
+        let custom = custom
+            .iter()
+            .map(|c| Regex::new(c.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { keywords, custom })
+    }
+
+    pub(crate) fn default_ref() -> &'static Self {
+        DEFAULT_PATTERNS.get_or_init(Self::default)
+    }
+
+    // Returns the marker found on a line of a comment, if any.
+    // When several patterns match, the leftmost one wins.
+    fn find_in_line<'a>(&self, line: &'a str) -> Option<FoundMarker<'a>> {
+        let keyword = self
+            .keywords
+            .as_ref()
+            .and_then(|re| re.find(line))
+            .map(|m| (m.start(), m.end(), m.as_str()));
+        let custom = self.custom.iter().filter_map(|re| {
+            re.captures(line).map(|caps| {
+                let whole = caps.get(0).unwrap();
+                let kind = caps.get(1).unwrap_or(whole).as_str();
+                (whole.start(), whole.end(), kind)
+            })
+        });
+        let (start, end, kind) = keyword
+            .into_iter()
+            .chain(custom)
+            .filter(|(start, end, _)| end > start)
+            .min_by_key(|(start, _, _)| *start)?;
+
+        Some(FoundMarker::new(start, kind, &line[end..]))
+    }
+
+    // Calls `found` for each marker contained in the text of a comment,
+    // passing the index of the line in the comment and the column of the marker.
+    pub(crate) fn scan<'a>(&self, comment: &'a str, mut found: impl FnMut(usize, FoundMarker<'a>)) {
+        for (index, line) in comment.lines().enumerate() {
+            if let Some(marker) = self.find_in_line(line) {
+                found(index, marker);
+            }
+        }
+    }
+}
+
+pub(crate) struct FoundMarker<'a> {
+    pub(crate) offset: usize,
+    pub(crate) kind: &'a str,
+    pub(crate) author: Option<&'a str>,
+    pub(crate) ticket: Option<&'a str>,
+    pub(crate) text: &'a str,
+}
+
+impl<'a> FoundMarker<'a> {
+    fn new(offset: usize, kind: &'a str, rest: &'a str) -> Self {
+        let mut author = None;
+        let mut ticket = None;
+        let mut rest = rest;
+
+        // `TODO(alice)` or `TODO(BUG-1234)`
+        if let Some((reference, tail)) = rest.strip_prefix('(').and_then(|r| r.split_once(')')) {
+            let reference = reference.trim();
+            if ticket_regex()
+                .find(reference)
+                .is_some_and(|m| m.as_str() == reference)
+            {
+                ticket = Some(reference);
+            } else if !reference.is_empty() {
+                author = Some(reference);
+            }
+            rest = tail;
+        }
+
+        let text = rest
+            .trim_start_matches([':', '-'])
+            .trim()
+            .trim_end_matches("*/")
+            .trim_end();
+
+        // `FIXME: BUG-1234 ...`
+        if ticket.is_none() {
+            ticket = ticket_regex().find(text).map(|m| m.as_str());
+        }
+
+        Self {
+            offset,
+            kind,
+            author,
+            ticket,
+            text,
+        }
+    }
+}
+
+/// A technical-debt marker found in a comment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DebtMarker {
+    /// The marker, for example `TODO` or `FIXME`
+    pub marker: String,
+    /// The author referenced by the marker, as in `TODO(alice)`
+    pub author: Option<String>,
+    /// The ticket referenced by the marker, as in `FIXME: BUG-1234`
+    pub ticket: Option<String>,
+    /// The text following the marker
+    pub text: String,
+    /// The line of the marker
+    pub line: usize,
+    /// The column of the marker
+    pub column: usize,
+    /// The name of the space containing the marker
+    pub space_name: Option<String>,
+    /// The first line of the space containing the marker
+    pub space_line: usize,
+    /// The kind of the space containing the marker
+    pub space_kind: SpaceKind,
+}
+
+/// All technical-debt markers of a code.
+#[derive(Debug, Clone, Serialize)]
+pub struct DebtMarkers {
+    /// The name of the analyzed file
+    pub name: Option<String>,
+    /// The markers, in order of appearance
+    pub markers: Vec<DebtMarker>,
+}
+
+/// Retrieves all technical-debt markers contained in the comments of a code.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{debt_markers, MarkerPatterns, ParserTrait, RustParser};
+///
+/// let source_code = "// TODO(alice): remove this\nfn f() {}";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Retrieve all markers
+/// let debt = debt_markers(&parser, &path, &MarkerPatterns::default());
+///
+/// assert_eq!(debt.markers[0].author.as_deref(), Some("alice"));
+/// ```
+pub fn debt_markers<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    patterns: &MarkerPatterns,
+) -> DebtMarkers {
+    let code = parser.get_code();
+    let mut markers = Vec::new();
+
+    walk_spaces(parser, path, |node, space, spaces| {
+        if T::Checker::is_comment(node) {
+            collect_markers(
+                node,
+                code,
+                patterns,
+                space.map(|i| &spaces[i]),
+                &mut markers,
+            );
+        }
+    });
+
+    DebtMarkers {
+        name: path.to_str().map(|name| name.to_string()),
+        markers,
+    }
+}
+
+fn collect_markers(
+    node: &Node,
+    code: &[u8],
+    patterns: &MarkerPatterns,
+    space: Option<&WalkedSpace>,
+    markers: &mut Vec<DebtMarker>,
+) {
+    let Some(text) = node.utf8_text(code) else {
+        return;
+    };
+    let (row, column) = node.start_position();
+    patterns.scan(text, |index, found| {
+        markers.push(DebtMarker {
+            marker: found.kind.to_string(),
+            author: found.author.map(|a| a.to_string()),
+            ticket: found.ticket.map(|t| t.to_string()),
+            text: found.text.to_string(),
+            line: row + index + 1,
+            column: if index == 0 { column } else { 0 } + found.offset + 1,
+            space_name: space.and_then(|s| s.name.clone()),
+            space_line: space.map_or(0, |s| s.line),
+            space_kind: space.map_or(SpaceKind::Unknown, |s| s.kind),
+        });
+    });
+}
+
+/// Configuration options for retrieving
+/// the technical-debt markers of a code.
+#[derive(Debug)]
+pub struct DebtCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+    /// The patterns used to find the markers
+    pub patterns: MarkerPatterns,
+}
+
+pub struct DebtCode {
+    _guard: (),
+}
+
+impl Callback for DebtCode {
+    type Res = Result<(), Error>;
+    type Cfg = DebtCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        Ok(dump_debt(&debt_markers(parser, &cfg.path, &cfg.patterns))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::trimmed_parser;
+    use crate::{JavaParser, PythonParser, RustParser};
+
+    use super::*;
+
+    fn check_debt<T: ParserTrait, F: Fn(DebtMarkers)>(
+        source: &str,
+        filename: &str,
+        patterns: &MarkerPatterns,
+        check: F,
+    ) {
+        let (parser, path) = trimmed_parser::<T>(source, filename);
+
+        check(debt_markers(&parser, &path, patterns))
+    }
+
+    #[test]
+    fn rust_debt_markers() {
+        check_debt::<RustParser, _>(
+            "// TODO(alice): remove this
+             fn f() {
+                 /* FIXME: BUG-1234 handle errors */
+                 let todo = 1; // not a marker: todo
+             }",
+            "foo.rs",
+            &MarkerPatterns::default(),
+            |debt| {
+                insta::assert_json_snapshot!(
+                    debt.markers,
+                    @r###"
+                    [
+                      {
+                        "marker": "TODO",
+                        "author": "alice",
+                        "ticket": null,
+                        "text": "remove this",
+                        "line": 1,
+                        "column": 4,
+                        "space_name": "foo.rs",
+                        "space_line": 1,
+                        "space_kind": "unit"
+                      },
+                      {
+                        "marker": "FIXME",
+                        "author": null,
+                        "ticket": "BUG-1234",
+                        "text": "BUG-1234 handle errors",
+                        "line": 3,
+                        "column": 21,
+                        "space_name": "f",
+                        "space_line": 2,
+                        "space_kind": "function"
+                      }
+                    ]"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn python_debt_markers() {
+        check_debt::<PythonParser, _>(
+            "class A:
+                 def f(self):
+                     # HACK(#42) works around a bug
+                     pass
+                 # XXX
+             ",
+            "foo.py",
+            &MarkerPatterns::default(),
+            |debt| {
+                assert_eq!(debt.markers.len(), 2);
+                assert_eq!(debt.markers[0].marker, "HACK");
+                assert_eq!(debt.markers[0].ticket.as_deref(), Some("#42"));
+                assert_eq!(debt.markers[0].author, None);
+                assert_eq!(debt.markers[0].text, "works around a bug");
+                assert_eq!(debt.markers[0].space_name.as_deref(), Some("f"));
+                assert_eq!(debt.markers[1].marker, "XXX");
+                assert_eq!(debt.markers[1].space_name.as_deref(), Some("A"));
+            },
+        );
+    }
+
+    #[test]
+    fn java_custom_markers() {
+        let patterns = MarkerPatterns::new(&["NOTE"], &[r"@(deprecated)-soon"]).unwrap();
+        check_debt::<JavaParser, _>(
+            "class A {
+                 /**
+                  * NOTE(bob) check this
+                  * TODO ignored, not in the keywords
+                  * @deprecated-soon use B
+                  */
+                 void f() {}
+             }",
+            "foo.java",
+            &patterns,
+            |debt| {
+                let markers: Vec<_> = debt
+                    .markers
+                    .iter()
+                    .map(|m| (m.marker.as_str(), m.line, m.text.as_str()))
+                    .collect();
+                assert_eq!(
+                    markers,
+                    [("NOTE", 3, "check this"), ("deprecated", 5, "use B")]
+                );
+                assert_eq!(debt.markers[0].author.as_deref(), Some("bob"));
+                assert_eq!(debt.markers[0].space_name.as_deref(), Some("A"));
+            },
+        );
+    }
+
+    #[test]
+    fn invalid_custom_marker() {
+        assert!(MarkerPatterns::new(DEFAULT_MARKERS, &["(unclosed"]).is_err());
+    }
+}
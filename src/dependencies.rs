@@ -0,0 +1,224 @@
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::imports::Imports;
+pub use crate::imports::{Import, ImportKind};
+
+use crate::dump_imports::*;
+use crate::traits::*;
+
+/// All imports of a code.
+#[derive(Debug, Clone, Serialize)]
+pub struct Dependencies {
+    /// The name of the analyzed file
+    pub name: Option<String>,
+    /// The imports, in order of appearance
+    pub imports: Vec<Import>,
+}
+
+/// Retrieves all imports of a code: the `use` and `mod` declarations in Rust,
+/// the `import` and `from` statements in Python, the `import` statements,
+/// `require()` and `import()` calls in JavaScript and TypeScript,
+/// the `import` declarations in Java and Kotlin, and the `#include`
+/// directives in C/C++.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{imports, ImportKind, ParserTrait, PythonParser};
+///
+/// let source_code = "from os import path, sep";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.py");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Python parser
+/// let parser = PythonParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Retrieve all imports
+/// let dependencies = imports(&parser, &path);
+///
+/// assert_eq!(dependencies.imports[0].kind, ImportKind::From);
+/// assert_eq!(dependencies.imports[0].path, "os");
+/// assert_eq!(dependencies.imports[0].symbols, ["path", "sep"]);
+/// ```
+pub fn imports<T: ParserTrait>(parser: &T, path: &Path) -> Dependencies {
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let mut imports = Vec::new();
+    let mut cursor = root.cursor();
+    let mut stack = vec![root];
+    let mut children = Vec::new();
+
+    while let Some(node) = stack.pop() {
+        T::Imports::collect(&node, code, &mut imports);
+
+        cursor.reset(&node);
+        if cursor.goto_first_child() {
+            loop {
+                children.push(cursor.node());
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+            for child in children.drain(..).rev() {
+                stack.push(child);
+            }
+        }
+    }
+
+    Dependencies {
+        name: path.to_str().map(|name| name.to_string()),
+        imports,
+    }
+}
+
+/// Configuration options for retrieving the imports of a code.
+#[derive(Debug)]
+pub struct ImportsCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+}
+
+pub struct ImportsCode {
+    _guard: (),
+}
+
+impl Callback for ImportsCode {
+    type Res = Result<(), Error>;
+    type Cfg = ImportsCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        Ok(dump_imports(&imports(parser, &cfg.path))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{JavaParser, KotlinParser, PythonParser, RustParser, TypescriptParser};
+
+    use super::*;
+
+    fn check<T: ParserTrait>(source: &str, file: &str) -> Vec<(ImportKind, String, Vec<String>)> {
+        let path = PathBuf::from(file);
+        let parser = T::new(source.as_bytes().to_vec(), &path, None).unwrap();
+        imports(&parser, &path)
+            .imports
+            .into_iter()
+            .map(|import| (import.kind, import.path, import.symbols))
+            .collect()
+    }
+
+    fn symbols(symbols: &[&str]) -> Vec<String> {
+        symbols.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn rust_imports() {
+        assert_eq!(
+            check::<RustParser>(
+                "use std::io::{self, Read as R, prelude::*, fmt::{Debug, Display}};
+                 use crate::a::b as c;
+                 use super::*;
+                 use serde;
+                 mod foo;
+                 mod bar {}",
+                "foo.rs",
+            ),
+            [
+                (
+                    ImportKind::Use,
+                    "std::io".into(),
+                    symbols(&["self", "Read"])
+                ),
+                (ImportKind::Use, "std::io::prelude".into(), symbols(&["*"])),
+                (
+                    ImportKind::Use,
+                    "std::io::fmt".into(),
+                    symbols(&["Debug", "Display"])
+                ),
+                (ImportKind::Use, "crate::a".into(), symbols(&["b"])),
+                (ImportKind::Use, "super".into(), symbols(&["*"])),
+                (ImportKind::Use, "serde".into(), symbols(&[])),
+                (ImportKind::Mod, "foo".into(), symbols(&[])),
+            ]
+        );
+    }
+
+    #[test]
+    fn python_imports() {
+        assert_eq!(
+            check::<PythonParser>(
+                "import os.path, sys as s
+from ..a.b import (c as d, e)
+from m import *",
+                "foo.py",
+            ),
+            [
+                (ImportKind::Import, "os.path".into(), symbols(&[])),
+                (ImportKind::Import, "sys".into(), symbols(&[])),
+                (ImportKind::From, "..a.b".into(), symbols(&["c", "e"])),
+                (ImportKind::From, "m".into(), symbols(&["*"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_imports() {
+        assert_eq!(
+            check::<TypescriptParser>(
+                "import d, { a as b, c } from './m';
+                 import * as ns from \"n\";
+                 import 'side';
+                 const x = require('r');
+                 import('dyn').then(f);",
+                "foo.ts",
+            ),
+            [
+                (
+                    ImportKind::Import,
+                    "./m".into(),
+                    symbols(&["default", "a", "c"])
+                ),
+                (ImportKind::Import, "n".into(), symbols(&["*"])),
+                (ImportKind::Import, "side".into(), symbols(&[])),
+                (ImportKind::Require, "r".into(), symbols(&[])),
+                (ImportKind::Dynamic, "dyn".into(), symbols(&[])),
+            ]
+        );
+    }
+
+    #[test]
+    fn java_and_kotlin_imports() {
+        assert_eq!(
+            check::<JavaParser>(
+                "import java.util.List;
+                 import static java.lang.Math.*;",
+                "Foo.java",
+            ),
+            [
+                (ImportKind::Import, "java.util".into(), symbols(&["List"])),
+                (ImportKind::Import, "java.lang.Math".into(), symbols(&["*"])),
+            ]
+        );
+        assert_eq!(
+            check::<KotlinParser>(
+                "import a.b.C
+                 import x.y.*
+                 import p.Q as R",
+                "foo.kt",
+            ),
+            [
+                (ImportKind::Import, "a.b".into(), symbols(&["C"])),
+                (ImportKind::Import, "x.y".into(), symbols(&["*"])),
+                (ImportKind::Import, "p".into(), symbols(&["Q"])),
+            ]
+        );
+    }
+}
@@ -0,0 +1,639 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use petgraph::{Direction, algo::kosaraju_scc, graph::NodeIndex, stable_graph::StableGraph};
+use serde::Serialize;
+
+use crate::dependencies::imports;
+use crate::error::Error;
+use crate::imports::{Import, ImportKind};
+use crate::langs::*;
+
+use crate::traits::*;
+
+// The extensions tried when resolving a JavaScript or TypeScript module
+const JS_EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+/// Builds the graph of the dependencies among the modules of a project.
+///
+/// The imports of each file are resolved to the files of the project:
+/// the Rust `mod` and `use` declarations through the module tree of each crate,
+/// the Python imports through their packages, the JavaScript and TypeScript
+/// relative imports through their paths, the Java and Kotlin imports through
+/// their packages and the C/C++ includes through their paths.
+/// The imports of modules outside the project are ignored.
+///
+/// Files are added one by one, possibly from several threads through the
+/// [`Callback`] implementation, then the graph is built
+/// with [`build`](Self::build).
+///
+/// [`Callback`]: trait.Callback.html
+#[derive(Debug, Default)]
+pub struct DependencyGraphBuilder {
+    files: Vec<(PathBuf, LANG, Vec<Import>)>,
+}
+
+impl DependencyGraphBuilder {
+    /// Creates a new empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the imports of a file to the builder.
+    pub fn add<T: ParserTrait>(&mut self, parser: &T, path: &Path) {
+        let imports = imports(parser, path).imports;
+        self.insert(path, parser.get_language(), imports);
+    }
+
+    fn insert(&mut self, path: &Path, lang: LANG, imports: Vec<Import>) {
+        self.files.push((normalize(path), lang, imports));
+    }
+
+    /// Returns the graph of the dependencies among the added files.
+    pub fn build(&self) -> DependencyGraph {
+        let resolver = Resolver::new(&self.files);
+
+        // Since the graph is only read, its indices are the indices of the files
+        let mut graph = StableGraph::<usize, ()>::new();
+        let nodes: Vec<NodeIndex> = (0..self.files.len()).map(|i| graph.add_node(i)).collect();
+        let mut edges = HashSet::new();
+        for (i, (_, lang, imports)) in self.files.iter().enumerate() {
+            for import in imports {
+                for j in resolver.resolve(i, *lang, import) {
+                    if j != i && edges.insert((i, j)) {
+                        graph.add_edge(nodes[i], nodes[j], ());
+                    }
+                }
+            }
+        }
+
+        let path = |node: &NodeIndex| self.files[graph[*node]].0.clone();
+
+        let mut cycles: Vec<Vec<PathBuf>> = kosaraju_scc(&graph)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|component| {
+                let mut paths: Vec<_> = component.iter().map(path).collect();
+                paths.sort();
+                paths
+            })
+            .collect();
+        cycles.sort();
+
+        let mut modules: Vec<Module> = nodes
+            .iter()
+            .map(|node| {
+                let afferent = graph.neighbors_directed(*node, Direction::Incoming).count();
+                let efferent = graph.neighbors_directed(*node, Direction::Outgoing).count();
+                Module {
+                    path: path(node),
+                    afferent,
+                    efferent,
+                    instability: efferent as f64 / (afferent + efferent) as f64,
+                }
+            })
+            .collect();
+        modules.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut dependencies: Vec<Dependency> = edges
+            .into_iter()
+            .map(|(i, j)| Dependency {
+                from: self.files[i].0.clone(),
+                to: self.files[j].0.clone(),
+            })
+            .collect();
+        dependencies.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+        DependencyGraph {
+            modules,
+            dependencies,
+            cycles,
+        }
+    }
+}
+
+/// Configuration options for adding a file to a dependency graph builder.
+#[derive(Debug)]
+pub struct DependencyGraphCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+    /// The dependency graph builder shared among threads
+    pub builder: Arc<Mutex<DependencyGraphBuilder>>,
+}
+
+impl Callback for DependencyGraphBuilder {
+    type Res = Result<(), Error>;
+    type Cfg = DependencyGraphCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        // The imports are retrieved outside the lock, so files are processed in parallel
+        let imports = imports(parser, &cfg.path).imports;
+        cfg.builder
+            .lock()
+            .unwrap()
+            .insert(&cfg.path, parser.get_language(), imports);
+        Ok(())
+    }
+}
+
+/// A module of a dependency graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct Module {
+    /// The file of the module
+    pub path: PathBuf,
+    /// The number of modules depending on this module (`Ca`)
+    pub afferent: usize,
+    /// The number of modules this module depends on (`Ce`)
+    pub efferent: usize,
+    /// The instability of the module, `Ce / (Ca + Ce)`.
+    ///
+    /// If the module has no dependencies at all, its value is `NAN`.
+    pub instability: f64,
+}
+
+/// A dependency of a module on another one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Dependency {
+    /// The file of the dependent module
+    pub from: PathBuf,
+    /// The file of the module it depends on
+    pub to: PathBuf,
+}
+
+/// The graph of the dependencies among the modules of a project.
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyGraph {
+    /// The modules, sorted by path
+    pub modules: Vec<Module>,
+    /// The dependencies, sorted by path
+    pub dependencies: Vec<Dependency>,
+    /// The strongly connected components having more than one module,
+    /// that is the modules depending on each other
+    pub cycles: Vec<Vec<PathBuf>>,
+}
+
+impl DependencyGraph {
+    /// Returns the graph in the `DOT` language of Graphviz.
+    ///
+    /// The modules belonging to a cycle are colored in red.
+    pub fn to_dot(&self) -> String {
+        let in_cycle: HashSet<&PathBuf> = self.cycles.iter().flatten().collect();
+        let quote = |path: &Path| format!("{:?}", path.to_string_lossy());
+
+        let mut dot = String::from("digraph dependencies {\n");
+        for module in &self.modules {
+            let _ = write!(dot, "    {}", quote(&module.path));
+            if in_cycle.contains(&module.path) {
+                dot.push_str(" [color=red]");
+            }
+            dot.push_str(";\n");
+        }
+        for dependency in &self.dependencies {
+            let _ = writeln!(
+                dot,
+                "    {} -> {};",
+                quote(&dependency.from),
+                quote(&dependency.to)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+// Removes the `.` and `..` components of a path, without accessing the disk
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+// Finds the files imported by an import
+struct Resolver<'a> {
+    files: &'a [(PathBuf, LANG, Vec<Import>)],
+    paths: HashMap<&'a Path, usize>,
+    names: HashMap<OsString, Vec<usize>>,
+    // The crate root and the module path of each Rust file
+    rust_modules: HashMap<(&'a Path, Vec<String>), usize>,
+    rust_paths: HashMap<usize, (&'a Path, Vec<String>)>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(files: &'a [(PathBuf, LANG, Vec<Import>)]) -> Self {
+        let paths: HashMap<&Path, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(i, (path, _, _))| (path.as_path(), i))
+            .collect();
+        let mut names: HashMap<OsString, Vec<usize>> = HashMap::new();
+        for (i, (path, _, _)) in files.iter().enumerate() {
+            if let Some(name) = path.file_name() {
+                names.entry(name.to_os_string()).or_default().push(i);
+            }
+        }
+
+        let mut rust_modules = HashMap::new();
+        let mut rust_paths = HashMap::new();
+        let rust_files = files
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, lang, _))| *lang == LANG::Rust);
+        for (i, (path, _, _)) in rust_files {
+            if let Some(module) = rust_module(path, &paths) {
+                rust_modules.insert(module.clone(), i);
+                rust_paths.insert(i, module);
+            }
+        }
+
+        Self {
+            files,
+            paths,
+            names,
+            rust_modules,
+            rust_paths,
+        }
+    }
+
+    fn resolve(&self, file: usize, lang: LANG, import: &Import) -> Vec<usize> {
+        match lang {
+            LANG::Rust => self.rust(file, import),
+            LANG::Python => self.python(file, import),
+            LANG::Mozjs | LANG::Javascript | LANG::Typescript | LANG::Tsx => {
+                self.javascript(file, import)
+            }
+            LANG::Java => self.jvm(file, import, "java"),
+            LANG::Kotlin => self.jvm(file, import, "kt"),
+            LANG::Cpp | LANG::Preproc | LANG::Ccomment => self.include(file, import),
+        }
+    }
+
+    fn dir(&self, file: usize) -> &Path {
+        self.files[file].0.parent().unwrap_or(Path::new(""))
+    }
+
+    // Returns the added file ending with `suffix` which is the closest
+    // to `file`, as the one sharing the longest path with it
+    fn find(&self, file: usize, suffix: &Path) -> Option<usize> {
+        let candidates = self.names.get(suffix.file_name()?)?;
+        let dir = self.dir(file);
+        candidates
+            .iter()
+            .filter(|i| self.files[**i].0.ends_with(suffix))
+            .max_by_key(|i| {
+                let path = &self.files[**i].0;
+                dir.components()
+                    .zip(path.components())
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            })
+            .copied()
+    }
+
+    fn get(&self, path: &Path) -> Option<usize> {
+        self.paths.get(normalize(path).as_path()).copied()
+    }
+
+    fn rust(&self, file: usize, import: &Import) -> Vec<usize> {
+        let Some((root, current)) = self.rust_paths.get(&file) else {
+            return Vec::new();
+        };
+        let module = |path: Vec<String>| self.rust_modules.get(&(*root, path)).copied();
+
+        if import.kind == ImportKind::Mod {
+            let mut path = current.clone();
+            path.push(import.path.clone());
+            return module(path).into_iter().collect();
+        }
+
+        let segments: Vec<&str> = import.path.split("::").collect();
+        let supers = segments.iter().take_while(|s| **s == "super").count();
+        // The first module which can be imported, and the shortest
+        // path to look for
+        let (bases, rest) = match segments[0] {
+            "crate" => (vec![(Vec::new(), 0)], &segments[1..]),
+            "self" => (vec![(current.clone(), 0)], &segments[1..]),
+            "super" => {
+                let base = current[..current.len().saturating_sub(supers)].to_vec();
+                (vec![(base, 0)], &segments[supers..])
+            }
+            // A path relative to the current module,
+            // or to the crate root as in Rust 2015
+            _ => (vec![(current.clone(), 1), (Vec::new(), 1)], &segments[..]),
+        };
+
+        let symbols: Vec<Option<&str>> = if import.symbols.is_empty() {
+            vec![None]
+        } else {
+            import.symbols.iter().map(|s| Some(s.as_str())).collect()
+        };
+
+        let mut resolved = Vec::new();
+        for symbol in symbols {
+            let mut segments: Vec<String> = rest.iter().map(|s| s.to_string()).collect();
+            segments.extend(
+                symbol
+                    .filter(|s| *s != "*" && *s != "self")
+                    .map(String::from),
+            );
+            // The longest path which is a module
+            let found = bases.iter().find_map(|(base, min)| {
+                (*min..=segments.len()).rev().find_map(|len| {
+                    let mut path = base.clone();
+                    path.extend_from_slice(&segments[..len]);
+                    module(path)
+                })
+            });
+            resolved.extend(found);
+        }
+        resolved
+    }
+
+    fn python(&self, file: usize, import: &Import) -> Vec<usize> {
+        let dots = import.path.chars().take_while(|c| *c == '.').count();
+        let parts: Vec<&str> = import.path[dots..]
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        // A relative import starts from the package of the file
+        let base = (dots > 0).then(|| {
+            let mut base = self.dir(file).to_path_buf();
+            for _ in 1..dots {
+                base.push("..");
+            }
+            base
+        });
+        let module = |parts: &[&str]| {
+            let path: PathBuf = parts.iter().collect();
+            [path.with_extension("py"), path.join("__init__.py")]
+                .into_iter()
+                .find_map(|path| match &base {
+                    Some(base) => self.get(&base.join(path)),
+                    None if parts.is_empty() => None,
+                    None => self.find(file, &path),
+                })
+        };
+
+        // The imported symbols may be modules too
+        let mut resolved: Vec<usize> = import
+            .symbols
+            .iter()
+            .filter(|symbol| *symbol != "*")
+            .filter_map(|symbol| {
+                let mut parts = parts.clone();
+                parts.push(symbol);
+                module(&parts)
+            })
+            .collect();
+        if resolved.is_empty() {
+            resolved.extend(
+                (0..=parts.len())
+                    .rev()
+                    .find_map(|len| module(&parts[..len])),
+            );
+        }
+        resolved
+    }
+
+    fn javascript(&self, file: usize, import: &Import) -> Vec<usize> {
+        // Only the relative imports refer to the files of the project
+        if !import.path.starts_with('.') {
+            return Vec::new();
+        }
+        let path = self.dir(file).join(&import.path);
+        let with_extension = |path: &Path| {
+            JS_EXTENSIONS.iter().find_map(|extension| {
+                let mut name = path.as_os_str().to_os_string();
+                name.push(".");
+                name.push(extension);
+                self.get(Path::new(&name))
+            })
+        };
+        self.get(&path)
+            .filter(|i| self.files[*i].0.extension().is_some())
+            .or_else(|| with_extension(&path))
+            .or_else(|| with_extension(&path.join("index")))
+            .into_iter()
+            .collect()
+    }
+
+    fn jvm(&self, file: usize, import: &Import, extension: &str) -> Vec<usize> {
+        let package: PathBuf = import.path.split('.').collect();
+        if import.symbols.iter().any(|symbol| symbol == "*") {
+            // All the files of a package, or the nested classes of a class
+            let files: Vec<usize> = self
+                .files
+                .iter()
+                .enumerate()
+                .filter(|(_, (path, _, _))| {
+                    path.extension().is_some_and(|ext| ext == extension)
+                        && path.parent().is_some_and(|dir| dir.ends_with(&package))
+                })
+                .map(|(i, _)| i)
+                .collect();
+            if !files.is_empty() {
+                return files;
+            }
+        }
+        // A class, or the class containing a static member
+        let class = import
+            .symbols
+            .first()
+            .filter(|symbol| *symbol != "*")
+            .and_then(|symbol| self.find(file, &package.join(symbol).with_extension(extension)));
+        class
+            .or_else(|| self.find(file, &package.with_extension(extension)))
+            .into_iter()
+            .collect()
+    }
+
+    fn include(&self, file: usize, import: &Import) -> Vec<usize> {
+        let path = Path::new(&import.path);
+        self.get(&self.dir(file).join(path))
+            .or_else(|| self.find(file, path))
+            .into_iter()
+            .collect()
+    }
+}
+
+// Returns the crate root directory and the module path of a Rust file.
+//
+// The crate root is the closest directory containing a `lib.rs`
+// or a `main.rs` file, or the directory of the file otherwise.
+fn rust_module<'a>(
+    path: &'a Path,
+    paths: &HashMap<&Path, usize>,
+) -> Option<(&'a Path, Vec<String>)> {
+    let dir = path.parent()?;
+    let root = dir
+        .ancestors()
+        .find(|dir| {
+            paths.contains_key(dir.join("lib.rs").as_path())
+                || paths.contains_key(dir.join("main.rs").as_path())
+        })
+        .unwrap_or(dir);
+
+    let mut module: Vec<String> = path
+        .strip_prefix(root)
+        .ok()?
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    let is_root = dir == root && matches!(module.last().map(|m| m.as_str()), Some("lib" | "main"));
+    if is_root || module.last().is_some_and(|m| m == "mod") {
+        module.pop();
+    }
+    Some((root, module))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{JavaParser, JavascriptParser, PythonParser, RustParser};
+
+    use super::*;
+
+    fn add<T: ParserTrait>(builder: &mut DependencyGraphBuilder, path: &str, source: &str) {
+        let path = PathBuf::from(path);
+        let parser = T::new(source.as_bytes().to_vec(), &path, None).unwrap();
+        builder.add(&parser, &path);
+    }
+
+    fn edges(graph: &DependencyGraph) -> Vec<(String, String)> {
+        graph
+            .dependencies
+            .iter()
+            .map(|d| (d.from.display().to_string(), d.to.display().to_string()))
+            .collect()
+    }
+
+    fn edge(from: &str, to: &str) -> (String, String) {
+        (from.to_string(), to.to_string())
+    }
+
+    #[test]
+    fn rust_dependency_graph() {
+        let mut builder = DependencyGraphBuilder::new();
+        add::<RustParser>(&mut builder, "src/lib.rs", "mod a;\nmod b;\nuse std::io;");
+        add::<RustParser>(&mut builder, "src/a/mod.rs", "mod c;\nuse crate::b::B;");
+        add::<RustParser>(&mut builder, "src/a/c.rs", "use super::super::b;");
+        add::<RustParser>(&mut builder, "src/b.rs", "use crate::a::{self, c::C};");
+        let graph = builder.build();
+
+        assert_eq!(
+            edges(&graph),
+            [
+                edge("src/a/c.rs", "src/b.rs"),
+                edge("src/a/mod.rs", "src/a/c.rs"),
+                edge("src/a/mod.rs", "src/b.rs"),
+                edge("src/b.rs", "src/a/c.rs"),
+                edge("src/b.rs", "src/a/mod.rs"),
+                edge("src/lib.rs", "src/a/mod.rs"),
+                edge("src/lib.rs", "src/b.rs"),
+            ]
+        );
+        assert_eq!(
+            graph.cycles,
+            [[
+                PathBuf::from("src/a/c.rs"),
+                PathBuf::from("src/a/mod.rs"),
+                PathBuf::from("src/b.rs")
+            ]]
+        );
+
+        let lib = &graph.modules[3];
+        assert_eq!((lib.afferent, lib.efferent, lib.instability), (0, 2, 1.));
+        let b = &graph.modules[2];
+        assert_eq!((b.afferent, b.efferent, b.instability), (3, 2, 0.4));
+    }
+
+    #[test]
+    fn python_dependency_graph() {
+        let mut builder = DependencyGraphBuilder::new();
+        add::<PythonParser>(&mut builder, "pkg/__init__.py", "from . import a");
+        add::<PythonParser>(&mut builder, "pkg/a.py", "import os\nfrom .sub.b import f");
+        add::<PythonParser>(
+            &mut builder,
+            "pkg/sub/b.py",
+            "from pkg import a\nimport pkg.a",
+        );
+        let graph = builder.build();
+
+        assert_eq!(
+            edges(&graph),
+            [
+                edge("pkg/__init__.py", "pkg/a.py"),
+                edge("pkg/a.py", "pkg/sub/b.py"),
+                edge("pkg/sub/b.py", "pkg/a.py"),
+            ]
+        );
+        assert_eq!(graph.cycles.len(), 1);
+    }
+
+    #[test]
+    fn javascript_and_java_dependency_graph() {
+        let mut builder = DependencyGraphBuilder::new();
+        add::<JavascriptParser>(
+            &mut builder,
+            "web/main.js",
+            "import { f } from './lib';\nconst u = require('../util.js');\nimport 'react';",
+        );
+        add::<JavascriptParser>(&mut builder, "web/lib/index.js", "export const f = 1;");
+        add::<JavascriptParser>(&mut builder, "util.js", "");
+        add::<JavascriptParser>(&mut builder, "web/unused.js", "");
+        add::<JavaParser>(
+            &mut builder,
+            "java/org/a/A.java",
+            "import org.b.*;\nimport static org.c.C.max;",
+        );
+        add::<JavaParser>(&mut builder, "java/org/b/B1.java", "");
+        add::<JavaParser>(&mut builder, "java/org/b/B2.java", "");
+        add::<JavaParser>(&mut builder, "java/org/c/C.java", "import java.util.List;");
+        let graph = builder.build();
+
+        assert_eq!(
+            edges(&graph),
+            [
+                edge("java/org/a/A.java", "java/org/b/B1.java"),
+                edge("java/org/a/A.java", "java/org/b/B2.java"),
+                edge("java/org/a/A.java", "java/org/c/C.java"),
+                edge("web/main.js", "util.js"),
+                edge("web/main.js", "web/lib/index.js"),
+            ]
+        );
+        assert!(graph.cycles.is_empty());
+        assert!(graph.modules[7].instability.is_nan());
+    }
+
+    #[test]
+    fn dependency_graph_dot() {
+        let mut builder = DependencyGraphBuilder::new();
+        add::<JavascriptParser>(&mut builder, "a.js", "import './b';");
+        add::<JavascriptParser>(&mut builder, "b.js", "import './a';");
+        add::<JavascriptParser>(&mut builder, "c.js", "import './a';");
+
+        insta::assert_snapshot!(builder.build().to_dot(), @r###"
+        digraph dependencies {
+            "a.js" [color=red];
+            "b.js" [color=red];
+            "c.js";
+            "a.js" -> "b.js";
+            "b.js" -> "a.js";
+            "c.js" -> "a.js";
+        }
+        "###);
+    }
+}
@@ -0,0 +1,514 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::halstead::HalsteadMaps;
+use crate::spaces::{FuncSpace, MetricsOptions, SpaceKind, compute_spaces};
+use crate::traits::ParserTrait;
+
+/// A text edit of a document.
+///
+/// The bytes `start_byte..old_end_byte` of the code are replaced by `text`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputEdit {
+    /// The first byte of the replaced code
+    pub start_byte: usize,
+    /// The byte following the replaced code
+    pub old_end_byte: usize,
+    /// The text replacing the code
+    pub text: String,
+}
+
+/// A parsed document whose metrics are updated after each edit.
+///
+/// When a document is edited, its code is parsed again reusing the
+/// unchanged nodes of the previous tree, and only the metrics of the
+/// function spaces whose code has changed are computed again.
+/// The metrics of the other spaces are taken from the previous analysis,
+/// so that an editor can update the metrics of a large file as it is typed.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{Document, InputEdit, MetricsOptions, RustParser};
+///
+/// let source_code = "fn f() {}\nfn g() {}\n";
+/// let path = Path::new("foo.rs");
+/// let mut document = Document::<RustParser>::new(
+///     source_code.as_bytes().to_vec(),
+///     &path,
+///     MetricsOptions::default(),
+/// )
+/// .unwrap();
+///
+/// let edit = InputEdit {
+///     start_byte: 8,
+///     old_end_byte: 8,
+///     text: "let a = 1;".to_string(),
+/// };
+/// let space = document.edit(&[edit]).unwrap();
+///
+/// assert_eq!(space.spaces.len(), 2);
+/// // The metrics of `g` have been reused
+/// assert_eq!(document.reused_spaces(), 1);
+/// ```
+pub struct Document<T: ParserTrait> {
+    path: PathBuf,
+    parser: T,
+    options: MetricsOptions,
+    cache: SpaceCache,
+    space: Option<FuncSpace>,
+}
+
+impl<T: ParserTrait> Document<T> {
+    /// Parses a code and computes its metrics.
+    ///
+    /// An error is returned if the metrics cannot be computed.
+    pub fn new(code: Vec<u8>, path: &Path, options: MetricsOptions) -> Result<Self, Error> {
+        let parser = T::new(code, path, None)?;
+        let mut cache = SpaceCache::default();
+        let space = compute_spaces(&parser, path, &options, Some(&mut cache))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            parser,
+            options,
+            cache,
+            space: Some(space),
+        })
+    }
+
+    /// Returns the code of the document.
+    pub fn code(&self) -> &[u8] {
+        self.parser.get_code()
+    }
+
+    /// Returns the metrics of the document.
+    ///
+    /// If `None`, the metrics could not be computed.
+    pub fn metrics(&self) -> Option<&FuncSpace> {
+        self.space.as_ref()
+    }
+
+    /// Returns the number of spaces whose metrics have been reused
+    /// by the last analysis, excluding their subspaces.
+    pub fn reused_spaces(&self) -> usize {
+        self.cache.reused
+    }
+
+    /// Applies some edits to the document, in order, and updates its metrics.
+    ///
+    /// The bytes of an edit refer to the code modified by the previous edits.
+    /// An error is returned, and the document is left unchanged, if
+    /// an edit is out of the code or splits a character.
+    ///
+    /// An error is returned too if the edited code cannot be parsed or its
+    /// metrics cannot be computed, and the document is then left without metrics.
+    pub fn edit(&mut self, edits: &[InputEdit]) -> Result<&FuncSpace, Error> {
+        let mut len = self.code().len();
+        let mut code = self.code().to_vec();
+        for edit in edits {
+            if edit.start_byte > edit.old_end_byte || edit.old_end_byte > len {
+                return Err(Error::InvalidInput(format!(
+                    "The edit {}..{} is out of the code of {} bytes",
+                    edit.start_byte, edit.old_end_byte, len
+                )));
+            }
+            if !is_char_boundary(&code, edit.start_byte)
+                || !is_char_boundary(&code, edit.old_end_byte)
+            {
+                return Err(Error::InvalidInput(format!(
+                    "The edit {}..{} splits a character",
+                    edit.start_byte, edit.old_end_byte
+                )));
+            }
+            code.splice(edit.start_byte..edit.old_end_byte, edit.text.bytes());
+            len = code.len();
+        }
+
+        for edit in edits {
+            self.cache.apply_edit(self.parser.get_code(), edit);
+            self.parser.edit(edit);
+        }
+        self.space = None;
+        let Some(changed_ranges) = self.parser.reparse() else {
+            self.cache = SpaceCache::default();
+            return Err(Error::Parse(self.path.clone()));
+        };
+        self.cache.invalidate(&changed_ranges);
+
+        let space = compute_spaces(
+            &self.parser,
+            &self.path,
+            &self.options,
+            Some(&mut self.cache),
+        )?;
+
+        Ok(self.space.insert(space))
+    }
+}
+
+// Checks whether a byte is the first byte of a character, or the end of the code
+#[inline(always)]
+fn is_char_boundary(code: &[u8], byte: usize) -> bool {
+    // The continuation bytes of a character are 0b10xxxxxx
+    code.get(byte).is_none_or(|c| (*c as i8) >= -0x40)
+}
+
+// A space computed by a previous analysis
+#[derive(Debug, Clone)]
+pub(crate) struct CachedSpace {
+    pub(crate) space: FuncSpace,
+    pub(crate) halstead_maps: HalsteadMaps<'static>,
+    // The kinds of the ancestors of the space, from its parent to the root
+    pub(crate) ancestors: Vec<u16>,
+    // The first byte of the code preceding the space which is read
+    // by its metrics, as its documentation comments
+    pub(crate) context_start: usize,
+}
+
+// The spaces computed by an analysis, indexed by their byte ranges
+#[derive(Debug, Default)]
+pub(crate) struct SpaceCache {
+    spaces: HashMap<(usize, usize), CachedSpace>,
+    // The number of spaces reused by the last analysis
+    pub(crate) reused: usize,
+}
+
+impl SpaceCache {
+    // Empties the cache, returning its old content
+    pub(crate) fn take(&mut self) -> Self {
+        self.reused = 0;
+        Self {
+            spaces: std::mem::take(&mut self.spaces),
+            reused: 0,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, range: (usize, usize), space: CachedSpace) {
+        self.spaces.insert(range, space);
+    }
+
+    // Removes a space from the cache, if it has been computed
+    // for a node of the same kind in the same position of the tree
+    pub(crate) fn remove(
+        &mut self,
+        range: (usize, usize),
+        context_start: usize,
+        kind: SpaceKind,
+        ancestors: &[u16],
+    ) -> Option<CachedSpace> {
+        self.spaces.remove(&range).filter(|cached| {
+            cached.space.kind == kind
+                && cached.context_start == context_start
+                && cached.ancestors == ancestors
+        })
+    }
+
+    // Moves the spaces nested in the given ranges from an old cache
+    pub(crate) fn keep_nested(&mut self, old: SpaceCache, ranges: &[(usize, usize)]) {
+        for ((start, end), space) in old.spaces {
+            if ranges.iter().any(|(s, e)| *s <= start && end <= *e) {
+                self.spaces.insert((start, end), space);
+            }
+        }
+    }
+
+    // Updates the cache before an edit of the code: the spaces before
+    // the edit are kept, the spaces after it are moved and the spaces
+    // containing it, or whose preceding code read by their metrics
+    // contains it, are removed. The spaces starting on the last line of
+    // the edit are removed too when their columns change, as well as
+    // the moved spaces with custom metrics, which cannot be moved.
+    pub(crate) fn apply_edit(&mut self, code: &[u8], edit: &InputEdit) {
+        let old_rows = count_rows(&code[edit.start_byte..edit.old_end_byte]);
+        let row_delta = count_rows(edit.text.as_bytes()) as isize - old_rows as isize;
+        let byte_delta = edit.text.len() as isize - (edit.old_end_byte - edit.start_byte) as isize;
+        let new_end_column = match edit.text.rfind('\n') {
+            Some(i) => edit.text.len() - i - 1,
+            None => get_column(code, edit.start_byte) + edit.text.len(),
+        };
+        let first_moved = if new_end_column == get_column(code, edit.old_end_byte) {
+            edit.old_end_byte
+        } else {
+            // The first byte following the last line of the edit
+            code[edit.old_end_byte..]
+                .iter()
+                .position(|c| *c == b'\n')
+                .map_or(code.len(), |i| edit.old_end_byte + i + 1)
+        };
+
+        self.spaces = std::mem::take(&mut self.spaces)
+            .into_iter()
+            .filter_map(|((start, end), mut cached)| {
+                if end <= edit.start_byte {
+                    Some(((start, end), cached))
+                } else if start >= first_moved
+                    && edit.old_end_byte < cached.context_start
+                    && (row_delta == 0 && byte_delta == 0 || cached.space.metrics.custom.is_empty())
+                {
+                    cached.space.shift(row_delta, byte_delta);
+                    cached.context_start = cached.context_start.saturating_add_signed(byte_delta);
+                    Some((
+                        (
+                            start.saturating_add_signed(byte_delta),
+                            end.saturating_add_signed(byte_delta),
+                        ),
+                        cached,
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    // Removes the spaces overlapping the ranges whose structure has changed,
+    // together with the code preceding them which is read by their metrics
+    pub(crate) fn invalidate(&mut self, ranges: &[(usize, usize)]) {
+        self.spaces.retain(|(_, end), cached| {
+            !ranges
+                .iter()
+                .any(|(s, e)| *s < *end && cached.context_start < *e)
+        });
+    }
+}
+
+#[inline(always)]
+fn count_rows(text: &[u8]) -> usize {
+    text.iter().filter(|c| **c == b'\n').count()
+}
+
+// Returns the column of a byte, starting from 0
+#[inline(always)]
+fn get_column(code: &[u8], byte: usize) -> usize {
+    byte - code[..byte]
+        .iter()
+        .rposition(|c| *c == b'\n')
+        .map_or(0, |i| i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{RustParser, metrics};
+
+    use super::*;
+
+    fn check_document(document: &Document<RustParser>) {
+        let parser = RustParser::new(document.code().to_vec(), Path::new("foo.rs"), None).unwrap();
+        let space = metrics(&parser, Path::new("foo.rs")).unwrap();
+
+        assert_eq!(
+            serde_json::to_value(document.metrics().unwrap()).unwrap(),
+            serde_json::to_value(space).unwrap()
+        );
+    }
+
+    #[test]
+    fn document_reuses_unchanged_spaces() {
+        let source_code = "fn f(a: u32) -> u32 {\n    a + 1\n}\n\nfn g(b: u32) -> u32 {\n    if b > 0 { b } else { 0 }\n}\n";
+        let mut document = Document::<RustParser>::new(
+            source_code.into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(document.reused_spaces(), 0);
+
+        // Edit the body of f
+        let start_byte = source_code.find("a + 1").unwrap();
+        let edit = InputEdit {
+            start_byte,
+            old_end_byte: start_byte + 5,
+            text: "if a > 1 { a } else { 1 }".to_string(),
+        };
+        document.edit(&[edit]).unwrap();
+
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+
+        // Edit the body of g
+        let start_byte = document
+            .code()
+            .windows(5)
+            .position(|w| w == b"b > 0")
+            .unwrap();
+        let edit = InputEdit {
+            start_byte,
+            old_end_byte: start_byte + 5,
+            text: "b > 0 && b < 10".to_string(),
+        };
+        document.edit(&[edit]).unwrap();
+
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+    }
+
+    #[test]
+    fn document_moves_reused_spaces() {
+        let source_code =
+            "fn f() {\n    let x = 1;\n}\n\nfn g() {\n    // Comment\n    let y = 2;\n}\n";
+        let mut document = Document::<RustParser>::new(
+            source_code.into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+
+        // Add some lines at the beginning of f
+        let edit = InputEdit {
+            start_byte: 9,
+            old_end_byte: 9,
+            text: "    let z = 0;\n\n".to_string(),
+        };
+        let space = document.edit(&[edit]).unwrap();
+
+        assert_eq!(space.spaces[1].start_line, 7);
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+    }
+
+    #[test]
+    fn document_recomputes_documented_spaces() {
+        let source_code = "pub fn f() {}\n\n/// Doc\npub fn g() {}\n";
+        let mut document = Document::<RustParser>::new(
+            source_code.into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+
+        // Turn the documentation of g into a comment
+        let start_byte = source_code.find("///").unwrap();
+        let edit = InputEdit {
+            start_byte,
+            old_end_byte: start_byte + 3,
+            text: "//".to_string(),
+        };
+        let space = document.edit(&[edit]).unwrap();
+
+        assert_eq!(space.metrics.doc_coverage.documented_sum(), 0.);
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+    }
+
+    #[test]
+    fn document_keeps_nested_spaces() {
+        let source_code = "impl A {\n    fn f() {\n        let x = 1;\n    }\n\n    fn g() {\n        let y = 2;\n    }\n}\n\nfn h() {}\n";
+        let mut document = Document::<RustParser>::new(
+            source_code.into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+
+        // Edit h, so that the impl is reused with its methods
+        let start_byte = source_code.find("{}").unwrap() + 1;
+        let edit = InputEdit {
+            start_byte,
+            old_end_byte: start_byte,
+            text: "let z = 3;".to_string(),
+        };
+        document.edit(&[edit]).unwrap();
+
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+
+        // Edit f, so that g and h are reused
+        let edit = InputEdit {
+            start_byte: source_code.find("1;").unwrap(),
+            old_end_byte: source_code.find("1;").unwrap() + 1,
+            text: "x + 1".to_string(),
+        };
+        document.edit(&[edit]).unwrap();
+
+        assert_eq!(document.reused_spaces(), 2);
+        check_document(&document);
+    }
+
+    #[test]
+    fn document_renames_reused_spaces() {
+        let source_code = "mod a {\n    impl A {\n        fn f() {}\n    }\n}\n";
+        let mut document = Document::<RustParser>::new(
+            source_code.into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+
+        // Rename the module, so that the impl is reused with a new qualifier
+        let edit = InputEdit {
+            start_byte: 4,
+            old_end_byte: 5,
+            text: "b".to_string(),
+        };
+        let space = document.edit(&[edit]).unwrap();
+
+        assert_eq!(
+            space.spaces[0].spaces[0].qualified_name.as_deref(),
+            Some("crate::foo::b::A::f")
+        );
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+    }
+
+    #[test]
+    fn document_numbers_reused_spaces() {
+        let source_code = "fn g() {}\nfn f() {}\n";
+        let mut document = Document::<RustParser>::new(
+            source_code.into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+
+        // Rename g as f, so that the reused f is numbered
+        let edit = InputEdit {
+            start_byte: 3,
+            old_end_byte: 4,
+            text: "f".to_string(),
+        };
+        let space = document.edit(&[edit]).unwrap();
+
+        let names: Vec<_> = space
+            .spaces
+            .iter()
+            .map(|space| space.qualified_name.as_deref())
+            .collect();
+        assert_eq!(names, [Some("crate::foo::f"), Some("crate::foo::f#2")]);
+        assert_eq!(document.reused_spaces(), 1);
+        check_document(&document);
+    }
+
+    #[test]
+    fn document_invalid_edit() {
+        let mut document = Document::<RustParser>::new(
+            "fn é() {}".into(),
+            Path::new("foo.rs"),
+            MetricsOptions::default(),
+        )
+        .unwrap();
+
+        let edit = InputEdit {
+            start_byte: 4,
+            old_end_byte: 100,
+            text: String::new(),
+        };
+        assert!(document.edit(&[edit]).is_err());
+
+        let edit = InputEdit {
+            start_byte: 4,
+            old_end_byte: 4,
+            text: "a".to_string(),
+        };
+        assert_eq!(
+            document.edit(&[edit]).unwrap_err().to_string(),
+            "The edit 4..4 splits a character"
+        );
+        assert_eq!(document.code(), "fn é() {}".as_bytes());
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+use std::str::Utf8Error;
+use std::string::FromUtf8Error;
+use std::time::Duration;
+
+/// The errors returned by the entry points of this crate.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{metrics_from_file, Error, MetricsOptions};
+///
+/// // A missing file
+/// let path = Path::new("missing.rs");
+/// let error = metrics_from_file(&path, &MetricsOptions::default()).unwrap_err();
+/// assert!(matches!(error, Error::Io(_)));
+///
+/// // A file written in an unsupported language
+/// let path = Path::new("Cargo.toml");
+/// let error = metrics_from_file(&path, &MetricsOptions::default()).unwrap_err();
+/// assert!(matches!(error, Error::UnknownLanguage(_)));
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// No language could be found for a file
+    UnknownLanguage(PathBuf),
+    /// A file could not be read or the output could not be written
+    Io(io::Error),
+    /// A code is not valid UTF-8
+    InvalidUtf8(Utf8Error),
+    /// A file could not be parsed, or no space could be computed
+    /// from its syntax tree
+    Parse(PathBuf),
+    /// The analysis of a code has taken more than the given time
+    Timeout(Duration),
+    /// An argument, such as a query or a rewrite rule, is not valid
+    InvalidInput(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownLanguage(path) => {
+                write!(f, "No language found for the file {}", path.display())
+            }
+            Error::Io(e) => write!(f, "I/O error: {e}"),
+            Error::InvalidUtf8(e) => write!(f, "The code is not valid UTF-8: {e}"),
+            Error::Parse(path) => write!(f, "The code of {} cannot be parsed", path.display()),
+            Error::Timeout(timeout) => write!(f, "The analysis has timed out after {timeout:?}"),
+            Error::InvalidInput(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::InvalidUtf8(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        Error::InvalidUtf8(e)
+    }
+}
+
+impl From<FromUtf8Error> for Error {
+    fn from(e: FromUtf8Error) -> Self {
+        Error::InvalidUtf8(e.utf8_error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        let error = Error::from(String::from_utf8(vec![0xFF, b'a']).unwrap_err());
+        assert_eq!(
+            error.to_string(),
+            "The code is not valid UTF-8: invalid utf-8 sequence of 1 bytes from index 0"
+        );
+        assert_eq!(
+            Error::UnknownLanguage(PathBuf::from("foo.bar")).to_string(),
+            "No language found for the file foo.bar"
+        );
+    }
+}
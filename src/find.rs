@@ -1,12 +1,15 @@
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::error::Error;
 use crate::node::Node;
 
 use crate::dump::*;
+use crate::query::*;
 use crate::traits::*;
 
 /// Finds the types of nodes specified in the input slice.
-pub fn find<'a, T: ParserTrait>(parser: &'a T, filters: &[String]) -> Option<Vec<Node<'a>>> {
+pub fn find<'a, T: ParserTrait>(parser: &'a T, filters: &[String]) -> Result<Vec<Node<'a>>, Error> {
     let filters = parser.get_filters(filters);
     let node = parser.get_root();
     let mut cursor = node.cursor();
@@ -33,7 +36,7 @@ pub fn find<'a, T: ParserTrait>(parser: &'a T, filters: &[String]) -> Option<Vec
             }
         }
     }
-    Some(good)
+    Ok(good)
 }
 
 /// Configuration options for finding different
@@ -44,6 +47,10 @@ pub struct FindCfg {
     pub path: PathBuf,
     /// Types of nodes to find
     pub filters: Vec<String>,
+    /// The tree-sitter query capturing the nodes to find, replacing the filters
+    ///
+    /// The query must be compiled for the language of the code
+    pub query: Option<Arc<Query>>,
     /// The first line of code considered in the search
     ///
     /// If `None`, the search starts from the
@@ -61,18 +68,35 @@ pub struct Find {
 }
 
 impl Callback for Find {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = FindCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        if let Some(good) = find(parser, &cfg.filters) {
+        if let Some(query) = &cfg.query {
+            let good = find_query(parser, query)?;
             if !good.is_empty() {
                 println!("In file {}", cfg.path.to_str().unwrap());
-                for node in good {
-                    dump_node(parser.get_code(), &node, 1, cfg.line_start, cfg.line_end)?;
+                for capture in good {
+                    print!("@{} ", capture.name);
+                    dump_node(
+                        parser.get_code(),
+                        &capture.node,
+                        1,
+                        cfg.line_start,
+                        cfg.line_end,
+                    )?;
                 }
                 println!();
             }
+            return Ok(());
+        }
+        let good = find(parser, &cfg.filters)?;
+        if !good.is_empty() {
+            println!("In file {}", cfg.path.to_str().unwrap());
+            for node in good {
+                dump_node(parser.get_code(), &node, 1, cfg.line_start, cfg.line_end)?;
+            }
+            println!();
         }
         Ok(())
     }
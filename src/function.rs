@@ -4,6 +4,7 @@ use std::path::PathBuf;
 use serde::Serialize;
 use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
 
+use crate::error::Error;
 use crate::traits::*;
 
 use crate::checker::Checker;
@@ -124,10 +125,10 @@ pub struct Function {
 }
 
 impl Callback for Function {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = FunctionCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        dump_spans(function(parser), cfg.path)
+        Ok(dump_spans(function(parser), cfg.path)?)
     }
 }
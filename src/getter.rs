@@ -1,10 +1,46 @@
 use crate::metrics::halstead::HalsteadType;
+use crate::metrics::statements::StatementKind;
 
 use crate::spaces::SpaceKind;
 use crate::traits::Search;
 
 use crate::*;
 
+macro_rules! get_statement_kind {
+    ($language:ident) => {
+        fn get_statement_kind(node: &Node) -> Option<StatementKind> {
+            match node.kind_id().into() {
+                $language::LexicalDeclaration | $language::VariableDeclaration => {
+                    Some(StatementKind::Declaration)
+                }
+                $language::ExpressionStatement => {
+                    node.child(0)
+                        .and_then(|child| match child.kind_id().into() {
+                            $language::AssignmentExpression
+                            | $language::AugmentedAssignmentExpression
+                            | $language::UpdateExpression => Some(StatementKind::Assignment),
+                            _ if Self::is_call(&child) => Some(StatementKind::Call),
+                            _ => None,
+                        })
+                }
+                $language::IfStatement if Self::is_else_if(node) => None,
+                $language::IfStatement
+                | $language::SwitchStatement
+                | $language::TryStatement
+                | $language::BreakStatement
+                | $language::ContinueStatement
+                | $language::ThrowStatement => Some(StatementKind::ControlFlow),
+                $language::ForStatement
+                | $language::ForInStatement
+                | $language::WhileStatement
+                | $language::DoStatement => Some(StatementKind::Loop),
+                $language::ReturnStatement => Some(StatementKind::Return),
+                _ => None,
+            }
+        }
+    };
+}
+
 macro_rules! get_operator {
     ($language:ident) => {
         #[inline(always)]
@@ -20,6 +56,22 @@ macro_rules! get_operator {
     };
 }
 
+// The name of an object literal, as the variable or the key
+// it is assigned to: var obj = { foo: function() {} }
+macro_rules! get_object_name {
+    ($language:ident, $node:ident, $code:ident) => {{
+        let name = $node
+            .parent()
+            .filter(|_| $node.kind_id() == $language::Object)
+            .and_then(|parent| match parent.kind_id().into() {
+                $language::Pair => parent.child_by_field_name("key"),
+                $language::VariableDeclarator => parent.child_by_field_name("name"),
+                _ => None,
+            });
+        name.and_then(|name| std::str::from_utf8(&$code[name.start_byte()..name.end_byte()]).ok())
+    }};
+}
+
 pub trait Getter {
     fn get_func_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
         Self::get_func_space_name(node, code)
@@ -27,7 +79,7 @@ pub trait Getter {
 
     fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
         // we're in a function or in a class
-        if let Some(name) = node.child_by_field_name("name") {
+        if let Some(name) = Self::get_func_space_name_node(node) {
             let code = &code[name.start_byte()..name.end_byte()];
             std::str::from_utf8(code).ok()
         } else {
@@ -35,6 +87,12 @@ pub trait Getter {
         }
     }
 
+    // The node of the name of a function space, which can be
+    // outside of the space, as the key of a pair
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
+        node.child_by_field_name("name")
+    }
+
     fn get_space_kind(_node: &Node) -> SpaceKind {
         SpaceKind::Unknown
     }
@@ -46,6 +104,35 @@ pub trait Getter {
     fn get_operator_id_as_str(_id: u16) -> &'static str {
         ""
     }
+
+    fn get_statement_kind(_node: &Node) -> Option<StatementKind> {
+        None
+    }
+
+    // The name of a space in a qualified name
+    fn get_qualified_space_name(node: &Node, code: &[u8]) -> Option<String> {
+        Self::get_func_space_name(node, code).map(String::from)
+    }
+
+    // The name of a namespace which is not a space, as a module
+    fn get_namespace_name<'a>(_node: &Node, _code: &'a [u8]) -> Option<&'a str> {
+        None
+    }
+
+    // The name of the package of a unit
+    fn get_package_name<'a>(_node: &Node, _code: &'a [u8]) -> Option<&'a str> {
+        None
+    }
+
+    // The name of the module of a file, derived from the components of its
+    // path without extension
+    fn get_module_name(path: &[String]) -> Option<String> {
+        (!path.is_empty()).then(|| path.join(Self::get_qualified_name_separator()))
+    }
+
+    fn get_qualified_name_separator() -> &'static str {
+        "."
+    }
 }
 
 impl Getter for PythonCode {
@@ -58,6 +145,15 @@ impl Getter for PythonCode {
         }
     }
 
+    fn get_module_name(path: &[String]) -> Option<String> {
+        // An `__init__.py` file is the module of its package
+        let path = match path.split_last() {
+            Some((name, package)) if name == "__init__" => package,
+            _ => path,
+        };
+        (!path.is_empty()).then(|| path.join("."))
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Python::*;
 
@@ -89,6 +185,30 @@ impl Getter for PythonCode {
     fn get_operator_id_as_str(id: u16) -> &'static str {
         Into::<Python>::into(id).into()
     }
+
+    fn get_statement_kind(node: &Node) -> Option<StatementKind> {
+        match node.kind_id().into() {
+            Python::ExpressionStatement => {
+                node.child(0)
+                    .and_then(|child| match child.kind_id().into() {
+                        Python::Assignment | Python::AugmentedAssignment => {
+                            Some(StatementKind::Assignment)
+                        }
+                        _ if Self::is_call(&child) => Some(StatementKind::Call),
+                        _ => None,
+                    })
+            }
+            Python::IfStatement
+            | Python::MatchStatement
+            | Python::TryStatement
+            | Python::BreakStatement
+            | Python::ContinueStatement
+            | Python::RaiseStatement => Some(StatementKind::ControlFlow),
+            Python::ForStatement | Python::WhileStatement => Some(StatementKind::Loop),
+            Python::ReturnStatement => Some(StatementKind::Return),
+            _ => None,
+        }
+    }
 }
 
 impl Getter for MozjsCode {
@@ -108,31 +228,22 @@ impl Getter for MozjsCode {
         }
     }
 
-    fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+    fn get_namespace_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        get_object_name!(Mozjs, node, code)
+    }
+
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
         if let Some(name) = node.child_by_field_name("name") {
-            let code = &code[name.start_byte()..name.end_byte()];
-            std::str::from_utf8(code).ok()
+            Some(name)
         } else {
             // We can be in a pair: foo: function() {}
             // Or in a variable declaration: var aFun = function() {}
-            if let Some(parent) = node.parent() {
-                match parent.kind_id().into() {
-                    Mozjs::Pair => {
-                        if let Some(name) = parent.child_by_field_name("key") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    Mozjs::VariableDeclarator => {
-                        if let Some(name) = parent.child_by_field_name("name") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    _ => {}
-                }
+            let parent = node.parent()?;
+            match parent.kind_id().into() {
+                Mozjs::Pair => parent.child_by_field_name("key"),
+                Mozjs::VariableDeclarator => parent.child_by_field_name("name"),
+                _ => None,
             }
-            Some("<anonymous>")
         }
     }
 
@@ -157,6 +268,8 @@ impl Getter for MozjsCode {
     }
 
     get_operator!(Mozjs);
+
+    get_statement_kind!(Mozjs);
 }
 
 impl Getter for JavascriptCode {
@@ -176,31 +289,22 @@ impl Getter for JavascriptCode {
         }
     }
 
-    fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+    fn get_namespace_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        get_object_name!(Javascript, node, code)
+    }
+
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
         if let Some(name) = node.child_by_field_name("name") {
-            let code = &code[name.start_byte()..name.end_byte()];
-            std::str::from_utf8(code).ok()
+            Some(name)
         } else {
             // We can be in a pair: foo: function() {}
             // Or in a variable declaration: var aFun = function() {}
-            if let Some(parent) = node.parent() {
-                match parent.kind_id().into() {
-                    Mozjs::Pair => {
-                        if let Some(name) = parent.child_by_field_name("key") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    Mozjs::VariableDeclarator => {
-                        if let Some(name) = parent.child_by_field_name("name") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    _ => {}
-                }
+            let parent = node.parent()?;
+            match parent.kind_id().into() {
+                Mozjs::Pair => parent.child_by_field_name("key"),
+                Mozjs::VariableDeclarator => parent.child_by_field_name("name"),
+                _ => None,
             }
-            Some("<anonymous>")
         }
     }
 
@@ -225,6 +329,8 @@ impl Getter for JavascriptCode {
     }
 
     get_operator!(Javascript);
+
+    get_statement_kind!(Javascript);
 }
 
 impl Getter for TypescriptCode {
@@ -245,31 +351,28 @@ impl Getter for TypescriptCode {
         }
     }
 
-    fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+    fn get_namespace_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        if node.kind_id() == Typescript::InternalModule {
+            node.child_by_field_name("name").and_then(|name| {
+                std::str::from_utf8(&code[name.start_byte()..name.end_byte()]).ok()
+            })
+        } else {
+            get_object_name!(Typescript, node, code)
+        }
+    }
+
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
         if let Some(name) = node.child_by_field_name("name") {
-            let code = &code[name.start_byte()..name.end_byte()];
-            std::str::from_utf8(code).ok()
+            Some(name)
         } else {
             // We can be in a pair: foo: function() {}
             // Or in a variable declaration: var aFun = function() {}
-            if let Some(parent) = node.parent() {
-                match parent.kind_id().into() {
-                    Mozjs::Pair => {
-                        if let Some(name) = parent.child_by_field_name("key") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    Mozjs::VariableDeclarator => {
-                        if let Some(name) = parent.child_by_field_name("name") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    _ => {}
-                }
+            let parent = node.parent()?;
+            match parent.kind_id().into() {
+                Mozjs::Pair => parent.child_by_field_name("key"),
+                Mozjs::VariableDeclarator => parent.child_by_field_name("name"),
+                _ => None,
             }
-            Some("<anonymous>")
         }
     }
 
@@ -294,6 +397,8 @@ impl Getter for TypescriptCode {
     }
 
     get_operator!(Typescript);
+
+    get_statement_kind!(Typescript);
 }
 
 impl Getter for TsxCode {
@@ -314,31 +419,28 @@ impl Getter for TsxCode {
         }
     }
 
-    fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+    fn get_namespace_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        if node.kind_id() == Tsx::InternalModule {
+            node.child_by_field_name("name").and_then(|name| {
+                std::str::from_utf8(&code[name.start_byte()..name.end_byte()]).ok()
+            })
+        } else {
+            get_object_name!(Tsx, node, code)
+        }
+    }
+
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
         if let Some(name) = node.child_by_field_name("name") {
-            let code = &code[name.start_byte()..name.end_byte()];
-            std::str::from_utf8(code).ok()
+            Some(name)
         } else {
             // We can be in a pair: foo: function() {}
             // Or in a variable declaration: var aFun = function() {}
-            if let Some(parent) = node.parent() {
-                match parent.kind_id().into() {
-                    Mozjs::Pair => {
-                        if let Some(name) = parent.child_by_field_name("key") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    Mozjs::VariableDeclarator => {
-                        if let Some(name) = parent.child_by_field_name("name") {
-                            let code = &code[name.start_byte()..name.end_byte()];
-                            return std::str::from_utf8(code).ok();
-                        }
-                    }
-                    _ => {}
-                }
+            let parent = node.parent()?;
+            match parent.kind_id().into() {
+                Mozjs::Pair => parent.child_by_field_name("key"),
+                Mozjs::VariableDeclarator => parent.child_by_field_name("name"),
+                _ => None,
             }
-            Some("<anonymous>")
         }
     }
 
@@ -363,21 +465,16 @@ impl Getter for TsxCode {
     }
 
     get_operator!(Tsx);
+
+    get_statement_kind!(Tsx);
 }
 
 impl Getter for RustCode {
-    fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
         // we're in a function or in a class or an impl
         // for an impl: we've  'impl ... type {...'
-        if let Some(name) = node
-            .child_by_field_name("name")
+        node.child_by_field_name("name")
             .or_else(|| node.child_by_field_name("type"))
-        {
-            let code = &code[name.start_byte()..name.end_byte()];
-            std::str::from_utf8(code).ok()
-        } else {
-            Some("<anonymous>")
-        }
     }
 
     fn get_space_kind(node: &Node) -> SpaceKind {
@@ -392,6 +489,53 @@ impl Getter for RustCode {
         }
     }
 
+    fn get_qualified_space_name(node: &Node, code: &[u8]) -> Option<String> {
+        let name = Self::get_func_space_name(node, code)?;
+        // The impls of different traits for a type have the same name
+        let trait_name = node
+            .child_by_field_name("trait")
+            .filter(|_| node.kind_id() == Rust::ImplItem)
+            .and_then(|name| std::str::from_utf8(&code[name.start_byte()..name.end_byte()]).ok());
+        match trait_name {
+            Some(trait_name) => Some(format!("<{name} as {trait_name}>")),
+            None => Some(name.to_string()),
+        }
+    }
+
+    fn get_module_name(path: &[String]) -> Option<String> {
+        // The crate root is the last `src` directory, if any
+        let start = path
+            .iter()
+            .take(path.len().saturating_sub(1))
+            .rposition(|name| name == "src")
+            .map_or(0, |i| i + 1);
+        let mut module = &path[start..];
+        let is_root = matches!(module, [name] if name == "lib" || name == "main");
+        if is_root || module.last().is_some_and(|name| name == "mod") {
+            module = &module[..module.len() - 1];
+        }
+        Some(
+            std::iter::once("crate")
+                .chain(module.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join("::"),
+        )
+    }
+
+    fn get_namespace_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        if node.kind_id() == Rust::ModItem {
+            node.child_by_field_name("name").and_then(|name| {
+                std::str::from_utf8(&code[name.start_byte()..name.end_byte()]).ok()
+            })
+        } else {
+            None
+        }
+    }
+
+    fn get_qualified_name_separator() -> &'static str {
+        "::"
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Rust::*;
 
@@ -427,15 +571,99 @@ impl Getter for RustCode {
     }
 
     get_operator!(Rust);
+
+    fn get_statement_kind(node: &Node) -> Option<StatementKind> {
+        match node.kind_id().into() {
+            Rust::LetDeclaration => Some(StatementKind::Declaration),
+            Rust::ExpressionStatement => {
+                node.child(0)
+                    .and_then(|child| match child.kind_id().into() {
+                        Rust::AssignmentExpression | Rust::CompoundAssignmentExpr => {
+                            Some(StatementKind::Assignment)
+                        }
+                        Rust::MacroInvocation => Some(StatementKind::Call),
+                        _ if Self::is_call(&child) => Some(StatementKind::Call),
+                        _ => None,
+                    })
+            }
+            Rust::IfExpression if Self::is_else_if(node) => None,
+            Rust::IfExpression
+            | Rust::MatchExpression
+            | Rust::BreakExpression
+            | Rust::ContinueExpression => Some(StatementKind::ControlFlow),
+            Rust::ForExpression | Rust::WhileExpression | Rust::LoopExpression => {
+                Some(StatementKind::Loop)
+            }
+            Rust::ReturnExpression => Some(StatementKind::Return),
+            // The final expression of the body of a function is returned
+            _ if is_rust_tail_expression(node) => Some(StatementKind::Return),
+            _ => None,
+        }
+    }
+}
+
+// Checks whether a node is the final expression of the body of a function,
+// as `b` in `fn f() -> u32 { let b = 1; b }`
+fn is_rust_tail_expression(node: &Node) -> bool {
+    use Rust::*;
+
+    // The statements and the declarations are not expressions
+    if matches!(
+        node.kind_id().into(),
+        ExpressionStatement
+            | LetDeclaration
+            | EmptyStatement
+            | LineComment
+            | BlockComment
+            | AttributeItem
+            | InnerAttributeItem
+            | ConstItem
+            | StaticItem
+            | FunctionItem
+            | StructItem
+            | EnumItem
+            | UnionItem
+            | TypeItem
+            | TraitItem
+            | ImplItem
+            | ModItem
+            | ForeignModItem
+            | UseDeclaration
+            | ExternCrateDeclaration
+            | MacroDefinition
+    ) || !node.is_named()
+    {
+        return false;
+    }
+    node.parent().is_some_and(|block| {
+        block.kind_id() == Block
+            && block.parent().is_some_and(|function| {
+                matches!(function.kind_id().into(), FunctionItem | ClosureExpression)
+            })
+            && block
+                .children()
+                .filter(|child| {
+                    child.is_named()
+                        && !matches!(child.kind_id().into(), LineComment | BlockComment)
+                })
+                .last()
+                .is_some_and(|last| last.id() == node.id())
+    })
 }
 
 impl Getter for CppCode {
     fn get_func_space_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        // A function without a name is not anonymous
+        let name = Self::get_func_space_name_node(node)?;
+        let code = &code[name.start_byte()..name.end_byte()];
+        std::str::from_utf8(code).ok()
+    }
+
+    fn get_func_space_name_node<'a>(node: &Node<'a>) -> Option<Node<'a>> {
         match node.kind_id().into() {
             Cpp::FunctionDefinition | Cpp::FunctionDefinition2 | Cpp::FunctionDefinition3 => {
                 if let Some(op_cast) = node.first_child(|id| Cpp::OperatorCast == id) {
-                    let code = &code[op_cast.start_byte()..op_cast.end_byte()];
-                    return std::str::from_utf8(code).ok();
+                    return Some(op_cast);
                 }
                 // we're in a function_definition so need to get the declarator
                 if let Some(declarator) = node.child_by_field_name("declarator") {
@@ -458,8 +686,7 @@ impl Getter for CppCode {
                                 | Cpp::QualifiedIdentifier4
                                 | Cpp::TemplateFunction
                                 | Cpp::TemplateMethod => {
-                                    let code = &code[first.start_byte()..first.end_byte()];
-                                    return std::str::from_utf8(code).ok();
+                                    return Some(first);
                                 }
                                 _ => {}
                             }
@@ -469,8 +696,7 @@ impl Getter for CppCode {
             }
             _ => {
                 if let Some(name) = node.child_by_field_name("name") {
-                    let code = &code[name.start_byte()..name.end_byte()];
-                    return std::str::from_utf8(code).ok();
+                    return Some(name);
                 }
             }
         }
@@ -490,6 +716,15 @@ impl Getter for CppCode {
         }
     }
 
+    // The names of C++ namespaces do not depend on the files
+    fn get_module_name(_path: &[String]) -> Option<String> {
+        None
+    }
+
+    fn get_qualified_name_separator() -> &'static str {
+        "::"
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Cpp::*;
 
@@ -514,6 +749,39 @@ impl Getter for CppCode {
     }
 
     get_operator!(Cpp);
+
+    fn get_statement_kind(node: &Node) -> Option<StatementKind> {
+        match node.kind_id().into() {
+            Cpp::Declaration => node
+                .parent()
+                .filter(|parent| parent.kind_id() == Cpp::CompoundStatement)
+                .map(|_| StatementKind::Declaration),
+            Cpp::ExpressionStatement | Cpp::ExpressionStatement2 => {
+                node.child(0)
+                    .and_then(|child| match child.kind_id().into() {
+                        Cpp::AssignmentExpression
+                        | Cpp::AssignmentExpression2
+                        | Cpp::UpdateExpression => Some(StatementKind::Assignment),
+                        _ if Self::is_call(&child) => Some(StatementKind::Call),
+                        _ => None,
+                    })
+            }
+            Cpp::IfStatement if Self::is_else_if(node) => None,
+            Cpp::IfStatement
+            | Cpp::SwitchStatement
+            | Cpp::TryStatement
+            | Cpp::TryStatement2
+            | Cpp::BreakStatement
+            | Cpp::ContinueStatement
+            | Cpp::GotoStatement
+            | Cpp::ThrowStatement => Some(StatementKind::ControlFlow),
+            Cpp::ForStatement | Cpp::ForRangeLoop | Cpp::WhileStatement | Cpp::DoStatement => {
+                Some(StatementKind::Loop)
+            }
+            Cpp::ReturnStatement => Some(StatementKind::Return),
+            _ => None,
+        }
+    }
 }
 
 impl Getter for PreprocCode {}
@@ -532,6 +800,19 @@ impl Getter for JavaCode {
         }
     }
 
+    fn get_package_name<'a>(node: &Node, code: &'a [u8]) -> Option<&'a str> {
+        node.first_child(|id| id == Java::PackageDeclaration)
+            .and_then(|package| {
+                package.first_child(|id| id == Java::ScopedIdentifier || id == Java::Identifier)
+            })
+            .and_then(|name| std::str::from_utf8(&code[name.start_byte()..name.end_byte()]).ok())
+    }
+
+    // The names of Java classes depend on their packages, not on the files
+    fn get_module_name(_path: &[String]) -> Option<String> {
+        None
+    }
+
     fn get_op_type(node: &Node) -> HalsteadType {
         use Java::*;
         // Some guides that informed grammar choice for Halstead
@@ -574,6 +855,47 @@ impl Getter for JavaCode {
             _ => typ.into(),
         }
     }
+
+    fn get_statement_kind(node: &Node) -> Option<StatementKind> {
+        match node.kind_id().into() {
+            Java::LocalVariableDeclaration => Some(StatementKind::Declaration),
+            Java::ExpressionStatement => {
+                node.child(0)
+                    .and_then(|child| match child.kind_id().into() {
+                        Java::AssignmentExpression | Java::UpdateExpression => {
+                            Some(StatementKind::Assignment)
+                        }
+                        Java::ObjectCreationExpression => Some(StatementKind::Call),
+                        _ if Self::is_call(&child) => Some(StatementKind::Call),
+                        _ => None,
+                    })
+            }
+            // An else-if is the alternative of its parent if statement
+            Java::IfStatement
+                if node.parent().is_some_and(|parent| {
+                    parent.kind_id() == Java::IfStatement
+                        && parent
+                            .child_by_field_name("alternative")
+                            .is_some_and(|alternative| alternative.id() == node.id())
+                }) =>
+            {
+                None
+            }
+            Java::IfStatement
+            | Java::SwitchExpression
+            | Java::TryStatement
+            | Java::TryWithResourcesStatement
+            | Java::BreakStatement
+            | Java::ContinueStatement
+            | Java::ThrowStatement => Some(StatementKind::ControlFlow),
+            Java::ForStatement
+            | Java::EnhancedForStatement
+            | Java::WhileStatement
+            | Java::DoStatement => Some(StatementKind::Loop),
+            Java::ReturnStatement => Some(StatementKind::Return),
+            _ => None,
+        }
+    }
 }
 
 impl Getter for KotlinCode {}
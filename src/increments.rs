@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::node::Node;
+use crate::spaces::metrics_with_increments;
+
+use crate::dump_increments::*;
+use crate::traits::*;
+
+/// A single increment of a complexity metric.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Increment {
+    /// The line of the node which caused the increment
+    pub line: usize,
+    /// The column of the node which caused the increment
+    pub column: usize,
+    /// The kind of the node which caused the increment
+    pub kind: &'static str,
+    /// The value added to the metric, nesting penalty included
+    pub value: usize,
+    /// The nesting penalty contained in the value
+    pub nesting: usize,
+    /// A human-readable explanation of the increment
+    pub reason: String,
+}
+
+impl Increment {
+    pub(crate) fn new(node: &Node, value: usize, nesting: usize, reason: String) -> Self {
+        let (row, column) = node.start_position();
+        Self {
+            line: row + 1,
+            column: column + 1,
+            kind: node.kind(),
+            value,
+            nesting,
+            reason,
+        }
+    }
+}
+
+/// The increments of the complexity metrics of a space.
+///
+/// Only the increments caused by the nodes directly contained in a space
+/// are listed, the ones of its subspaces are stored in the subspaces.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Increments {
+    /// `Cognitive Complexity` increments
+    pub cognitive: Vec<Increment>,
+    /// `Cyclomatic` increments
+    pub cyclomatic: Vec<Increment>,
+}
+
+impl Increments {
+    /// Returns the sum of the `Cognitive Complexity` increments on a line.
+    pub fn cognitive_on_line(&self, line: usize) -> usize {
+        sum_on_line(&self.cognitive, line)
+    }
+
+    /// Returns the sum of the `Cyclomatic` increments on a line.
+    pub fn cyclomatic_on_line(&self, line: usize) -> usize {
+        sum_on_line(&self.cyclomatic, line)
+    }
+
+    // Moves the increments by `delta` lines
+    pub(crate) fn shift_lines(&mut self, delta: isize) {
+        for inc in self.cognitive.iter_mut().chain(self.cyclomatic.iter_mut()) {
+            inc.line = inc.line.saturating_add_signed(delta);
+        }
+    }
+}
+
+#[inline(always)]
+fn sum_on_line(increments: &[Increment], line: usize) -> usize {
+    increments
+        .iter()
+        .filter(|inc| inc.line == line)
+        .map(|inc| inc.value)
+        .sum()
+}
+
+// Turns a node kind into something readable: `if_statement` becomes `if`,
+// `for_in_statement` becomes `for in` and so on
+pub(crate) fn humanize_kind(kind: &str) -> String {
+    let kind = kind
+        .strip_suffix("_statement")
+        .or_else(|| kind.strip_suffix("_expression"))
+        .or_else(|| kind.strip_suffix("_clause"))
+        .unwrap_or(kind);
+    kind.replace('_', " ")
+}
+
+/// Configuration options for explaining the complexity
+/// of the functions in a code.
+#[derive(Debug)]
+pub struct ExplainCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+}
+
+pub struct Explain {
+    _guard: (),
+}
+
+impl Callback for Explain {
+    type Res = Result<(), Error>;
+    type Cfg = ExplainCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        let space = metrics_with_increments(parser, &cfg.path)?;
+        Ok(dump_increments(&space, parser.get_code())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_func_space_with_options;
+    use crate::{FuncSpace, JavaParser, MetricsOptions, ParserTrait, PythonParser, RustParser};
+
+    use super::*;
+
+    fn check_increments<T: ParserTrait, F: Fn(FuncSpace)>(source: &str, filename: &str, check: F) {
+        let options = MetricsOptions {
+            increments: true,
+            ..MetricsOptions::default()
+        };
+        check_func_space_with_options::<T, _>(source, filename, &options, check)
+    }
+
+    #[test]
+    fn increments_are_not_computed_by_default() {
+        crate::tools::check_func_space::<RustParser, _>(
+            "fn f(a: bool) { if a {} }",
+            "foo.rs",
+            |space| {
+                assert!(space.increments.is_none());
+                assert!(space.spaces[0].increments.is_none());
+            },
+        );
+    }
+
+    #[test]
+    fn rust_increments() {
+        check_increments::<RustParser, _>(
+            "fn f(a: bool, b: bool) {
+                 if a {
+                     for i in 0..10 {
+                         if a && b || a {}
+                     }
+                 }
+             }",
+            "foo.rs",
+            |space| {
+                let increments = space.spaces[0].increments.as_ref().unwrap();
+                insta::assert_json_snapshot!(
+                    increments.cognitive,
+                    @r###"
+                    [
+                      {
+                        "line": 2,
+                        "column": 18,
+                        "kind": "if_expression",
+                        "value": 1,
+                        "nesting": 0,
+                        "reason": "if"
+                      },
+                      {
+                        "line": 3,
+                        "column": 22,
+                        "kind": "for_expression",
+                        "value": 2,
+                        "nesting": 1,
+                        "reason": "nested for"
+                      },
+                      {
+                        "line": 4,
+                        "column": 26,
+                        "kind": "if_expression",
+                        "value": 3,
+                        "nesting": 2,
+                        "reason": "nested if"
+                      },
+                      {
+                        "line": 4,
+                        "column": 31,
+                        "kind": "&&",
+                        "value": 1,
+                        "nesting": 0,
+                        "reason": "boolean sequence"
+                      },
+                      {
+                        "line": 4,
+                        "column": 36,
+                        "kind": "||",
+                        "value": 1,
+                        "nesting": 0,
+                        "reason": "boolean sequence change"
+                      }
+                    ]"###
+                );
+                assert_eq!(
+                    increments.cognitive.iter().map(|i| i.value).sum::<usize>() as f64,
+                    space.spaces[0].metrics.cognitive.cognitive()
+                );
+                assert_eq!(increments.cyclomatic.len(), 5);
+                assert_eq!(increments.cognitive_on_line(4), 5);
+                assert_eq!(increments.cyclomatic_on_line(4), 3);
+            },
+        );
+    }
+
+    #[test]
+    fn python_increments() {
+        check_increments::<PythonParser, _>(
+            "def f(a):
+                 if a:
+                     return 1
+                 elif not a:
+                     return 2
+                 else:
+                     return 3",
+            "foo.py",
+            |space| {
+                let increments = space.spaces[0].increments.as_ref().unwrap();
+                let reasons: Vec<_> = increments
+                    .cognitive
+                    .iter()
+                    .map(|i| i.reason.as_str())
+                    .collect();
+                assert_eq!(reasons, ["if", "elif", "else"]);
+                let reasons: Vec<_> = increments
+                    .cyclomatic
+                    .iter()
+                    .map(|i| i.reason.as_str())
+                    .collect();
+                assert_eq!(reasons, ["if", "elif", "else"]);
+            },
+        );
+    }
+
+    #[test]
+    fn java_increments_in_nested_spaces() {
+        check_increments::<JavaParser, _>(
+            "class A {
+                 void f(int x) {
+                     while (x > 0) {
+                         x--;
+                     }
+                 }
+             }",
+            "foo.java",
+            |space| {
+                let class = &space.spaces[0];
+                let method = &class.spaces[0];
+                assert!(class.increments.as_ref().unwrap().cognitive.is_empty());
+                let increments = method.increments.as_ref().unwrap();
+                assert_eq!(increments.cognitive.len(), 1);
+                assert_eq!(increments.cognitive[0].line, 3);
+                assert_eq!(increments.cognitive[0].reason, "while");
+                assert_eq!(increments.cyclomatic[0].kind, "while");
+            },
+        );
+    }
+
+    #[test]
+    fn humanize() {
+        assert_eq!(humanize_kind("if_statement"), "if");
+        assert_eq!(humanize_kind("for_in_statement"), "for in");
+        assert_eq!(humanize_kind("catch_clause"), "catch");
+        assert_eq!(humanize_kind("match_arm"), "match arm");
+        assert_eq!(humanize_kind("&&"), "&&");
+    }
+}
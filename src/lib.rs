@@ -22,6 +22,8 @@
 //!
 //! ## Supported Metrics
 //!
+//! - BOOLEANS: it measures the complexity of the boolean expressions
+//!   of a space, as the maximum number of operands of a condition.
 //! - CC: it calculates the code complexity examining the
 //!   control flow of a program.
 //! - SLOC: it counts the number of lines in a source file.
@@ -31,11 +33,23 @@
 //!   contained in a source file.
 //! - CLOC: it counts the number of comments in a source file.
 //! - BLANK: it counts the number of blank lines in a source file.
+//! - DOC_COVERAGE: it counts the public functions, classes, methods
+//!   and fields, and how many of them are documented.
+//! - DUPLICATION: it counts the lines of a space which belong to
+//!   code clones found across a set of files.
+//! - EXCEPTIONS: it counts the try blocks, catch handlers and throw sites
+//!   of a space, and the handlers catching every exception or empty.
 //! - HALSTEAD: it is a suite that provides a series of information,
 //!   such as the effort required to maintain the analyzed code,
 //!   the size in bits to store the program, the difficulty to understand
 //!   the code, an estimate of the number of bugs present in the codebase,
 //!   and an estimate of the time needed to implement the software.
+//! - IMPORTS: it counts the static and dynamic imports of a space,
+//!   as `use` declarations, `import` statements and `require()` calls.
+//! - LITERALS: it counts the magic numbers and the repeated string
+//!   literals of a space.
+//! - MARKERS: it counts the technical-debt markers, such as TODO
+//!   and FIXME, contained in the comments of a space.
 //! - MI: it is a suite that allows to evaluate the maintainability
 //!   of a software.
 //! - NOM: it counts the number of functions and closures
@@ -43,6 +57,12 @@
 //! - NEXITS: it counts the number of possible exit points
 //!   from a method/function.
 //! - NARGS: it counts the number of arguments of a function/method.
+//! - SAFETY: it counts the unsafe code, the unwraps, the panics and
+//!   the indexing expressions of a Rust space.
+//! - STATEMENTS: it counts the declarations, assignments, calls,
+//!   control flow statements, returns and loops of a space.
+//! - VARIABLES: it measures the span between consecutive references
+//!   to a variable and the number of live variables of a space.
 
 #![allow(clippy::upper_case_acronyms)]
 
@@ -54,6 +74,9 @@ mod macros;
 mod alterator;
 pub use alterator::*;
 
+mod error;
+pub use crate::error::*;
+
 mod node;
 pub use crate::node::*;
 
@@ -75,6 +98,27 @@ pub use crate::spaces::*;
 mod ops;
 pub use crate::ops::*;
 
+mod increments;
+pub use crate::increments::*;
+
+mod debt;
+pub use crate::debt::*;
+
+mod clones;
+pub use crate::clones::*;
+
+mod smells;
+pub use crate::smells::*;
+
+mod naming;
+pub use crate::naming::*;
+
+mod dependencies;
+pub use crate::dependencies::*;
+
+mod dependency_graph;
+pub use crate::dependency_graph::*;
+
 mod find;
 pub use crate::find::*;
 
@@ -87,6 +131,9 @@ pub use crate::ast::*;
 mod count;
 pub use crate::count::*;
 
+mod query;
+pub use crate::query::*;
+
 mod preproc;
 pub use crate::preproc::*;
 
@@ -107,3 +154,18 @@ pub use crate::parser::*;
 
 mod comment_rm;
 pub use crate::comment_rm::*;
+
+mod rewrite;
+pub use crate::rewrite::*;
+
+mod custom_metrics;
+pub use crate::custom_metrics::*;
+
+mod metric_set;
+pub use crate::metric_set::*;
+
+mod document;
+pub use crate::document::*;
+
+mod syntax_errors;
+pub use crate::syntax_errors::*;
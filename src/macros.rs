@@ -28,10 +28,17 @@ macro_rules! implement_metric_trait {
            }
         )+
     );
+    (DocCoverage, $($code:ident),+) => (
+        $(
+           impl DocCoverage for $code {
+               fn compute(_node: &Node, _code: &[u8], _stats: &mut Stats) {}
+           }
+        )+
+    );
     (Halstead, $($code:ident),+) => (
         $(
            impl Halstead for $code {
-               fn compute<'a>(_node: &Node<'a>, _code: &'a [u8], _halstead_maps: &mut HalsteadMaps<'a>) {}
+               fn compute<'a>(_node: &Node<'a>, _code: &'a [u8], _table: &HalsteadTable, _halstead_maps: &mut HalsteadMaps<'a>) {}
            }
         )+
     );
@@ -66,7 +73,7 @@ macro_rules! implement_metric_trait {
 macro_rules! mk_lang {
     ( $( ($camel:ident, $name:ident, $display: expr, $description:expr) ),* ) => {
         /// The list of supported languages.
-        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
         pub enum LANG {
             $(
                 #[doc = $description]
@@ -125,6 +132,8 @@ macro_rules! mk_action {
         /// Runs a function, which implements the [`Callback`] trait,
         /// on a code written in one of the supported languages.
         ///
+        /// Returns an error if the code cannot be parsed.
+        ///
         /// # Examples
         ///
         /// The following example dumps to shell every metric computed using
@@ -133,7 +142,7 @@ macro_rules! mk_action {
         /// ```
         /// use std::path::PathBuf;
         ///
-        /// use rust_code_analysis::{action, Callback, LANG, Metrics, MetricsCfg};
+        /// use rust_code_analysis::{action, Callback, LANG, MetricSet, Metrics, MetricsCfg};
         ///
         /// let source_code = "int a = 42;";
         /// let language = LANG::Cpp;
@@ -145,24 +154,63 @@ macro_rules! mk_action {
         /// // Configuration options used by the function which computes the metrics
         /// let cfg = MetricsCfg {
         ///     path,
+        ///     selection: MetricSet::all(),
         /// };
         ///
-        /// action::<Metrics>(&language, source_as_vec, &cfg.path.clone(), None, cfg);
+        /// action::<Metrics>(&language, source_as_vec, &cfg.path.clone(), None, cfg).unwrap();
         /// ```
         ///
         /// [`Callback`]: trait.Callback.html
         #[inline(always)]
-        pub fn action<T: Callback>(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>, cfg: T::Cfg) -> T::Res {
+        pub fn action<T: Callback>(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>, cfg: T::Cfg) -> Result<T::Res, Error> {
             match lang {
                 $(
                     LANG::$camel => {
-                        let parser = $parser::new(source, path, pr);
-                        T::call(cfg, &parser)
+                        let parser = $parser::new(source, path, pr)?;
+                        Ok(T::call(cfg, &parser))
                     },
                 )*
             }
         }
 
+        /// Runs a function, which implements the [`Callback`] trait
+        /// and returns a `Result`, on a code written in one of the
+        /// supported languages.
+        ///
+        /// Returns an error if the code cannot be parsed or if the function
+        /// fails.
+        ///
+        /// # Examples
+        ///
+        /// The following example dumps to shell the operands and operators
+        /// of the dummy source code.
+        ///
+        /// ```
+        /// use std::path::PathBuf;
+        ///
+        /// use rust_code_analysis::{try_action, OpsCfg, OpsCode, LANG};
+        ///
+        /// let source_code = "let a = 42;";
+        /// let language = LANG::Rust;
+        ///
+        /// // The path to a dummy file used to contain the source code
+        /// let path = PathBuf::from("foo.rs");
+        /// let source_as_vec = source_code.as_bytes().to_vec();
+        ///
+        /// let cfg = OpsCfg { path: path.clone() };
+        ///
+        /// try_action::<OpsCode, _>(&language, source_as_vec, &path, None, cfg).unwrap();
+        /// ```
+        ///
+        /// [`Callback`]: trait.Callback.html
+        #[inline(always)]
+        pub fn try_action<T, R>(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>, cfg: T::Cfg) -> Result<R, Error>
+        where
+            T: Callback<Res = Result<R, Error>>,
+        {
+            action::<T>(lang, source, path, pr, cfg)?
+        }
+
         /// Returns all function spaces data of a code.
         ///
         /// # Examples
@@ -182,11 +230,11 @@ macro_rules! mk_action {
         /// get_function_spaces(&language, source_as_vec, &path, None).unwrap();
         /// ```
         #[inline(always)]
-        pub fn get_function_spaces(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Option<FuncSpace> {
+        pub fn get_function_spaces(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Result<FuncSpace, Error> {
             match lang {
                 $(
                     LANG::$camel => {
-                        let parser = $parser::new(source, &path, pr);
+                        let parser = $parser::new(source, &path, pr)?;
                         metrics(&parser, &path)
                     },
                 )*
@@ -214,11 +262,11 @@ macro_rules! mk_action {
         /// # }
         /// ```
         #[inline(always)]
-        pub fn get_ops(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Option<Ops> {
+        pub fn get_ops(lang: &LANG, source: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Result<Ops, Error> {
             match lang {
                 $(
                     LANG::$camel => {
-                        let parser = $parser::new(source, &path, pr);
+                        let parser = $parser::new(source, &path, pr)?;
                         operands_and_operators(&parser, &path)
                     },
                 )*
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::mi::{MiComplexity, MiFormula};
+
+macro_rules! metric_kinds {
+    ( $( ($kind:ident, $name:expr, $description:expr) ),* $(,)? ) => {
+        /// The list of metrics which can be selected.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum MetricKind {
+            $(
+                #[doc = $description]
+                $kind,
+            )*
+        }
+
+        impl MetricKind {
+            /// All the metrics, in the order of the fields of `CodeMetrics`.
+            pub const ALL: &'static [MetricKind] = &[$(MetricKind::$kind),*];
+
+            /// Returns the name of a metric, as used in the output.
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(MetricKind::$kind => $name,)*
+                }
+            }
+        }
+
+        impl FromStr for MetricKind {
+            type Err = String;
+
+            fn from_str(name: &str) -> Result<Self, Self::Err> {
+                match name {
+                    $($name => Ok(MetricKind::$kind),)*
+                    _ => Err(format!("Unknown metric `{name}`")),
+                }
+            }
+        }
+    };
+}
+
+metric_kinds!(
+    (NArgs, "nargs", "The number of arguments"),
+    (NExits, "nexits", "The number of exit points"),
+    (Cognitive, "cognitive", "The cognitive complexity"),
+    (Cyclomatic, "cyclomatic", "The cyclomatic complexity"),
+    (Halstead, "halstead", "The Halstead suite"),
+    (Loc, "loc", "The lines of code"),
+    (Nom, "nom", "The number of methods"),
+    (Mi, "mi", "The maintainability index"),
+    (Abc, "abc", "The ABC metric"),
+    (Wmc, "wmc", "The weighted methods per class"),
+    (Npm, "npm", "The number of public methods"),
+    (Npa, "npa", "The number of public attributes"),
+    (Duplication, "duplication", "The duplicated lines"),
+    (Markers, "markers", "The technical-debt markers"),
+    (DocCoverage, "doc_coverage", "The documentation coverage"),
+    (Exceptions, "exceptions", "The exception handling"),
+    (
+        Literals,
+        "literals",
+        "The magic numbers and repeated strings"
+    ),
+    (Safety, "safety", "The unsafe code of Rust"),
+    (
+        Booleans,
+        "booleans",
+        "The complexity of boolean expressions"
+    ),
+    (Imports, "imports", "The imports"),
+    (
+        Variables,
+        "variables",
+        "The variable spans and live variables"
+    ),
+    (Statements, "statements", "The statements by category"),
+    (Naming, "naming", "The naming of the declared identifiers"),
+    (
+        Custom,
+        "custom",
+        "The metrics defined outside of this crate"
+    ),
+);
+
+impl fmt::Display for MetricKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// A set of metrics, used to select the metrics to compute.
+///
+/// By default, all the metrics are selected.
+///
+/// # Examples
+///
+/// ```
+/// use rust_code_analysis::{MetricKind, MetricSet};
+///
+/// let set: MetricSet = "loc,cyclomatic".parse().unwrap();
+///
+/// assert!(set.contains(MetricKind::Loc));
+/// assert!(!set.contains(MetricKind::Halstead));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(try_from = "Vec<String>", into = "Vec<String>")]
+pub struct MetricSet(u32);
+
+impl Default for MetricSet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl MetricSet {
+    /// Returns the set of all the metrics.
+    pub fn all() -> Self {
+        MetricKind::ALL
+            .iter()
+            .fold(Self::empty(), |set, kind| set.with(*kind))
+    }
+
+    /// Returns an empty set.
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Returns the set with a metric added.
+    pub fn with(self, kind: MetricKind) -> Self {
+        Self(self.0 | (1 << kind as u32))
+    }
+
+    /// Adds a metric to the set.
+    pub fn insert(&mut self, kind: MetricKind) {
+        *self = self.with(kind);
+    }
+
+    /// Checks whether a metric is in the set.
+    pub fn contains(&self, kind: MetricKind) -> bool {
+        self.0 & (1 << kind as u32) != 0
+    }
+
+    /// Returns the metrics in the set.
+    pub fn iter(&self) -> impl Iterator<Item = MetricKind> + '_ {
+        MetricKind::ALL
+            .iter()
+            .copied()
+            .filter(|kind| self.contains(*kind))
+    }
+
+    /// Returns the set with the metrics needed to compute
+    /// the metrics of this set added.
+    ///
+    /// The `Mi` metric is computed from the `Loc`, `Cyclomatic` and
+    /// `Halstead` metrics, and from the `Cognitive` metric when it is
+    /// the complexity of the given formula, the `Wmc` metric from
+    /// the `Cyclomatic` metric, and the averages of the `Cognitive`,
+    /// `NExits` and `NArgs` metrics from the `Nom` metric.
+    pub fn with_dependencies(self, mi_formula: Option<&MiFormula>) -> Self {
+        let mut set = self;
+        if set.contains(MetricKind::Mi) {
+            set = set
+                .with(MetricKind::Loc)
+                .with(MetricKind::Cyclomatic)
+                .with(MetricKind::Halstead);
+            if mi_formula
+                .is_some_and(|formula| formula.complexity_metric == MiComplexity::Cognitive)
+            {
+                set = set.with(MetricKind::Cognitive);
+            }
+        }
+        if set.contains(MetricKind::Wmc) {
+            set = set.with(MetricKind::Cyclomatic);
+        }
+        if [MetricKind::Cognitive, MetricKind::NExits, MetricKind::NArgs]
+            .iter()
+            .any(|kind| set.contains(*kind))
+        {
+            set = set.with(MetricKind::Nom);
+        }
+        set
+    }
+}
+
+impl FromStr for MetricSet {
+    type Err = String;
+
+    fn from_str(names: &str) -> Result<Self, Self::Err> {
+        names
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .try_fold(Self::empty(), |set, name| Ok(set.with(name.parse()?)))
+    }
+}
+
+impl TryFrom<Vec<String>> for MetricSet {
+    type Error = String;
+
+    fn try_from(names: Vec<String>) -> Result<Self, Self::Error> {
+        names
+            .iter()
+            .try_fold(Self::empty(), |set, name| Ok(set.with(name.parse()?)))
+    }
+}
+
+impl From<MetricSet> for Vec<String> {
+    fn from(set: MetricSet) -> Self {
+        set.iter().map(|kind| kind.name().to_string()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metric_set_parse() {
+        let set: MetricSet = "loc, cyclomatic".parse().unwrap();
+
+        assert_eq!(
+            set.iter().collect::<Vec<_>>(),
+            [MetricKind::Cyclomatic, MetricKind::Loc]
+        );
+        assert_eq!(
+            "loc,foo".parse::<MetricSet>(),
+            Err("Unknown metric `foo`".to_string())
+        );
+        assert_eq!(MetricSet::default().iter().count(), MetricKind::ALL.len());
+    }
+
+    #[test]
+    fn metric_set_dependencies() {
+        let set = MetricSet::empty().with(MetricKind::Mi);
+
+        assert_eq!(
+            set.with_dependencies(None)
+                .iter()
+                .map(|kind| kind.name())
+                .collect::<Vec<_>>(),
+            ["cyclomatic", "halstead", "loc", "mi"]
+        );
+
+        let formula = MiFormula {
+            complexity_metric: MiComplexity::Cognitive,
+            ..MiFormula::default()
+        };
+        assert_eq!(
+            set.with_dependencies(Some(&formula))
+                .iter()
+                .map(|kind| kind.name())
+                .collect::<Vec<_>>(),
+            ["cognitive", "cyclomatic", "halstead", "loc", "nom", "mi"]
+        );
+    }
+}
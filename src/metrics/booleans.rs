@@ -0,0 +1,384 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::cognitive::boolean_operators;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The maximum number of operands of a condition which is not complex.
+pub const COMPLEX_CONDITION_OPERANDS: usize = 4;
+
+/// The `Booleans` metric.
+///
+/// This metric measures the complexity of the boolean expressions
+/// of a space: the maximum number of operands combined by `&&` and `||`
+/// operators in a single condition, the number of conditions having more
+/// than [`COMPLEX_CONDITION_OPERANDS`] operands and the maximum number of
+/// nested negations in a single condition.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    operands: usize,
+    complex_conditions: usize,
+    negation_depth: usize,
+    operands_max: usize,
+    complex_conditions_sum: usize,
+    negation_depth_max: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("booleans", 3)?;
+        st.serialize_field("max_operands", &self.operands_max())?;
+        st.serialize_field("complex_conditions", &self.complex_conditions_sum())?;
+        st.serialize_field("max_negation_depth", &self.negation_depth_max())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "max_operands: {}, complex_conditions: {}, max_negation_depth: {}",
+            self.operands_max(),
+            self.complex_conditions_sum(),
+            self.negation_depth_max()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Booleans` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.operands_max = self.operands_max.max(other.operands_max);
+        self.complex_conditions_sum += other.complex_conditions_sum;
+        self.negation_depth_max = self.negation_depth_max.max(other.negation_depth_max);
+    }
+
+    /// Returns the maximum number of operands of a condition in a space
+    #[inline(always)]
+    pub fn operands(&self) -> f64 {
+        self.operands as f64
+    }
+
+    /// Returns the number of complex conditions in a space
+    #[inline(always)]
+    pub fn complex_conditions(&self) -> f64 {
+        self.complex_conditions as f64
+    }
+
+    /// Returns the maximum number of nested negations
+    /// of a condition in a space
+    #[inline(always)]
+    pub fn negation_depth(&self) -> f64 {
+        self.negation_depth as f64
+    }
+
+    /// Returns the maximum number of operands of a condition
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn operands_max(&self) -> f64 {
+        self.operands_max as f64
+    }
+
+    /// Returns the number of complex conditions in a space and its subspaces
+    #[inline(always)]
+    pub fn complex_conditions_sum(&self) -> f64 {
+        self.complex_conditions_sum as f64
+    }
+
+    /// Returns the maximum number of nested negations of a condition
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn negation_depth_max(&self) -> f64 {
+        self.negation_depth_max as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.operands_max = self.operands_max.max(self.operands);
+        self.complex_conditions_sum += self.complex_conditions;
+        self.negation_depth_max = self.negation_depth_max.max(self.negation_depth);
+    }
+}
+
+pub trait Booleans
+where
+    Self: Checker,
+{
+    /// Returns the number of `&&` and `||` operators which are
+    /// children of a node
+    fn operators(_node: &Node) -> usize {
+        0
+    }
+
+    /// Checks whether a node is a negation, as `!a` or `not a`
+    fn is_negation(_node: &Node) -> bool {
+        false
+    }
+
+    /// Checks whether a node is a parenthesized expression
+    fn is_parenthesized(_node: &Node) -> bool {
+        false
+    }
+
+    fn compute(node: &Node, stats: &mut Stats) {
+        if !is_logical::<Self>(node) {
+            return;
+        }
+
+        // Only the outermost logical expression of a condition is measured
+        let mut parent = node.parent();
+        while let Some(p) = parent.filter(|p| Self::is_parenthesized(p)) {
+            parent = p.parent();
+        }
+        if parent.is_some_and(|p| is_logical::<Self>(&p)) {
+            return;
+        }
+
+        let mut operands = 1;
+        let mut negation_depth = 0;
+        let mut stack = vec![(*node, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            let depth = depth + Self::is_negation(&node) as usize;
+            operands += Self::operators(&node);
+            negation_depth = negation_depth.max(depth);
+            for child in node.children() {
+                if is_logical::<Self>(&child) || Self::is_parenthesized(&child) {
+                    stack.push((child, depth));
+                }
+            }
+        }
+
+        stats.operands = stats.operands.max(operands);
+        stats.negation_depth = stats.negation_depth.max(negation_depth);
+        if operands > COMPLEX_CONDITION_OPERANDS {
+            stats.complex_conditions += 1;
+        }
+    }
+}
+
+#[inline(always)]
+fn is_logical<T: Booleans + ?Sized>(node: &Node) -> bool {
+    T::operators(node) > 0 || T::is_negation(node)
+}
+
+impl Booleans for PythonCode {
+    fn operators(node: &Node) -> usize {
+        if node.kind_id() == Python::BooleanOperator {
+            boolean_operators(node, Python::And, Python::Or).count()
+        } else {
+            0
+        }
+    }
+
+    fn is_negation(node: &Node) -> bool {
+        node.kind_id() == Python::NotOperator
+    }
+
+    fn is_parenthesized(node: &Node) -> bool {
+        node.kind_id() == Python::ParenthesizedExpression
+    }
+}
+
+// The languages whose boolean expressions are binary expressions
+// with `&&` and `||` operators and negations are unary expressions
+// with a `!` operator
+macro_rules! c_style_booleans {
+    ($code:ident, $lang:ident, [$($binary:ident)|+], [$($unary:ident)|+], [$($paren:ident)|+]) => {
+        impl Booleans for $code {
+            fn operators(node: &Node) -> usize {
+                if matches!(node.kind_id().into(), $($lang::$binary)|+) {
+                    boolean_operators(node, $lang::AMPAMP, $lang::PIPEPIPE).count()
+                } else {
+                    0
+                }
+            }
+
+            fn is_negation(node: &Node) -> bool {
+                matches!(node.kind_id().into(), $($lang::$unary)|+)
+                    && node.child(0).is_some_and(|op| op.kind_id() == $lang::BANG)
+            }
+
+            fn is_parenthesized(node: &Node) -> bool {
+                matches!(node.kind_id().into(), $($lang::$paren)|+)
+            }
+        }
+    };
+}
+
+c_style_booleans!(
+    RustCode,
+    Rust,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    MozjsCode,
+    Mozjs,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    JavascriptCode,
+    Javascript,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    TypescriptCode,
+    Typescript,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    TsxCode,
+    Tsx,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    JavaCode,
+    Java,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    KotlinCode,
+    Kotlin,
+    [BinaryExpression],
+    [UnaryExpression],
+    [ParenthesizedExpression]
+);
+c_style_booleans!(
+    CppCode,
+    Cpp,
+    [BinaryExpression | BinaryExpression2],
+    [UnaryExpression | UnaryExpression2],
+    [ParenthesizedExpression | ParenthesizedExpression2]
+);
+
+implement_metric_trait!([Booleans], PreprocCode, CcommentCode);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn python_booleans() {
+        check_metrics::<PythonParser>(
+            "def f(a, b, c, d, e):
+                 if a and (b or not c) and d or e:
+                     return not (a and not b)
+                 return a or b",
+            "foo.py",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.booleans,
+                    @r###"
+                    {
+                      "max_operands": 5.0,
+                      "complex_conditions": 1.0,
+                      "max_negation_depth": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_booleans() {
+        check_metrics::<RustParser>(
+            "fn f(a: bool, b: bool, c: bool, x: i32) -> bool {
+                 let d = -x > 0 && !a;
+                 if !(a && !(b || !c)) || g(a && b) {
+                     return true;
+                 }
+                 d
+             }",
+            "foo.rs",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.booleans,
+                    @r###"
+                    {
+                      "max_operands": 4.0,
+                      "complex_conditions": 0.0,
+                      "max_negation_depth": 3.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_booleans() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 boolean f(boolean a, boolean b, boolean c) {
+                     return a && b || c && !a || b && c;
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.booleans,
+                    @r###"
+                    {
+                      "max_operands": 6.0,
+                      "complex_conditions": 1.0,
+                      "max_negation_depth": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_booleans_per_space() {
+        check_func_space::<JavascriptParser, _>(
+            "function f(a, b, c, d, e) {
+                 return a && b && c && d && e;
+             }
+             function g(a, b) {
+                 return !a || !!b;
+             }",
+            "foo.js",
+            |func_space| {
+                let f = &func_space.spaces[0].metrics.booleans;
+                let g = &func_space.spaces[1].metrics.booleans;
+                assert_eq!(
+                    (f.operands(), f.complex_conditions(), f.negation_depth()),
+                    (5., 1., 0.)
+                );
+                assert_eq!(
+                    (g.operands(), g.complex_conditions(), g.negation_depth()),
+                    (2., 0., 2.)
+                );
+                insta::assert_json_snapshot!(
+                    func_space.metrics.booleans,
+                    @r###"
+                    {
+                      "max_operands": 5.0,
+                      "complex_conditions": 1.0,
+                      "max_negation_depth": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -5,6 +5,7 @@ use serde::ser::{SerializeStruct, Serializer};
 use std::fmt;
 
 use crate::checker::Checker;
+use crate::increments::{Increment, humanize_kind};
 use crate::macros::implement_metric_trait;
 use crate::*;
 
@@ -27,6 +28,7 @@ pub struct Stats {
     nesting: usize,
     total_space_functions: usize,
     boolean_seq: BoolSequence,
+    increments: Option<Vec<Increment>>,
 }
 
 impl Default for Stats {
@@ -39,6 +41,7 @@ impl Default for Stats {
             nesting: 0,
             total_space_functions: 1,
             boolean_seq: BoolSequence::default(),
+            increments: None,
         }
     }
 }
@@ -119,6 +122,51 @@ impl Stats {
     pub(crate) fn finalize(&mut self, total_space_functions: usize) {
         self.total_space_functions = total_space_functions;
     }
+
+    // Starts recording every increment of the metric
+    #[inline(always)]
+    pub(crate) fn record_increments(&mut self) {
+        self.increments = Some(Vec::new());
+    }
+
+    // Returns the recorded increments, if any, in the order of the code
+    #[inline(always)]
+    pub(crate) fn take_increments(&mut self) -> Option<Vec<Increment>> {
+        let mut increments = self.increments.take()?;
+        increments.sort_by_key(|increment| (increment.line, increment.column));
+        Some(increments)
+    }
+
+    // Adds the increment of a boolean operator. The operators are visited
+    // from the root of their expression, as `||` before `&&` in `a && b || c`,
+    // so the sequence is started by the operator which is the first
+    // in the code, not the first visited one.
+    fn add_boolean(&mut self, node: &Node, value: usize, first: bool) {
+        self.structural += value;
+        let Some(increments) = self.increments.as_mut() else {
+            return;
+        };
+        let mut increment = Increment::new(node, value, 0, "boolean sequence change".to_string());
+        if first {
+            increment.reason = "boolean sequence".to_string();
+            self.boolean_seq.start = Some(increments.len());
+        } else if let Some(start) = self.boolean_seq.start {
+            let start_increment = &mut increments[start];
+            if (increment.line, increment.column) < (start_increment.line, start_increment.column) {
+                std::mem::swap(&mut increment.reason, &mut start_increment.reason);
+                self.boolean_seq.start = Some(increments.len());
+            }
+        }
+        increments.push(increment);
+    }
+
+    #[inline(always)]
+    fn add(&mut self, node: &Node, value: usize, nesting: usize, reason: impl FnOnce() -> String) {
+        self.structural += value;
+        if let Some(increments) = self.increments.as_mut() {
+            increments.push(Increment::new(node, value, nesting, reason()));
+        }
+    }
 }
 
 pub trait Cognitive
@@ -132,17 +180,30 @@ where
     );
 }
 
+// Returns the boolean operators, of kind `typs1` or `typs2`,
+// which are children of a node
+pub(crate) fn boolean_operators<'a, T: std::cmp::PartialEq + std::convert::From<u16>>(
+    node: &Node<'a>,
+    typs1: T,
+    typs2: T,
+) -> impl Iterator<Item = Node<'a>> {
+    node.children()
+        .filter(move |child| typs1 == child.kind_id().into() || typs2 == child.kind_id().into())
+}
+
 fn compute_booleans<T: std::cmp::PartialEq + std::convert::From<u16>>(
     node: &Node,
     stats: &mut Stats,
     typs1: T,
     typs2: T,
 ) {
-    for child in node.children() {
-        if typs1 == child.kind_id().into() || typs2 == child.kind_id().into() {
-            stats.structural = stats
-                .boolean_seq
-                .eval_based_on_prev(child.kind_id(), stats.structural)
+    for child in boolean_operators(node, typs1, typs2) {
+        let first = stats.boolean_seq.boolean_op.is_none();
+        let structural = stats
+            .boolean_seq
+            .eval_based_on_prev(child.kind_id(), stats.structural);
+        if structural > stats.structural {
+            stats.add_boolean(&child, structural - stats.structural, first);
         }
     }
 }
@@ -150,15 +211,19 @@ fn compute_booleans<T: std::cmp::PartialEq + std::convert::From<u16>>(
 #[derive(Debug, Default, Clone)]
 struct BoolSequence {
     boolean_op: Option<u16>,
+    // The index of the recorded increment starting the sequence
+    start: Option<usize>,
 }
 
 impl BoolSequence {
     fn reset(&mut self) {
         self.boolean_op = None;
+        self.start = None;
     }
 
     fn not_operator(&mut self, not_id: u16) {
         self.boolean_op = Some(not_id);
+        self.start = None;
     }
 
     fn eval_based_on_prev(&mut self, bool_id: u16, structural: usize) -> usize {
@@ -182,13 +247,21 @@ impl BoolSequence {
 }
 
 #[inline(always)]
-fn increment(stats: &mut Stats) {
-    stats.structural += stats.nesting + 1;
+fn increment(node: &Node, stats: &mut Stats) {
+    let nesting = stats.nesting;
+    stats.add(node, nesting + 1, nesting, || {
+        let kind = humanize_kind(node.kind());
+        if nesting > 0 {
+            format!("nested {kind}")
+        } else {
+            kind
+        }
+    });
 }
 
 #[inline(always)]
-fn increment_by_one(stats: &mut Stats) {
-    stats.structural += 1;
+fn increment_by_one(node: &Node, stats: &mut Stats) {
+    stats.add(node, 1, 0, || humanize_kind(node.kind()));
 }
 
 fn get_nesting_from_map(
@@ -223,9 +296,15 @@ fn increment_function_depth<T: std::cmp::PartialEq + std::convert::From<u16>>(
 }
 
 #[inline(always)]
-fn increase_nesting(stats: &mut Stats, nesting: &mut usize, depth: usize, lambda: usize) {
+fn increase_nesting(
+    node: &Node,
+    stats: &mut Stats,
+    nesting: &mut usize,
+    depth: usize,
+    lambda: usize,
+) {
     stats.nesting = *nesting + depth + lambda;
-    increment(stats);
+    increment(node, stats);
     *nesting += 1;
     stats.boolean_seq.reset();
 }
@@ -243,23 +322,23 @@ impl Cognitive for PythonCode {
 
         match node.kind_id().into() {
             IfStatement | ForStatement | WhileStatement | ConditionalExpression => {
-                increase_nesting(stats, &mut nesting, depth, lambda);
+                increase_nesting(node, stats, &mut nesting, depth, lambda);
             }
             ElifClause => {
                 // No nesting increment for them because their cost has already
                 // been paid by the if construct
-                increment_by_one(stats);
+                increment_by_one(node, stats);
                 // Reset the boolean sequence
                 stats.boolean_seq.reset();
             }
             ElseClause | FinallyClause => {
                 // No nesting increment for them because their cost has already
                 // been paid by the if construct
-                increment_by_one(stats);
+                increment_by_one(node, stats);
             }
             ExceptClause => {
                 nesting += 1;
-                increment(stats);
+                increment(node, stats);
             }
             ExpressionList | ExpressionStatement | Tuple => {
                 stats.boolean_seq.reset();
@@ -273,7 +352,7 @@ impl Cognitive for PythonCode {
                     |node| node.kind_id() == Lambda,
                 ) == 0
                 {
-                    stats.structural += node.count_specific_ancestors::<PythonParser>(
+                    let lambdas = node.count_specific_ancestors::<PythonParser>(
                         |node| node.kind_id() == Lambda,
                         |node| {
                             matches!(
@@ -282,6 +361,11 @@ impl Cognitive for PythonCode {
                             )
                         },
                     );
+                    if lambdas > 0 {
+                        stats.add(node, lambdas, lambdas, || {
+                            "boolean operator in lambda".to_string()
+                        });
+                    }
                 }
                 compute_booleans::<language_python::Python>(node, stats, And, Or);
             }
@@ -318,19 +402,19 @@ impl Cognitive for RustCode {
             IfExpression => {
                 // Check if a node is not an else-if
                 if !Self::is_else_if(node) {
-                    increase_nesting(stats,&mut nesting, depth, lambda);
+                    increase_nesting(node, stats,&mut nesting, depth, lambda);
                 }
             }
             ForExpression | WhileExpression | MatchExpression => {
-                increase_nesting(stats,&mut nesting, depth, lambda);
+                increase_nesting(node, stats,&mut nesting, depth, lambda);
             }
             Else /*else-if also */ => {
-                increment_by_one(stats);
+                increment_by_one(node, stats);
             }
             BreakExpression | ContinueExpression => {
                 if let Some(label_child) = node.child(1) {
                     if let Label = label_child.kind_id().into() {
-                        increment_by_one(stats);
+                        increment_by_one(node, stats);
                     }
                 }
             }
@@ -368,14 +452,14 @@ impl Cognitive for CppCode {
         match node.kind_id().into() {
             IfStatement => {
                 if !Self::is_else_if(node) {
-                    increase_nesting(stats,&mut nesting, depth, lambda);
+                    increase_nesting(node, stats,&mut nesting, depth, lambda);
                 }
             }
             ForStatement | WhileStatement | DoStatement | SwitchStatement | CatchClause => {
-                increase_nesting(stats,&mut nesting, depth, lambda);
+                increase_nesting(node, stats,&mut nesting, depth, lambda);
             }
             GotoStatement | Else /* else-if also */ => {
-                increment_by_one(stats);
+                increment_by_one(node, stats);
             }
             UnaryExpression2 => {
                 stats.boolean_seq.not_operator(node.kind_id());
@@ -401,14 +485,14 @@ macro_rules! js_cognitive {
             match node.kind_id().into() {
                 IfStatement => {
                     if !Self::is_else_if(&node) {
-                        increase_nesting(stats,&mut nesting, depth, lambda);
+                        increase_nesting(node, stats,&mut nesting, depth, lambda);
                     }
                 }
                 ForStatement | ForInStatement | WhileStatement | DoStatement | SwitchStatement | CatchClause | TernaryExpression => {
-                    increase_nesting(stats,&mut nesting, depth, lambda);
+                    increase_nesting(node, stats,&mut nesting, depth, lambda);
                 }
                 Else /* else-if also */ => {
-                    increment_by_one(stats);
+                    increment_by_one(node, stats);
                 }
                 ExpressionStatement => {
                     // Reset the boolean sequence
@@ -466,14 +550,14 @@ impl Cognitive for JavaCode {
         match node.kind_id().into() {
             IfStatement => {
                 if !Self::is_else_if(node) {
-                    increase_nesting(stats,&mut nesting, depth, lambda);
+                    increase_nesting(node, stats,&mut nesting, depth, lambda);
                 }
             }
             ForStatement | WhileStatement | DoStatement | SwitchBlock | CatchClause => {
-                increase_nesting(stats,&mut nesting, depth, lambda);
+                increase_nesting(node, stats,&mut nesting, depth, lambda);
             }
             Else /* else-if also */ => {
-                increment_by_one(stats);
+                increment_by_one(node, stats);
             }
             UnaryExpression => {
                 stats.boolean_seq.not_operator(node.kind_id());
@@ -3,6 +3,7 @@ use serde::ser::{SerializeStruct, Serializer};
 use std::fmt;
 
 use crate::checker::Checker;
+use crate::increments::{Increment, humanize_kind};
 use crate::macros::implement_metric_trait;
 use crate::*;
 
@@ -14,6 +15,7 @@ pub struct Stats {
     n: usize,
     cyclomatic_max: f64,
     cyclomatic_min: f64,
+    increments: Option<Vec<Increment>>,
 }
 
 impl Default for Stats {
@@ -24,6 +26,7 @@ impl Default for Stats {
             n: 1,
             cyclomatic_max: 0.,
             cyclomatic_min: f64::MAX,
+            increments: None,
         }
     }
 }
@@ -100,6 +103,27 @@ impl Stats {
         self.cyclomatic_min = self.cyclomatic_min.min(self.cyclomatic);
         self.compute_sum();
     }
+
+    // Starts recording every increment of the metric
+    #[inline(always)]
+    pub(crate) fn record_increments(&mut self) {
+        self.increments = Some(Vec::new());
+    }
+
+    // Returns the recorded increments, if any
+    #[inline(always)]
+    pub(crate) fn take_increments(&mut self) -> Option<Vec<Increment>> {
+        self.increments.take()
+    }
+
+    // Increments the metric by one because of a decision point
+    #[inline(always)]
+    fn increment(&mut self, node: &Node) {
+        self.cyclomatic += 1.;
+        if let Some(increments) = self.increments.as_mut() {
+            increments.push(Increment::new(node, 1, 0, humanize_kind(node.kind())));
+        }
+    }
 }
 
 pub trait Cyclomatic
@@ -115,14 +139,14 @@ impl Cyclomatic for PythonCode {
 
         match node.kind_id().into() {
             If | Elif | For | While | Except | With | Assert | And | Or => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             Else => {
                 if node.has_ancestors(
                     |node| matches!(node.kind_id().into(), ForStatement | WhileStatement),
                     |node| node.kind_id() == ElseClause,
                 ) {
-                    stats.cyclomatic += 1.;
+                    stats.increment(node);
                 }
             }
             _ => {}
@@ -136,7 +160,7 @@ impl Cyclomatic for MozjsCode {
 
         match node.kind_id().into() {
             If | For | While | Case | Catch | TernaryExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
@@ -149,7 +173,7 @@ impl Cyclomatic for JavascriptCode {
 
         match node.kind_id().into() {
             If | For | While | Case | Catch | TernaryExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
@@ -162,7 +186,7 @@ impl Cyclomatic for TypescriptCode {
 
         match node.kind_id().into() {
             If | For | While | Case | Catch | TernaryExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
@@ -175,7 +199,7 @@ impl Cyclomatic for TsxCode {
 
         match node.kind_id().into() {
             If | For | While | Case | Catch | TernaryExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
@@ -188,7 +212,7 @@ impl Cyclomatic for RustCode {
 
         match node.kind_id().into() {
             If | For | While | Loop | MatchArm | MatchArm2 | TryExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
@@ -201,7 +225,7 @@ impl Cyclomatic for CppCode {
 
         match node.kind_id().into() {
             If | For | While | Case | Catch | ConditionalExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
@@ -214,7 +238,7 @@ impl Cyclomatic for JavaCode {
 
         match node.kind_id().into() {
             If | For | While | Case | Catch | TernaryExpression | AMPAMP | PIPEPIPE => {
-                stats.cyclomatic += 1.;
+                stats.increment(node);
             }
             _ => {}
         }
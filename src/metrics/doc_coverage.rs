@@ -0,0 +1,693 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::langs::*;
+use crate::macros::implement_metric_trait;
+use crate::node::Node;
+use crate::*;
+
+/// The `DocCoverage` metric.
+///
+/// This metric counts the public functions, classes, methods and fields
+/// of a space and how many of them are documented.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    documented: usize,
+    total: usize,
+    documented_sum: usize,
+    total_sum: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("doc_coverage", 3)?;
+        st.serialize_field("documented", &self.documented_sum())?;
+        st.serialize_field("total", &self.total_sum())?;
+        st.serialize_field("coverage", &self.coverage())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "documented: {}, total: {}, coverage: {}",
+            self.documented_sum(),
+            self.total_sum(),
+            self.coverage()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `DocCoverage` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.documented_sum += other.documented_sum;
+        self.total_sum += other.total_sum;
+    }
+
+    /// Returns the number of documented public items in a space
+    #[inline(always)]
+    pub fn documented(&self) -> f64 {
+        self.documented as f64
+    }
+
+    /// Returns the number of public items in a space
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.total as f64
+    }
+
+    /// Returns the number of documented public items
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn documented_sum(&self) -> f64 {
+        self.documented_sum as f64
+    }
+
+    /// Returns the number of public items in a space and its subspaces
+    #[inline(always)]
+    pub fn total_sum(&self) -> f64 {
+        self.total_sum as f64
+    }
+
+    /// Returns the share of documented public items
+    /// in a space and its subspaces
+    ///
+    /// If there are no public items in a space, its value is `NAN`.
+    #[inline(always)]
+    pub fn coverage(&self) -> f64 {
+        self.documented_sum() / self.total_sum()
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.documented_sum += self.documented;
+        self.total_sum += self.total;
+    }
+
+    #[inline(always)]
+    fn add_item(&mut self, documented: bool) {
+        self.total += 1;
+        if documented {
+            self.documented += 1;
+        }
+    }
+}
+
+pub trait DocCoverage
+where
+    Self: Checker,
+{
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats);
+}
+
+// Checks whether the comment preceding a node is a documentation comment.
+// The siblings for which `skip` returns true, such as attributes, can be
+// placed between the comment and the node, but no blank lines.
+fn has_leading_doc<T: Checker>(
+    node: &Node,
+    code: &[u8],
+    skip: fn(&Node) -> bool,
+    is_doc: fn(&str) -> bool,
+) -> bool {
+    let mut row = node.start_row();
+    let mut prev = node.previous_sibling();
+    while let Some(sibling) = prev {
+        if sibling.end_row() + 1 < row {
+            return false;
+        }
+        if T::is_comment(&sibling) {
+            return sibling.utf8_text(code).is_some_and(is_doc);
+        }
+        if !skip(&sibling) {
+            return false;
+        }
+        row = sibling.start_row();
+        prev = sibling.previous_sibling();
+    }
+    false
+}
+
+#[inline(always)]
+fn is_block_doc(text: &str) -> bool {
+    // `/**/` is an empty block comment
+    text.starts_with("/**") && !text.starts_with("/**/")
+}
+
+#[inline(always)]
+fn is_rust_outer_doc(text: &str) -> bool {
+    (text.starts_with("///") && !text.starts_with("////"))
+        || (is_block_doc(text) && !text.starts_with("/***"))
+}
+
+#[inline(always)]
+fn is_rust_inner_doc(text: &str) -> bool {
+    text.starts_with("//!") || text.starts_with("/*!")
+}
+
+#[inline(always)]
+fn is_doxygen(text: &str) -> bool {
+    text.starts_with("///")
+        || text.starts_with("//!")
+        || text.starts_with("/*!")
+        || is_block_doc(text)
+}
+
+#[inline(always)]
+fn skip_nothing(_: &Node) -> bool {
+    false
+}
+
+impl DocCoverage for RustCode {
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        use Rust::*;
+
+        match node.kind_id().into() {
+            FunctionItem
+            | FunctionSignatureItem
+            | StructItem
+            | EnumItem
+            | UnionItem
+            | TraitItem
+            | TypeItem
+            | AssociatedType
+            | ConstItem
+            | StaticItem
+            | ModItem
+            | FieldDeclaration
+            | EnumVariant => {
+                // Only the items marked as `pub` belong to the public API,
+                // when the type containing them is public too
+                let enclosing_type =
+                    node.parent()
+                        .and_then(|body| body.parent())
+                        .filter(|parent| {
+                            matches!(
+                                parent.kind_id().into(),
+                                TraitItem | EnumItem | StructItem | UnionItem
+                            )
+                        });
+                let is_public = match enclosing_type {
+                    // The items of a trait and the variants of an enum
+                    // are as visible as their parent
+                    Some(parent) if matches!(parent.kind_id().into(), TraitItem | EnumItem) => {
+                        is_rust_pub(&parent, code)
+                    }
+                    Some(parent) => is_rust_pub(node, code) && is_rust_pub(&parent, code),
+                    None => is_rust_pub(node, code),
+                };
+                if !is_public {
+                    return;
+                }
+                let documented = has_leading_doc::<Self>(
+                    node,
+                    code,
+                    |node| node.kind_id() == AttributeItem,
+                    is_rust_outer_doc,
+                ) || (node.kind_id() == ModItem && has_inner_doc(node, code));
+                stats.add_item(documented);
+            }
+            _ => {}
+        }
+    }
+}
+
+// Checks whether a `Rust` item is marked as `pub`
+#[inline(always)]
+fn is_rust_pub(node: &Node, code: &[u8]) -> bool {
+    node.children().any(|child| {
+        child.kind_id() == Rust::VisibilityModifier
+            && child.utf8_text(code).is_some_and(|text| text == "pub")
+    })
+}
+
+// Checks whether a `Rust` module starts with an inner documentation comment
+fn has_inner_doc(node: &Node, code: &[u8]) -> bool {
+    node.children()
+        .find(|child| child.kind_id() == Rust::DeclarationList)
+        .and_then(|body| {
+            body.children()
+                .find(|child| child.kind_id() != Rust::LBRACE)
+        })
+        .is_some_and(|first| {
+            RustCode::is_comment(&first) && first.utf8_text(code).is_some_and(is_rust_inner_doc)
+        })
+}
+
+impl DocCoverage for JavaCode {
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        use Java::*;
+
+        match node.kind_id().into() {
+            ClassDeclaration
+            | InterfaceDeclaration
+            | EnumDeclaration
+            | RecordDeclaration
+            | MethodDeclaration
+            | ConstructorDeclaration
+            | FieldDeclaration
+            | ConstantDeclaration
+                if is_java_public(node) =>
+            {
+                stats.add_item(has_leading_doc::<Self>(
+                    node,
+                    code,
+                    skip_nothing,
+                    is_block_doc,
+                ));
+            }
+            _ => {}
+        }
+    }
+}
+
+// Checks whether a `Java` declaration is public, as the members of an
+// interface implicitly are, and so are the types containing it
+fn is_java_public(node: &Node) -> bool {
+    use Java::*;
+
+    let is_public = node
+        .parent()
+        .is_some_and(|parent| parent.kind_id() == InterfaceBody)
+        || node.child(0).is_some_and(|modifiers| {
+            modifiers.kind_id() == Modifiers && modifiers.first_child(|id| id == Public).is_some()
+        });
+    let enclosing_type = node
+        .parent()
+        .and_then(|body| body.parent())
+        .filter(|parent| {
+            matches!(
+                parent.kind_id().into(),
+                ClassDeclaration | InterfaceDeclaration | EnumDeclaration | RecordDeclaration
+            )
+        });
+    is_public && enclosing_type.is_none_or(|parent| is_java_public(&parent))
+}
+
+impl DocCoverage for PythonCode {
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        use Python::*;
+
+        if !matches!(node.kind_id().into(), FunctionDefinition | ClassDefinition) {
+            return;
+        }
+
+        // Names starting with an underscore are private by convention
+        let is_public = node
+            .child_by_field_name("name")
+            .and_then(|name| name.utf8_text(code))
+            .is_some_and(|name| !name.starts_with('_'));
+
+        // Only module-level definitions and class members
+        // belong to the public API
+        let mut parent = node.parent();
+        if parent.is_some_and(|p| p.kind_id() == DecoratedDefinition) {
+            parent = parent.and_then(|p| p.parent());
+        }
+        let is_api = parent.is_some_and(|p| match p.kind_id().into() {
+            Module => true,
+            Block | Block2 => p.parent().is_some_and(|p| p.kind_id() == ClassDefinition),
+            _ => false,
+        });
+
+        if is_public && is_api {
+            // A docstring is a string in the first statement of the body,
+            // which can be preceded by comments
+            let has_docstring = node
+                .child_by_field_name("body")
+                .and_then(|body| body.children().find(|child| !Self::is_comment(child)))
+                .is_some_and(|first| {
+                    first.kind_id() == ExpressionStatement
+                        && first.child(0).is_some_and(|s| s.kind_id() == String)
+                });
+            stats.add_item(has_docstring);
+        }
+    }
+}
+
+macro_rules! js_doc_coverage {
+    ($lang:ident, [$($decl:ident)|+], [$($member:ident)|+] $(, $accessibility:ident)?) => {
+        fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+            use $lang::*;
+
+            match node.kind_id().into() {
+                // Only exported declarations belong to the public API,
+                // their documentation precedes the `export` keyword
+                $($decl)|+ => {
+                    if let Some(export) = node.parent().filter(|p| p.kind_id() == ExportStatement) {
+                        stats.add_item(has_leading_doc::<Self>(&export, code, skip_nothing, is_block_doc));
+                    }
+                }
+                // Public members of exported classes
+                $($member)|+ => {
+                    let is_exported = node
+                        .parent()
+                        .and_then(|body| body.parent())
+                        .and_then(|class| class.parent())
+                        .is_some_and(|p| p.kind_id() == ExportStatement);
+                    let is_public = node.children().all(|child| {
+                        if child.kind_id() == PrivatePropertyIdentifier {
+                            return false;
+                        }
+                        $(
+                            if child.kind_id() == $accessibility {
+                                return child.utf8_text(code) == Some("public");
+                            }
+                        )?
+                        true
+                    });
+                    if is_exported && is_public {
+                        stats.add_item(has_leading_doc::<Self>(node, code, skip_nothing, is_block_doc));
+                    }
+                }
+                _ => {}
+            }
+        }
+    };
+}
+
+impl DocCoverage for MozjsCode {
+    js_doc_coverage!(
+        Mozjs,
+        [FunctionDeclaration | GeneratorFunctionDeclaration | ClassDeclaration],
+        [MethodDefinition | FieldDefinition]
+    );
+}
+
+impl DocCoverage for JavascriptCode {
+    js_doc_coverage!(
+        Javascript,
+        [FunctionDeclaration | GeneratorFunctionDeclaration | ClassDeclaration],
+        [MethodDefinition | FieldDefinition]
+    );
+}
+
+impl DocCoverage for TypescriptCode {
+    js_doc_coverage!(
+        Typescript,
+        [FunctionDeclaration
+            | GeneratorFunctionDeclaration
+            | ClassDeclaration
+            | AbstractClassDeclaration
+            | InterfaceDeclaration],
+        [MethodDefinition | PublicFieldDefinition],
+        AccessibilityModifier
+    );
+}
+
+impl DocCoverage for TsxCode {
+    js_doc_coverage!(
+        Tsx,
+        [FunctionDeclaration
+            | GeneratorFunctionDeclaration
+            | ClassDeclaration
+            | AbstractClassDeclaration
+            | InterfaceDeclaration],
+        [MethodDefinition | PublicFieldDefinition],
+        AccessibilityModifier
+    );
+}
+
+impl DocCoverage for CppCode {
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        use Cpp::*;
+
+        let is_public = match node.kind_id().into() {
+            FunctionDefinition | FunctionDefinition2 | FunctionDefinition3
+            | FunctionDefinition4 => match node.parent().map(|p| p.kind_id().into()) {
+                // Static functions are not visible outside their translation unit
+                Some(TranslationUnit | DeclarationList | TemplateDeclaration) => {
+                    !node.children().any(|child| {
+                        child.kind_id() == StorageClassSpecifier
+                            && child.utf8_text(code) == Some("static")
+                    })
+                }
+                Some(FieldDeclarationList) => is_public_member(node, code),
+                _ => false,
+            },
+            ClassSpecifier | StructSpecifier => {
+                // Only definitions are considered, not forward declarations
+                node.child_by_field_name("body").is_some()
+                    && node.parent().is_some_and(|p| match p.kind_id().into() {
+                        FieldDeclarationList => is_public_member(node, code),
+                        _ => true,
+                    })
+            }
+            FieldDeclaration => {
+                node.parent()
+                    .is_some_and(|p| p.kind_id() == FieldDeclarationList)
+                    && is_public_member(node, code)
+            }
+            _ => false,
+        };
+
+        if is_public {
+            // The documentation of a template precedes the `template` keyword
+            let target = node
+                .parent()
+                .filter(|p| p.kind_id() == TemplateDeclaration)
+                .unwrap_or(*node);
+            stats.add_item(has_leading_doc::<Self>(
+                &target,
+                code,
+                skip_nothing,
+                is_doxygen,
+            ));
+        }
+    }
+}
+
+// Checks whether the member of a `C++` class is public, looking for the
+// nearest access specifier which precedes it. The members of a struct
+// are public by default, while the ones of a class are private.
+fn is_public_member(node: &Node, code: &[u8]) -> bool {
+    let mut prev = node.previous_sibling();
+    while let Some(sibling) = prev {
+        if sibling.kind_id() == Cpp::AccessSpecifier {
+            return sibling.utf8_text(code) == Some("public");
+        }
+        prev = sibling.previous_sibling();
+    }
+    node.parent()
+        .and_then(|body| body.parent())
+        .is_some_and(|class| class.kind_id() == Cpp::StructSpecifier)
+}
+
+implement_metric_trait!(DocCoverage, PreprocCode, CcommentCode, KotlinCode);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn rust_doc_coverage() {
+        check_metrics::<RustParser>(
+            "/// Documented
+             pub fn a() {}
+             // Not a doc comment
+             pub fn b() {}
+             fn private() {}
+             /// Documented
+             #[derive(Debug)]
+             pub struct S {
+                 /// Documented
+                 pub x: i32,
+                 pub y: i32,
+                 z: i32,
+             }
+             pub mod m {
+                 //! Documented
+             }
+             pub(crate) fn c() {}
+             /// Documented
+             pub trait T {
+                 /// Documented
+                 fn t();
+                 fn u() {}
+             }
+             pub enum E {
+                 /// Documented
+                 A,
+                 B,
+             }
+             struct Private {
+                 pub p: i32,
+             }",
+            "foo.rs",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.doc_coverage,
+                    @r###"
+                    {
+                      "documented": 7.0,
+                      "total": 12.0,
+                      "coverage": 0.5833333333333334
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_doc_coverage() {
+        check_metrics::<JavaParser>(
+            "/** Documented */
+             public class A {
+                 /** Documented */
+                 public int x;
+                 /* Not a Javadoc */
+                 public void f() {}
+                 private void g() {}
+                 /**
+                  * Documented
+                  */
+                 @Override
+                 public String toString() { return \"\"; }
+             }
+             interface I {
+                 void h();
+             }
+             /** Documented */
+             public interface J {
+                 void k();
+             }",
+            "foo.java",
+            |metric| {
+                // The members of the package-private interface are not public
+                insta::assert_json_snapshot!(
+                    metric.doc_coverage,
+                    @r###"
+                    {
+                      "documented": 4.0,
+                      "total": 6.0,
+                      "coverage": 0.6666666666666666
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn python_doc_coverage() {
+        check_metrics::<PythonParser>(
+            "def a():
+                 '''Documented'''
+                 def inner():
+                     pass
+             def _private():
+                 pass
+             class C:
+                 \"\"\"Documented\"\"\"
+                 @property
+                 def b(self):
+                     return 1
+                 def c(self):
+                     '''Documented'''
+             def d():
+                 # A comment before the docstring
+                 '''Documented'''",
+            "foo.py",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.doc_coverage,
+                    @r###"
+                    {
+                      "documented": 4.0,
+                      "total": 5.0,
+                      "coverage": 0.8
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_doc_coverage() {
+        check_metrics::<JavascriptParser>(
+            "/** Documented */
+             export function a() {}
+             export function b() {}
+             function notExported() {}
+             /** Documented */
+             export class C {
+                 /** Documented */
+                 m() {}
+                 #hidden() {}
+                 n() {}
+             }",
+            "foo.js",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.doc_coverage,
+                    @r###"
+                    {
+                      "documented": 3.0,
+                      "total": 5.0,
+                      "coverage": 0.6
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn typescript_doc_coverage() {
+        check_metrics::<TypescriptParser>(
+            "export class C {
+                 /** Documented */
+                 public x: number = 1;
+                 private y: number = 2;
+                 protected m() {}
+             }
+             /** Documented */
+             export interface I {}",
+            "foo.ts",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.doc_coverage,
+                    @r###"
+                    {
+                      "documented": 2.0,
+                      "total": 3.0,
+                      "coverage": 0.6666666666666666
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn doc_coverage_per_space() {
+        check_func_space::<JavaParser, _>(
+            "public class A {
+                 /** Documented */
+                 public void f() {}
+             }",
+            "foo.java",
+            |space| {
+                let class = &space.spaces[0];
+                assert_eq!(class.metrics.doc_coverage.total(), 1.);
+                assert_eq!(class.metrics.doc_coverage.documented(), 0.);
+                assert_eq!(class.spaces[0].metrics.doc_coverage.documented(), 1.);
+                assert_eq!(space.metrics.doc_coverage.documented_sum(), 1.);
+                assert_eq!(space.metrics.doc_coverage.total_sum(), 2.);
+            },
+        );
+    }
+
+    #[test]
+    fn no_public_items() {
+        check_metrics::<PythonParser>("_a = 1", "foo.py", |metric| {
+            assert!(metric.doc_coverage.coverage().is_nan());
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+/// The `Duplication` metric.
+///
+/// This metric counts the lines of a space which belong to code clones.
+///
+/// Clones are searched across a set of files, so this metric is only
+/// available when the spaces of a file are annotated with the result
+/// of a clone detection.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    duplicated_lines: usize,
+    lines: usize,
+    is_enabled: bool,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("duplication", 2)?;
+        st.serialize_field("duplicated_lines", &self.duplicated_lines())?;
+        st.serialize_field("ratio", &self.ratio())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "duplicated_lines: {}, ratio: {}",
+            self.duplicated_lines(),
+            self.ratio()
+        )
+    }
+}
+
+impl Stats {
+    /// Returns the number of duplicated lines in a space
+    #[inline(always)]
+    pub fn duplicated_lines(&self) -> f64 {
+        self.duplicated_lines as f64
+    }
+
+    /// Returns the share of duplicated lines in a space
+    ///
+    /// If a space has no lines, its value is `NAN`.
+    #[inline(always)]
+    pub fn ratio(&self) -> f64 {
+        self.duplicated_lines() / self.lines as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn set(&mut self, duplicated_lines: usize, lines: usize) {
+        self.duplicated_lines = duplicated_lines;
+        self.lines = lines;
+        self.is_enabled = true;
+    }
+
+    // Checks if the `Duplication` metric is disabled
+    #[inline(always)]
+    pub(crate) fn is_disabled(&self) -> bool {
+        !self.is_enabled
+    }
+}
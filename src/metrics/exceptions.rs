@@ -0,0 +1,432 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The `Exceptions` metric.
+///
+/// This metric counts the exception-handling constructs of a space:
+/// the `try` blocks, the `catch` handlers, the `throw` and `raise` sites,
+/// the handlers catching every exception, as `catch (Exception e)`,
+/// bare `except:` and `catch (...)`, and the handlers with an empty body.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    tries: usize,
+    catches: usize,
+    throws: usize,
+    catch_alls: usize,
+    empty_catches: usize,
+    tries_sum: usize,
+    catches_sum: usize,
+    throws_sum: usize,
+    catch_alls_sum: usize,
+    empty_catches_sum: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("exceptions", 5)?;
+        st.serialize_field("tries", &self.tries_sum())?;
+        st.serialize_field("catches", &self.catches_sum())?;
+        st.serialize_field("throws", &self.throws_sum())?;
+        st.serialize_field("catch_alls", &self.catch_alls_sum())?;
+        st.serialize_field("empty_catches", &self.empty_catches_sum())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "tries: {}, catches: {}, throws: {}, catch_alls: {}, empty_catches: {}",
+            self.tries_sum(),
+            self.catches_sum(),
+            self.throws_sum(),
+            self.catch_alls_sum(),
+            self.empty_catches_sum()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Exceptions` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.tries_sum += other.tries_sum;
+        self.catches_sum += other.catches_sum;
+        self.throws_sum += other.throws_sum;
+        self.catch_alls_sum += other.catch_alls_sum;
+        self.empty_catches_sum += other.empty_catches_sum;
+    }
+
+    /// Returns the number of `try` blocks in a space
+    #[inline(always)]
+    pub fn tries(&self) -> f64 {
+        self.tries as f64
+    }
+
+    /// Returns the number of `catch` handlers in a space
+    #[inline(always)]
+    pub fn catches(&self) -> f64 {
+        self.catches as f64
+    }
+
+    /// Returns the number of `throw` and `raise` sites in a space
+    #[inline(always)]
+    pub fn throws(&self) -> f64 {
+        self.throws as f64
+    }
+
+    /// Returns the number of handlers catching every exception in a space
+    #[inline(always)]
+    pub fn catch_alls(&self) -> f64 {
+        self.catch_alls as f64
+    }
+
+    /// Returns the number of handlers with an empty body in a space
+    #[inline(always)]
+    pub fn empty_catches(&self) -> f64 {
+        self.empty_catches as f64
+    }
+
+    /// Returns the number of `try` blocks in a space and its subspaces
+    #[inline(always)]
+    pub fn tries_sum(&self) -> f64 {
+        self.tries_sum as f64
+    }
+
+    /// Returns the number of `catch` handlers in a space and its subspaces
+    #[inline(always)]
+    pub fn catches_sum(&self) -> f64 {
+        self.catches_sum as f64
+    }
+
+    /// Returns the number of `throw` and `raise` sites
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn throws_sum(&self) -> f64 {
+        self.throws_sum as f64
+    }
+
+    /// Returns the number of handlers catching every exception
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn catch_alls_sum(&self) -> f64 {
+        self.catch_alls_sum as f64
+    }
+
+    /// Returns the number of handlers with an empty body
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn empty_catches_sum(&self) -> f64 {
+        self.empty_catches_sum as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.tries_sum += self.tries;
+        self.catches_sum += self.catches;
+        self.throws_sum += self.throws;
+        self.catch_alls_sum += self.catch_alls;
+        self.empty_catches_sum += self.empty_catches;
+    }
+}
+
+/// An exception-handling construct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exception {
+    /// A `try` block
+    Try,
+    /// A `catch` or `except` handler
+    Catch {
+        /// Whether the handler catches every exception
+        catch_all: bool,
+        /// Whether the body of the handler is empty
+        empty: bool,
+    },
+    /// A `throw` or `raise` site
+    Throw,
+}
+
+pub trait Exceptions
+where
+    Self: Checker,
+{
+    /// Returns the exception-handling construct represented by a node, if any
+    fn exception(_node: &Node, _code: &[u8]) -> Option<Exception> {
+        None
+    }
+
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        match Self::exception(node, code) {
+            Some(Exception::Try) => stats.tries += 1,
+            Some(Exception::Catch { catch_all, empty }) => {
+                stats.catches += 1;
+                stats.catch_alls += catch_all as usize;
+                stats.empty_catches += empty as usize;
+            }
+            Some(Exception::Throw) => stats.throws += 1,
+            None => {}
+        }
+    }
+}
+
+// Checks whether the last segment of a qualified type name,
+// as `java.lang.Exception`, is one of the given names
+fn has_type_name(node: &Node, code: &[u8], names: &[&str]) -> bool {
+    node.utf8_text(code)
+        .and_then(|text| text.rsplit(['.', ':']).next())
+        .is_some_and(|name| names.contains(&name.trim()))
+}
+
+// Checks whether a block contains only comments and no-op statements
+fn is_empty_block<T: Checker>(block: Option<Node>, is_noop: impl Fn(&Node) -> bool) -> bool {
+    block.is_some_and(|block| {
+        block
+            .children()
+            .all(|child| !child.is_named() || T::is_comment(&child) || is_noop(&child))
+    })
+}
+
+impl Exceptions for PythonCode {
+    fn exception(node: &Node, code: &[u8]) -> Option<Exception> {
+        // `Python::None` would shadow `Option::None`, so no glob import here
+        match node.kind_id().into() {
+            Python::TryStatement => Some(Exception::Try),
+            Python::ExceptClause | Python::ExceptGroupClause => {
+                let mut body = None;
+                let mut types = None;
+                for child in node.children() {
+                    match child.kind_id().into() {
+                        Python::Block | Python::Block2 => body = Some(child),
+                        _ if child.is_named() && !Self::is_comment(&child) => {
+                            types.get_or_insert(child);
+                        }
+                        _ => {}
+                    }
+                }
+                // In `except (A, B) as e:` only the tuple is considered
+                let types = types.map(|types| match types.kind_id().into() {
+                    Python::AsPattern | Python::AsPattern2 => types.child(0).unwrap_or(types),
+                    _ => types,
+                });
+                let catch_all = types.is_none_or(|types| match types.kind_id().into() {
+                    Python::Tuple | Python::ParenthesizedExpression => types
+                        .children()
+                        .any(|t| has_type_name(&t, code, &["Exception", "BaseException"])),
+                    _ => has_type_name(&types, code, &["Exception", "BaseException"]),
+                });
+                let empty = is_empty_block::<Self>(body, |child| {
+                    matches!(child.kind_id().into(), Python::PassStatement)
+                });
+                Some(Exception::Catch { catch_all, empty })
+            }
+            Python::RaiseStatement => Some(Exception::Throw),
+            _ => None,
+        }
+    }
+}
+
+impl Exceptions for JavaCode {
+    fn exception(node: &Node, code: &[u8]) -> Option<Exception> {
+        use Java::*;
+
+        match node.kind_id().into() {
+            TryStatement | TryWithResourcesStatement => Some(Exception::Try),
+            CatchClause => {
+                let catch_all = node
+                    .first_child(|id| id == CatchFormalParameter)
+                    .and_then(|param| param.first_child(|id| id == CatchType))
+                    .is_some_and(|types| {
+                        types
+                            .children()
+                            .any(|t| has_type_name(&t, code, &["Exception", "Throwable"]))
+                    });
+                let empty = is_empty_block::<Self>(node.child_by_field_name("body"), |_| false);
+                Some(Exception::Catch { catch_all, empty })
+            }
+            ThrowStatement => Some(Exception::Throw),
+            _ => None,
+        }
+    }
+}
+
+impl Exceptions for KotlinCode {
+    fn exception(node: &Node, code: &[u8]) -> Option<Exception> {
+        use Kotlin::*;
+
+        match node.kind_id().into() {
+            TryExpression => Some(Exception::Try),
+            CatchBlock => {
+                let catch_all = node
+                    .first_child(|id| id == UserType)
+                    .is_some_and(|t| has_type_name(&t, code, &["Exception", "Throwable"]));
+                let empty = is_empty_block::<Self>(node.first_child(|id| id == Block), |_| false);
+                Some(Exception::Catch { catch_all, empty })
+            }
+            ThrowExpression => Some(Exception::Throw),
+            _ => None,
+        }
+    }
+}
+
+impl Exceptions for CppCode {
+    fn exception(node: &Node, code: &[u8]) -> Option<Exception> {
+        // `Cpp::String` would shadow the standard `String`, so no glob import here
+        match node.kind_id().into() {
+            Cpp::TryStatement | Cpp::TryStatement2 => Some(Exception::Try),
+            Cpp::CatchClause => {
+                let catch_all = node
+                    .child_by_field_name("parameters")
+                    .and_then(|params| params.utf8_text(code))
+                    .is_some_and(|params| params.split_whitespace().collect::<String>() == "(...)");
+                let empty = is_empty_block::<Self>(node.child_by_field_name("body"), |_| false);
+                Some(Exception::Catch { catch_all, empty })
+            }
+            Cpp::ThrowStatement => Some(Exception::Throw),
+            _ => None,
+        }
+    }
+}
+
+// In the JavaScript family a handler always catches every exception,
+// so no handler is reported as catch-all
+macro_rules! js_exceptions {
+    ($code:ident, $lang:ident) => {
+        impl Exceptions for $code {
+            fn exception(node: &Node, _code: &[u8]) -> Option<Exception> {
+                use $lang::*;
+
+                match node.kind_id().into() {
+                    TryStatement => Some(Exception::Try),
+                    CatchClause => {
+                        let empty =
+                            is_empty_block::<Self>(node.child_by_field_name("body"), |_| false);
+                        Some(Exception::Catch {
+                            catch_all: false,
+                            empty,
+                        })
+                    }
+                    ThrowStatement => Some(Exception::Throw),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+js_exceptions!(MozjsCode, Mozjs);
+js_exceptions!(JavascriptCode, Javascript);
+js_exceptions!(TypescriptCode, Typescript);
+js_exceptions!(TsxCode, Tsx);
+
+implement_metric_trait!([Exceptions], RustCode, PreprocCode, CcommentCode);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn python_exceptions() {
+        check_metrics::<PythonParser>(
+            "def f():
+                 try:
+                     g()
+                 except (ValueError, Exception) as e:
+                     raise
+                 except KeyError:
+                     pass
+                 except:
+                     log()",
+            "foo.py",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.exceptions,
+                    @r###"
+                    {
+                      "tries": 1.0,
+                      "catches": 3.0,
+                      "throws": 1.0,
+                      "catch_alls": 2.0,
+                      "empty_catches": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_exceptions() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 void f() throws Exception {
+                     try (Reader r = open()) {
+                         g();
+                     } catch (java.lang.Throwable t) {
+                         // ignored
+                     } catch (IOException | RuntimeException e) {
+                         log(e);
+                     }
+                     throw new Exception(\"x\");
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.exceptions,
+                    @r###"
+                    {
+                      "tries": 1.0,
+                      "catches": 2.0,
+                      "throws": 1.0,
+                      "catch_alls": 1.0,
+                      "empty_catches": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_exceptions_per_space() {
+        check_func_space::<JavascriptParser, _>(
+            "function f() {
+                 try {
+                     g();
+                 } catch (e) {
+                 }
+             }
+             function h() {
+                 throw new Error();
+             }",
+            "foo.js",
+            |func_space| {
+                let f = &func_space.spaces[0].metrics.exceptions;
+                let h = &func_space.spaces[1].metrics.exceptions;
+                assert_eq!((f.tries(), f.catches(), f.throws()), (1., 1., 0.));
+                assert_eq!((f.catch_alls(), f.empty_catches()), (0., 1.));
+                assert_eq!((h.tries(), h.catches(), h.throws()), (0., 0., 1.));
+                insta::assert_json_snapshot!(
+                    func_space.metrics.exceptions,
+                    @r###"
+                    {
+                      "tries": 1.0,
+                      "catches": 1.0,
+                      "throws": 1.0,
+                      "catch_alls": 0.0,
+                      "empty_catches": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -1,7 +1,8 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use serde::Serialize;
 use serde::ser::{SerializeStruct, Serializer};
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 use crate::checker::Checker;
@@ -20,6 +21,7 @@ pub struct Stats {
 }
 
 /// Specifies the type of nodes accepted by the `Halstead` metric.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HalsteadType {
     /// The node is an `Halstead` operator
     Operator,
@@ -29,10 +31,134 @@ pub enum HalsteadType {
     Unknown,
 }
 
+/// The node kinds of a language classified as `Halstead` operators
+/// or operands, or ignored by the metric.
+///
+/// The kinds are the names of the nodes in the grammar of the language,
+/// as printed by the `dump` command.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct HalsteadKinds {
+    /// The node kinds counted as operators
+    pub operators: Vec<String>,
+    /// The node kinds counted as operands
+    pub operands: Vec<String>,
+    /// The node kinds ignored by the metric
+    pub ignored: Vec<String>,
+}
+
+/// The classification of nodes as `Halstead` operators and operands.
+///
+/// For each language, the node kinds in the table override the built-in
+/// classification, which is used for all the other kinds.
+/// An empty table, the default one, hence leaves the metric unchanged.
+///
+/// A table can be deserialized from a map of language names,
+/// as returned by [`LANG::get_name`], to [`HalsteadKinds`].
+/// For example, in `TOML`:
+///
+/// ```toml
+/// [rust]
+/// operators = ["let"]
+/// ignored = [";", "primitive_type"]
+///
+/// [python]
+/// operands = ["string"]
+/// ```
+///
+/// A name shared by several languages, as `javascript`,
+/// applies to all of them.
+///
+/// [`LANG::get_name`]: ../../enum.LANG.html#method.get_name
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(try_from = "HashMap<String, HalsteadKinds>")]
+pub struct HalsteadTable {
+    // The overridden kinds of each language, indexed by language
+    langs: Vec<HashMap<u16, HalsteadType>>,
+}
+
+impl HalsteadTable {
+    /// Creates a new table using the built-in classification.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the type of the nodes of a given kind in a language.
+    ///
+    /// Returns an error if the language has no such kind of node.
+    pub fn set(&mut self, lang: LANG, kind: &str, typ: HalsteadType) -> Result<(), String> {
+        let language = lang.get_ts_language();
+        let ids: Vec<u16> = (0..language.node_kind_count() as u16)
+            .filter(|id| language.node_kind_for_id(*id) == Some(kind))
+            .collect();
+        if ids.is_empty() {
+            return Err(format!(
+                "Unknown node kind `{kind}` for language {}",
+                lang.get_name()
+            ));
+        }
+
+        let index = lang as usize;
+        if self.langs.len() <= index {
+            self.langs.resize_with(index + 1, HashMap::default);
+        }
+        for id in ids {
+            self.langs[index].insert(id, typ);
+        }
+        Ok(())
+    }
+
+    /// Sets the types of the node kinds in a language.
+    ///
+    /// Returns an error if the language misses one of the kinds.
+    pub fn set_kinds(&mut self, lang: LANG, kinds: &HalsteadKinds) -> Result<(), String> {
+        let types = [
+            (&kinds.operators, HalsteadType::Operator),
+            (&kinds.operands, HalsteadType::Operand),
+            (&kinds.ignored, HalsteadType::Unknown),
+        ];
+        for (kinds, typ) in types {
+            for kind in kinds {
+                self.set(lang, kind, typ)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub(crate) fn get_op_type<T: Getter + ?Sized>(&self, lang: LANG, node: &Node) -> HalsteadType {
+        self.langs
+            .get(lang as usize)
+            .and_then(|kinds| kinds.get(&node.kind_id()))
+            .copied()
+            .unwrap_or_else(|| T::get_op_type(node))
+    }
+}
+
+impl TryFrom<HashMap<String, HalsteadKinds>> for HalsteadTable {
+    type Error = String;
+
+    fn try_from(config: HashMap<String, HalsteadKinds>) -> Result<Self, Self::Error> {
+        let mut table = Self::new();
+        for (name, kinds) in &config {
+            let mut langs = LANG::into_enum_iter()
+                .filter(|lang| lang.get_name() == name)
+                .peekable();
+            if langs.peek().is_none() {
+                return Err(format!("Unknown language {name}"));
+            }
+            for lang in langs {
+                table.set_kinds(lang, kinds)?;
+            }
+        }
+        Ok(table)
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct HalsteadMaps<'a> {
     pub(crate) operators: HashMap<u16, u64>,
-    pub(crate) operands: HashMap<&'a [u8], u64>,
+    pub(crate) operands: HashMap<Cow<'a, [u8]>, u64>,
 }
 
 impl<'a> HalsteadMaps<'a> {
@@ -48,7 +174,19 @@ impl<'a> HalsteadMaps<'a> {
             *self.operators.entry(*k).or_insert(0) += v;
         }
         for (k, v) in other.operands.iter() {
-            *self.operands.entry(*k).or_insert(0) += v;
+            *self.operands.entry(k.clone()).or_insert(0) += v;
+        }
+    }
+
+    // Copies the operands, so that the maps outlive the code
+    pub(crate) fn to_owned_maps(&self) -> HalsteadMaps<'static> {
+        HalsteadMaps {
+            operators: self.operators.clone(),
+            operands: self
+                .operands
+                .iter()
+                .map(|(k, v)| (Cow::Owned(k.to_vec()), *v))
+                .collect(),
         }
     }
 
@@ -252,7 +390,12 @@ pub trait Halstead
 where
     Self: Checker,
 {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    );
 }
 
 #[inline(always)]
@@ -261,19 +404,20 @@ fn get_id<'a>(node: &Node<'a>, code: &'a [u8]) -> &'a [u8] {
 }
 
 #[inline(always)]
-fn compute_halstead<'a, T: Getter>(
+fn compute_halstead<'a, T: Getter + LanguageInfo>(
     node: &Node<'a>,
     code: &'a [u8],
+    table: &HalsteadTable,
     halstead_maps: &mut HalsteadMaps<'a>,
 ) {
-    match T::get_op_type(node) {
+    match table.get_op_type::<T>(T::get_lang(), node) {
         HalsteadType::Operator => {
             *halstead_maps.operators.entry(node.kind_id()).or_insert(0) += 1;
         }
         HalsteadType::Operand => {
             *halstead_maps
                 .operands
-                .entry(get_id(node, code))
+                .entry(Cow::Borrowed(get_id(node, code)))
                 .or_insert(0) += 1;
         }
         _ => {}
@@ -281,50 +425,90 @@ fn compute_halstead<'a, T: Getter>(
 }
 
 impl Halstead for PythonCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for MozjsCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for JavascriptCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for TypescriptCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for TsxCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for RustCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for CppCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
 impl Halstead for JavaCode {
-    fn compute<'a>(node: &Node<'a>, code: &'a [u8], halstead_maps: &mut HalsteadMaps<'a>) {
-        compute_halstead::<Self>(node, code, halstead_maps);
+    fn compute<'a>(
+        node: &Node<'a>,
+        code: &'a [u8],
+        table: &HalsteadTable,
+        halstead_maps: &mut HalsteadMaps<'a>,
+    ) {
+        compute_halstead::<Self>(node, code, table, halstead_maps);
     }
 }
 
@@ -698,4 +882,60 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn rust_halstead_table() {
+        let source = "fn main() { let a: u32 = 1; let b = a + 2; }";
+        let path = std::path::Path::new("foo.rs");
+        let parser = RustParser::new(source.as_bytes().to_vec(), path, None).unwrap();
+
+        let default = metrics(&parser, path).unwrap().metrics.halstead;
+        let table = HalsteadTable::try_from(HashMap::from([(
+            "rust".to_string(),
+            HalsteadKinds {
+                operators: vec!["identifier".to_string()],
+                operands: vec!["primitive_type".to_string()],
+                ignored: vec![";".to_string()],
+            },
+        )]))
+        .unwrap();
+        let halstead = metrics_with_halstead_table(&parser, path, &table)
+            .unwrap()
+            .metrics
+            .halstead;
+
+        // The identifiers main, a, b, a are operators,
+        // the u32 type is an operand and the semicolons are ignored
+        assert_eq!(halstead.operators(), default.operators() + 4. - 1. - 2.);
+        assert_eq!(halstead.operands(), default.operands() - 4. + 1.);
+
+        // The table of another language does not change the metric
+        let mut table = HalsteadTable::new();
+        table
+            .set(LANG::Python, "identifier", HalsteadType::Unknown)
+            .unwrap();
+        let halstead = metrics_with_halstead_table(&parser, path, &table)
+            .unwrap()
+            .metrics
+            .halstead;
+        assert_eq!(halstead.operators(), default.operators());
+        assert_eq!(halstead.operands(), default.operands());
+    }
+
+    #[test]
+    fn halstead_table_errors() {
+        let mut table = HalsteadTable::new();
+        assert_eq!(
+            table.set(LANG::Java, "no_such_kind", HalsteadType::Operator),
+            Err("Unknown node kind `no_such_kind` for language java".to_string())
+        );
+        assert_eq!(
+            HalsteadTable::try_from(HashMap::from([(
+                "cobol".to_string(),
+                HalsteadKinds::default()
+            )]))
+            .unwrap_err(),
+            "Unknown language cobol"
+        );
+    }
 }
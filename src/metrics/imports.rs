@@ -0,0 +1,549 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The `Imports` metric.
+///
+/// This metric counts the imports of a space: the static ones,
+/// as `use` and `mod` declarations, `import` statements and `#include` directives,
+/// and the dynamic ones, as `require()` and `import()` calls.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    static_imports: usize,
+    dynamic_imports: usize,
+    static_imports_sum: usize,
+    dynamic_imports_sum: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("imports", 3)?;
+        st.serialize_field("static", &self.static_imports_sum())?;
+        st.serialize_field("dynamic", &self.dynamic_imports_sum())?;
+        st.serialize_field("total", &self.total())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "static: {}, dynamic: {}, total: {}",
+            self.static_imports_sum(),
+            self.dynamic_imports_sum(),
+            self.total()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Imports` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.static_imports_sum += other.static_imports_sum;
+        self.dynamic_imports_sum += other.dynamic_imports_sum;
+    }
+
+    /// Returns the number of static imports in a space
+    #[inline(always)]
+    pub fn static_imports(&self) -> f64 {
+        self.static_imports as f64
+    }
+
+    /// Returns the number of dynamic imports in a space
+    #[inline(always)]
+    pub fn dynamic_imports(&self) -> f64 {
+        self.dynamic_imports as f64
+    }
+
+    /// Returns the number of static imports in a space and its subspaces
+    #[inline(always)]
+    pub fn static_imports_sum(&self) -> f64 {
+        self.static_imports_sum as f64
+    }
+
+    /// Returns the number of dynamic imports in a space and its subspaces
+    #[inline(always)]
+    pub fn dynamic_imports_sum(&self) -> f64 {
+        self.dynamic_imports_sum as f64
+    }
+
+    /// Returns the number of imports in a space and its subspaces
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.static_imports_sum() + self.dynamic_imports_sum()
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.static_imports_sum += self.static_imports;
+        self.dynamic_imports_sum += self.dynamic_imports;
+    }
+}
+
+/// The kind of an import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    /// A Rust `use` declaration
+    Use,
+    /// A Rust `mod` declaration of a module defined in another file
+    Mod,
+    /// An `import` statement
+    Import,
+    /// A Python `from ... import` statement
+    From,
+    /// A C/C++ `#include` directive
+    Include,
+    /// A JavaScript `require()` call
+    Require,
+    /// A JavaScript dynamic `import()` call
+    Dynamic,
+}
+
+impl ImportKind {
+    /// Checks whether the import is resolved at runtime
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, ImportKind::Require | ImportKind::Dynamic)
+    }
+}
+
+impl fmt::Display for ImportKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            ImportKind::Use => "use",
+            ImportKind::Mod => "mod",
+            ImportKind::Import => "import",
+            ImportKind::From => "from",
+            ImportKind::Include => "include",
+            ImportKind::Require => "require",
+            ImportKind::Dynamic => "dynamic",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// An import of a module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Import {
+    /// The path of the imported module, as written in the code.
+    ///
+    /// For the imports naming a single item, as `use std::io::Write`
+    /// or `import java.util.List`, the path excludes the item.
+    pub path: String,
+    /// The symbols imported from the module, `*` for a wildcard.
+    ///
+    /// The aliases are not considered, only the original names are.
+    pub symbols: Vec<String>,
+    /// The kind of the import
+    pub kind: ImportKind,
+    /// The line of the import
+    pub line: usize,
+    /// The column of the import
+    pub column: usize,
+}
+
+impl Import {
+    fn new(node: &Node, path: &str, symbols: Vec<String>, kind: ImportKind) -> Self {
+        let (row, column) = node.start_position();
+        Self {
+            path: path.to_string(),
+            symbols,
+            kind,
+            line: row + 1,
+            column: column + 1,
+        }
+    }
+}
+
+pub trait Imports
+where
+    Self: Checker,
+{
+    /// Adds the imports represented by a node to `imports`
+    fn collect(_node: &Node, _code: &[u8], _imports: &mut Vec<Import>) {}
+
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        let mut imports = Vec::new();
+        Self::collect(node, code, &mut imports);
+        for import in imports {
+            if import.kind.is_dynamic() {
+                stats.dynamic_imports += 1;
+            } else {
+                stats.static_imports += 1;
+            }
+        }
+    }
+}
+
+// Splits a qualified name, as `java.util.List`,
+// into its path and its last segment
+fn split_last<'a>(name: &'a str, separator: &str) -> (&'a str, Option<&'a str>) {
+    match name.rsplit_once(separator) {
+        Some((path, last)) => (path, Some(last)),
+        None => (name, None),
+    }
+}
+
+// Removes the delimiters of a string literal, as `"a"` or `<a>`
+fn unquote(text: &str) -> &str {
+    text.trim_matches(|c| matches!(c, '"' | '\'' | '`' | '<' | '>'))
+}
+
+impl Imports for RustCode {
+    fn collect(node: &Node, code: &[u8], imports: &mut Vec<Import>) {
+        // Collects the paths and the symbols of a `use` tree,
+        // as `a::{b, c::d}`
+        fn text<'a>(node: Option<Node<'a>>, code: &'a [u8]) -> Option<&'a str> {
+            node.and_then(|node| node.utf8_text(code))
+        }
+
+        fn tree(node: &Node, prefix: &str, code: &[u8], items: &mut Vec<(String, String)>) {
+            let join = |path: &str| {
+                if prefix.is_empty() {
+                    path.to_string()
+                } else {
+                    format!("{prefix}::{path}")
+                }
+            };
+
+            match node.kind_id().into() {
+                Rust::ScopedIdentifier => {
+                    let path =
+                        text(node.child_by_field_name("path"), code).map_or(prefix.into(), join);
+                    let name = text(node.child_by_field_name("name"), code).unwrap_or_default();
+                    items.push((path, name.to_string()));
+                }
+                Rust::UseAsClause => {
+                    if let Some(path) = node.child_by_field_name("path") {
+                        tree(&path, prefix, code, items);
+                    }
+                }
+                Rust::UseWildcard => {
+                    let path = text(
+                        node.child(0).filter(|path| path.kind_id() != Rust::STAR),
+                        code,
+                    )
+                    .map_or(prefix.into(), join);
+                    items.push((path, "*".to_string()));
+                }
+                Rust::ScopedUseList => {
+                    let path =
+                        text(node.child_by_field_name("path"), code).map_or(prefix.into(), join);
+                    if let Some(list) = node.child_by_field_name("list") {
+                        tree(&list, &path, code, items);
+                    }
+                }
+                Rust::UseList => {
+                    for child in node.children().filter(|child| child.is_named()) {
+                        tree(&child, prefix, code, items);
+                    }
+                }
+                _ => {
+                    if let Some(name) = node.utf8_text(code) {
+                        if prefix.is_empty() {
+                            items.push((name.to_string(), String::new()));
+                        } else {
+                            items.push((prefix.to_string(), name.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+
+        // A module without a body is defined in another file
+        if node.kind_id() == Rust::ModItem && node.child_by_field_name("body").is_none() {
+            if let Some(name) = node
+                .child_by_field_name("name")
+                .and_then(|name| name.utf8_text(code))
+            {
+                imports.push(Import::new(node, name, Vec::new(), ImportKind::Mod));
+            }
+            return;
+        }
+        if node.kind_id() != Rust::UseDeclaration {
+            return;
+        }
+        let Some(argument) = node.child_by_field_name("argument") else {
+            return;
+        };
+
+        let mut items = Vec::new();
+        tree(&argument, "", code, &mut items);
+
+        // The symbols imported from the same path are grouped
+        let start = imports.len();
+        for (path, symbol) in items {
+            let import = match imports[start..].iter_mut().find(|i| i.path == path) {
+                Some(import) => import,
+                None => {
+                    imports.push(Import::new(node, &path, Vec::new(), ImportKind::Use));
+                    imports.last_mut().unwrap()
+                }
+            };
+            if !symbol.is_empty() {
+                import.symbols.push(symbol);
+            }
+        }
+    }
+}
+
+impl Imports for PythonCode {
+    fn collect(node: &Node, code: &[u8], imports: &mut Vec<Import>) {
+        // The name of an imported module or symbol, without its alias
+        fn name<'a>(node: Node<'a>, code: &'a [u8]) -> Option<&'a str> {
+            let node = if node.kind_id() == Python::AliasedImport {
+                node.child_by_field_name("name")?
+            } else {
+                node
+            };
+            node.utf8_text(code)
+        }
+
+        match node.kind_id().into() {
+            Python::ImportStatement => {
+                for child in node.children_by_field_name("name") {
+                    if let Some(path) = name(child, code) {
+                        imports.push(Import::new(node, path, Vec::new(), ImportKind::Import));
+                    }
+                }
+            }
+            Python::ImportFromStatement | Python::FutureImportStatement => {
+                let path = if node.kind_id() == Python::FutureImportStatement {
+                    Some("__future__")
+                } else {
+                    node.child_by_field_name("module_name")
+                        .and_then(|module| module.utf8_text(code))
+                };
+                let Some(path) = path else {
+                    return;
+                };
+                let mut symbols: Vec<_> = node
+                    .children_by_field_name("name")
+                    .into_iter()
+                    .filter_map(|child| name(child, code).map(|name| name.to_string()))
+                    .collect();
+                if node
+                    .first_child(|id| id == Python::WildcardImport)
+                    .is_some()
+                {
+                    symbols.push("*".to_string());
+                }
+                imports.push(Import::new(node, path, symbols, ImportKind::From));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Imports for JavaCode {
+    fn collect(node: &Node, code: &[u8], imports: &mut Vec<Import>) {
+        if node.kind_id() != Java::ImportDeclaration {
+            return;
+        }
+        let Some(name) = node
+            .first_child(|id| id == Java::ScopedIdentifier || id == Java::Identifier)
+            .and_then(|name| name.utf8_text(code))
+        else {
+            return;
+        };
+        let (path, symbol) = if node.first_child(|id| id == Java::Asterisk).is_some() {
+            (name, Some("*"))
+        } else {
+            split_last(name, ".")
+        };
+        let symbols = symbol.into_iter().map(|s| s.to_string()).collect();
+        imports.push(Import::new(node, path, symbols, ImportKind::Import));
+    }
+}
+
+impl Imports for KotlinCode {
+    fn collect(node: &Node, code: &[u8], imports: &mut Vec<Import>) {
+        if node.kind_id() != Kotlin::Import {
+            return;
+        }
+        let Some(name) = node
+            .first_child(|id| id == Kotlin::QualifiedIdentifier)
+            .and_then(|name| name.utf8_text(code))
+        else {
+            return;
+        };
+        let (path, symbol) = if node.first_child(|id| id == Kotlin::STAR).is_some() {
+            (name, Some("*"))
+        } else {
+            split_last(name, ".")
+        };
+        let symbols = symbol.into_iter().map(|s| s.to_string()).collect();
+        imports.push(Import::new(node, path, symbols, ImportKind::Import));
+    }
+}
+
+impl Imports for CppCode {
+    fn collect(node: &Node, code: &[u8], imports: &mut Vec<Import>) {
+        if node.kind_id() != Cpp::PreprocInclude {
+            return;
+        }
+        if let Some(path) = node
+            .child_by_field_name("path")
+            .and_then(|path| path.utf8_text(code))
+        {
+            imports.push(Import::new(
+                node,
+                unquote(path),
+                Vec::new(),
+                ImportKind::Include,
+            ));
+        }
+    }
+}
+
+// The JavaScript family shares the same grammar for imports
+macro_rules! js_imports {
+    ($code:ident, $lang:ident, [$($string:ident)|+]) => {
+        impl Imports for $code {
+            fn collect(node: &Node, code: &[u8], imports: &mut Vec<Import>) {
+                match node.kind_id().into() {
+                    $lang::ImportStatement => {
+                        let Some(path) = node
+                            .child_by_field_name("source")
+                            .and_then(|source| source.utf8_text(code))
+                        else {
+                            return;
+                        };
+                        let mut symbols = Vec::new();
+                        if let Some(clause) = node.first_child(|id| id == $lang::ImportClause) {
+                            for child in clause.children() {
+                                match child.kind_id().into() {
+                                    $lang::Identifier => symbols.push("default".to_string()),
+                                    $lang::NamespaceImport => symbols.push("*".to_string()),
+                                    $lang::NamedImports => symbols.extend(
+                                        child
+                                            .children()
+                                            .filter(|s| s.kind_id() == $lang::ImportSpecifier)
+                                            .filter_map(|s| s.child_by_field_name("name"))
+                                            .filter_map(|name| name.utf8_text(code))
+                                            .map(|name| unquote(name).to_string()),
+                                    ),
+                                    _ => {}
+                                }
+                            }
+                        }
+                        imports.push(Import::new(
+                            node,
+                            unquote(path),
+                            symbols,
+                            ImportKind::Import,
+                        ));
+                    }
+                    $lang::CallExpression => {
+                        let kind = match node.child_by_field_name("function") {
+                            Some(function) if function.kind_id() == $lang::Import => {
+                                ImportKind::Dynamic
+                            }
+                            Some(function)
+                                if function.kind_id() == $lang::Identifier
+                                    && function.utf8_text(code) == Some("require") =>
+                            {
+                                ImportKind::Require
+                            }
+                            _ => return,
+                        };
+                        // Only the modules named by a string literal are known
+                        if let Some(path) = node
+                            .child_by_field_name("arguments")
+                            .and_then(|arguments| arguments.child(1))
+                            .filter(|path| matches!(path.kind_id().into(), $($lang::$string)|+))
+                            .and_then(|path| path.utf8_text(code))
+                        {
+                            imports.push(Import::new(node, unquote(path), Vec::new(), kind));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+js_imports!(MozjsCode, Mozjs, [String | String2]);
+js_imports!(JavascriptCode, Javascript, [String | String2]);
+js_imports!(TypescriptCode, Typescript, [String | String2]);
+js_imports!(TsxCode, Tsx, [String | String2 | String3]);
+
+implement_metric_trait!([Imports], PreprocCode, CcommentCode);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn python_imports() {
+        check_metrics::<PythonParser>(
+            "import os.path, sys as s
+             from ..a import (b as c, d)
+             def f():
+                 from m import *",
+            "foo.py",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.imports,
+                    @r###"
+                    {
+                      "static": 4.0,
+                      "dynamic": 0.0,
+                      "total": 4.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_imports() {
+        check_metrics::<JavascriptParser>(
+            "import d, { a as b } from './m';
+             const x = require('r');
+             import('dyn').then(f);
+             import(name);",
+            "foo.js",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.imports,
+                    @r###"
+                    {
+                      "static": 1.0,
+                      "dynamic": 2.0,
+                      "total": 3.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_imports_per_space() {
+        check_func_space::<RustParser, _>(
+            "use std::io::{self, Read, fmt::{Debug, Display}};
+             fn f() {
+                 use super::*;
+             }",
+            "foo.rs",
+            |func_space| {
+                let f = &func_space.spaces[0].metrics.imports;
+                assert_eq!(f.static_imports(), 1.);
+                assert_eq!(func_space.metrics.imports.static_imports(), 2.);
+                assert_eq!(func_space.metrics.imports.total(), 3.);
+            },
+        );
+    }
+}
@@ -0,0 +1,647 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The `Literals` metric.
+///
+/// This metric counts the hard-coded literals of a space:
+/// the magic numbers, which are the numeric literals not contained in the
+/// list of allowed numbers, and the string literals repeated in the space.
+///
+/// The literals used to initialize constants, `static` or `final`
+/// variables and enum values are not counted.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    magic_numbers: usize,
+    repeated_strings: usize,
+    magic_numbers_sum: usize,
+    repeated_strings_sum: usize,
+    strings: HashMap<String, usize>,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("literals", 2)?;
+        st.serialize_field("magic_numbers", &self.magic_numbers_sum())?;
+        st.serialize_field("repeated_strings", &self.repeated_strings_sum())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "magic_numbers: {}, repeated_strings: {}",
+            self.magic_numbers_sum(),
+            self.repeated_strings_sum()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Literals` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.magic_numbers_sum += other.magic_numbers_sum;
+        self.repeated_strings_sum += other.repeated_strings_sum;
+    }
+
+    /// Returns the number of magic numbers in a space
+    #[inline(always)]
+    pub fn magic_numbers(&self) -> f64 {
+        self.magic_numbers as f64
+    }
+
+    /// Returns the number of occurrences of the string literals
+    /// repeated in a space
+    #[inline(always)]
+    pub fn repeated_strings(&self) -> f64 {
+        self.repeated_strings as f64
+    }
+
+    /// Returns the number of magic numbers in a space and its subspaces
+    #[inline(always)]
+    pub fn magic_numbers_sum(&self) -> f64 {
+        self.magic_numbers_sum as f64
+    }
+
+    /// Returns the number of occurrences of the string literals
+    /// repeated in a space, summed over the space and its subspaces
+    #[inline(always)]
+    pub fn repeated_strings_sum(&self) -> f64 {
+        self.repeated_strings_sum as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.repeated_strings = self.strings.values().filter(|&&n| n > 1).sum();
+        self.strings.clear();
+        self.magic_numbers_sum += self.magic_numbers;
+        self.repeated_strings_sum += self.repeated_strings;
+    }
+}
+
+/// The kind of a literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiteralKind {
+    /// A numeric literal
+    Number,
+    /// A string literal
+    String,
+}
+
+/// A hard-coded literal.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum HardCoded<'a> {
+    /// A magic number, including its sign
+    Number(Node<'a>),
+    /// A string literal
+    String(Node<'a>),
+}
+
+pub trait Literals
+where
+    Self: Checker,
+{
+    /// Returns the kind of the literal represented by a node, if any
+    fn literal(_node: &Node) -> Option<LiteralKind> {
+        None
+    }
+
+    /// Checks whether the literals contained in a node are not hard-coded,
+    /// as in constant declarations, enum values and annotations
+    fn is_constant(_node: &Node, _code: &[u8]) -> bool {
+        false
+    }
+
+    /// Checks whether a node is an expression used as statement
+    fn is_expression_statement(_node: &Node) -> bool {
+        false
+    }
+
+    /// Checks whether a node negates its operand, as in `-1`
+    fn is_negation(_node: &Node) -> bool {
+        false
+    }
+
+    fn compute(node: &Node, code: &[u8], allowed_numbers: &[f64], stats: &mut Stats) {
+        match hard_coded::<Self>(node, code, allowed_numbers) {
+            Some(HardCoded::Number(_)) => stats.magic_numbers += 1,
+            Some(HardCoded::String(node)) => {
+                if let Some(text) = node.utf8_text(code) {
+                    *stats.strings.entry(text.to_string()).or_default() += 1;
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+// Returns a literal if it is a magic number or a string which could be repeated
+pub(crate) fn hard_coded<'a, T: Literals + ?Sized>(
+    node: &Node<'a>,
+    code: &[u8],
+    allowed_numbers: &[f64],
+) -> Option<HardCoded<'a>> {
+    // Some keywords, as the `number` type, share their name with a literal
+    if !node.is_named() {
+        return None;
+    }
+    let literal = T::literal(node)?;
+
+    // A literal used as statement is a docstring or a directive, as "use strict"
+    let parent = node.parent();
+    if parent.is_some_and(|parent| T::is_expression_statement(&parent)) {
+        return None;
+    }
+
+    let hard_coded = match literal {
+        LiteralKind::Number => {
+            let (node, sign) = match parent {
+                Some(parent) if T::is_negation(&parent) => (parent, -1.),
+                _ => (*node, 1.),
+            };
+            let value = node
+                .utf8_text(code)
+                .and_then(|text| parse_number(text.trim_start_matches(['-', ' '])));
+            if value.is_some_and(|value| allowed_numbers.contains(&(sign * value))) {
+                return None;
+            }
+            HardCoded::Number(node)
+        }
+        LiteralKind::String => {
+            let text = node.utf8_text(code)?;
+            // Empty strings are ignored, with their prefix, as in `r""` or `f""`
+            if text
+                .trim_start_matches(|c: char| c.is_ascii_alphabetic())
+                .trim_matches(|c: char| "\"'`#".contains(c))
+                .is_empty()
+            {
+                return None;
+            }
+            HardCoded::String(*node)
+        }
+    };
+
+    let mut ancestor = node.parent();
+    while let Some(node) = ancestor {
+        if T::is_func(&node) {
+            break;
+        }
+        if T::is_constant(&node, code) {
+            return None;
+        }
+        ancestor = node.parent();
+    }
+
+    Some(hard_coded)
+}
+
+// Checks whether a node is a unary expression with a `-` operator
+#[inline(always)]
+fn is_unary_minus(node: &Node, unary: u16, minus: u16) -> bool {
+    node.kind_id() == unary
+        && node.child_count() == 2
+        && node.child(0).is_some_and(|op| op.kind_id() == minus)
+}
+
+// Parses a numeric literal, ignoring its separators, radix prefix and type suffix
+fn parse_number(text: &str) -> Option<f64> {
+    let text = text.replace(['_', '\''], "").to_ascii_lowercase();
+    for (prefix, radix) in [("0x", 16), ("0b", 2), ("0o", 8)] {
+        if let Some(digits) = text.strip_prefix(prefix) {
+            let end = digits
+                .find(|c: char| !c.is_digit(radix))
+                .unwrap_or(digits.len());
+            return u64::from_str_radix(&digits[..end], radix)
+                .ok()
+                .map(|n| n as f64);
+        }
+    }
+
+    let bytes = text.as_bytes();
+    let mut end = 0;
+    while end < bytes.len() {
+        match bytes[end] {
+            b'0'..=b'9' | b'.' => end += 1,
+            b'e' => {
+                let sign = matches!(bytes.get(end + 1), Some(b'+' | b'-')) as usize;
+                if !bytes.get(end + 1 + sign).is_some_and(u8::is_ascii_digit) {
+                    break;
+                }
+                end += 1 + sign;
+            }
+            _ => break,
+        }
+    }
+    text[..end].parse().ok()
+}
+
+// Checks whether a declaration has a child, as a modifier,
+// with one of the given kinds and texts
+fn has_modifier(node: &Node, code: &[u8], kinds: &[&str], texts: &[&str]) -> bool {
+    node.children().any(|child| {
+        kinds.contains(&child.kind())
+            && child.utf8_text(code).is_some_and(|text| {
+                text.split_whitespace()
+                    .any(|modifier| texts.contains(&modifier))
+            })
+    })
+}
+
+impl Literals for PythonCode {
+    fn literal(node: &Node) -> Option<LiteralKind> {
+        match node.kind_id().into() {
+            Python::Integer | Python::Float => Some(LiteralKind::Number),
+            Python::String => Some(LiteralKind::String),
+            _ => None,
+        }
+    }
+
+    fn is_constant(node: &Node, code: &[u8]) -> bool {
+        // Constants are named in uppercase by convention
+        matches!(node.kind_id().into(), Python::Assignment)
+            && node
+                .child_by_field_name("left")
+                .filter(|left| left.kind_id() == Python::Identifier)
+                .and_then(|left| left.utf8_text(code))
+                .is_some_and(|name| {
+                    name.chars().any(|c| c.is_ascii_uppercase())
+                        && !name.chars().any(|c| c.is_ascii_lowercase())
+                })
+            || matches!(node.kind_id().into(), Python::Decorator)
+    }
+
+    fn is_expression_statement(node: &Node) -> bool {
+        node.kind_id() == Python::ExpressionStatement
+    }
+
+    fn is_negation(node: &Node) -> bool {
+        is_unary_minus(node, Python::UnaryOperator as u16, Python::DASH as u16)
+    }
+}
+
+impl Literals for RustCode {
+    fn literal(node: &Node) -> Option<LiteralKind> {
+        use Rust::*;
+
+        match node.kind_id().into() {
+            IntegerLiteral | FloatLiteral => {
+                // The index of a tuple field, as in `t.0`
+                if node
+                    .parent()
+                    .is_some_and(|parent| parent.kind_id() == FieldExpression)
+                {
+                    None
+                } else {
+                    Some(LiteralKind::Number)
+                }
+            }
+            StringLiteral | RawStringLiteral => Some(LiteralKind::String),
+            _ => None,
+        }
+    }
+
+    fn is_constant(node: &Node, _code: &[u8]) -> bool {
+        use Rust::*;
+
+        matches!(
+            node.kind_id().into(),
+            ConstItem | StaticItem | EnumVariant | AttributeItem | InnerAttributeItem
+        )
+    }
+
+    fn is_expression_statement(node: &Node) -> bool {
+        node.kind_id() == Rust::ExpressionStatement
+    }
+
+    fn is_negation(node: &Node) -> bool {
+        is_unary_minus(node, Rust::UnaryExpression as u16, Rust::DASH as u16)
+    }
+}
+
+impl Literals for JavaCode {
+    fn literal(node: &Node) -> Option<LiteralKind> {
+        use Java::*;
+
+        match node.kind_id().into() {
+            DecimalIntegerLiteral
+            | HexIntegerLiteral
+            | OctalIntegerLiteral
+            | BinaryIntegerLiteral
+            | DecimalFloatingPointLiteral
+            | HexFloatingPointLiteral => Some(LiteralKind::Number),
+            StringLiteral => Some(LiteralKind::String),
+            _ => None,
+        }
+    }
+
+    fn is_constant(node: &Node, code: &[u8]) -> bool {
+        use Java::*;
+
+        match node.kind_id().into() {
+            FieldDeclaration | LocalVariableDeclaration => {
+                has_modifier(node, code, &["modifiers"], &["static", "final"])
+            }
+            ConstantDeclaration | EnumConstant | Annotation | MarkerAnnotation => true,
+            _ => false,
+        }
+    }
+
+    fn is_expression_statement(node: &Node) -> bool {
+        node.kind_id() == Java::ExpressionStatement
+    }
+
+    fn is_negation(node: &Node) -> bool {
+        is_unary_minus(node, Java::UnaryExpression as u16, Java::DASH as u16)
+    }
+}
+
+impl Literals for KotlinCode {
+    fn literal(node: &Node) -> Option<LiteralKind> {
+        use Kotlin::*;
+
+        match node.kind_id().into() {
+            NumberLiteral | FloatLiteral => Some(LiteralKind::Number),
+            StringLiteral | MultilineStringLiteral => Some(LiteralKind::String),
+            _ => None,
+        }
+    }
+
+    fn is_constant(node: &Node, code: &[u8]) -> bool {
+        use Kotlin::*;
+
+        match node.kind_id().into() {
+            PropertyDeclaration => has_modifier(node, code, &["modifiers"], &["const"]),
+            EnumEntry | Annotation | Annotation2 => true,
+            _ => false,
+        }
+    }
+
+    fn is_negation(node: &Node) -> bool {
+        is_unary_minus(node, Kotlin::UnaryExpression as u16, Kotlin::DASH as u16)
+    }
+}
+
+impl Literals for CppCode {
+    fn literal(node: &Node) -> Option<LiteralKind> {
+        match node.kind_id().into() {
+            Cpp::NumberLiteral => Some(LiteralKind::Number),
+            Cpp::StringLiteral | Cpp::RawStringLiteral => Some(LiteralKind::String),
+            _ => None,
+        }
+    }
+
+    fn is_constant(node: &Node, code: &[u8]) -> bool {
+        match node.kind_id().into() {
+            Cpp::Declaration
+            | Cpp::Declaration2
+            | Cpp::Declaration3
+            | Cpp::Declaration4
+            | Cpp::FieldDeclaration => has_modifier(
+                node,
+                code,
+                &["type_qualifier", "storage_class_specifier"],
+                &["const", "constexpr", "constinit", "static"],
+            ),
+            Cpp::PreprocDef | Cpp::Enumerator | Cpp::AttributeDeclaration => true,
+            _ => false,
+        }
+    }
+
+    fn is_expression_statement(node: &Node) -> bool {
+        node.kind_id() == Cpp::ExpressionStatement
+    }
+
+    fn is_negation(node: &Node) -> bool {
+        is_unary_minus(node, Cpp::UnaryExpression as u16, Cpp::DASH as u16)
+    }
+}
+
+macro_rules! js_literals {
+    ($code:ident, $lang:ident) => {
+        impl Literals for $code {
+            fn literal(node: &Node) -> Option<LiteralKind> {
+                use $lang::*;
+
+                match node.kind_id().into() {
+                    Number => Some(LiteralKind::Number),
+                    String | String2 | TemplateString => Some(LiteralKind::String),
+                    _ => None,
+                }
+            }
+
+            fn is_constant(node: &Node, code: &[u8]) -> bool {
+                use $lang::*;
+
+                match node.kind_id().into() {
+                    LexicalDeclaration => node
+                        .child(0)
+                        .is_some_and(|keyword| keyword.kind_id() == Const),
+                    FieldDefinition => has_modifier(node, code, &["static"], &["static"]),
+                    Decorator => true,
+                    _ => false,
+                }
+            }
+
+            fn is_expression_statement(node: &Node) -> bool {
+                node.kind_id() == $lang::ExpressionStatement
+            }
+
+            fn is_negation(node: &Node) -> bool {
+                is_unary_minus(node, $lang::UnaryExpression as u16, $lang::DASH as u16)
+            }
+        }
+    };
+}
+
+js_literals!(MozjsCode, Mozjs);
+js_literals!(JavascriptCode, Javascript);
+
+macro_rules! ts_literals {
+    ($code:ident, $lang:ident, [$($string:ident),+]) => {
+        impl Literals for $code {
+            fn literal(node: &Node) -> Option<LiteralKind> {
+                use $lang::*;
+
+                match node.kind_id().into() {
+                    Number => Some(LiteralKind::Number),
+                    $($string)|+ | TemplateString => Some(LiteralKind::String),
+                    _ => None,
+                }
+            }
+
+            fn is_constant(node: &Node, code: &[u8]) -> bool {
+                use $lang::*;
+
+                match node.kind_id().into() {
+                    LexicalDeclaration => node
+                        .child(0)
+                        .is_some_and(|keyword| keyword.kind_id() == Const),
+                    PublicFieldDefinition => has_modifier(node, code, &["static"], &["static"]),
+                    EnumBody | Decorator => true,
+                    _ => false,
+                }
+            }
+
+            fn is_expression_statement(node: &Node) -> bool {
+                node.kind_id() == $lang::ExpressionStatement
+            }
+
+            fn is_negation(node: &Node) -> bool {
+                is_unary_minus(node, $lang::UnaryExpression as u16, $lang::DASH as u16)
+            }
+        }
+    };
+}
+
+ts_literals!(TypescriptCode, Typescript, [String, String2]);
+ts_literals!(TsxCode, Tsx, [String, String2, String3]);
+
+implement_metric_trait!([Literals], PreprocCode, CcommentCode);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_func_space_with_options, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn rust_literals() {
+        check_metrics::<RustParser>(
+            "const LIMIT: u32 = 42;
+             static NAME: &str = \"name\";
+             enum E { A = 3 }
+             #[cfg(feature = \"x\")]
+             fn f(t: (u8, u8)) -> f64 {
+                 let a = t.1 + 0 + 1 + 2 - 1;
+                 let b = -1 * -3 + 0x10 + 1_000u32;
+                 g(\"key\", \"key\", \"other\", \"\");
+                 1.5
+             }",
+            "foo.rs",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.literals,
+                    @r###"
+                    {
+                      "magic_numbers": 4.0,
+                      "repeated_strings": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn python_literals() {
+        check_metrics::<PythonParser>(
+            "MAX_SIZE = 1024
+             def f(x):
+                 \"\"\"Docstring\"\"\"
+                 if x > 100:
+                     return 'big'
+                 return 'big' if x > 2.5 else 'small'",
+            "foo.py",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.literals,
+                    @r###"
+                    {
+                      "magic_numbers": 2.0,
+                      "repeated_strings": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_literals() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 static final int SIZE = 16;
+                 private int limit = 8;
+                 enum Color { RED(255), GREEN(128) }
+                 @Deprecated(since = \"9\")
+                 int f(int x) {
+                     final int factor = 7;
+                     return x * 60 + 0xFFL + factor;
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.literals,
+                    @r###"
+                    {
+                      "magic_numbers": 3.0,
+                      "repeated_strings": 0.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn typescript_literals_per_space() {
+        check_func_space::<TypescriptParser, _>(
+            "const TIMEOUT = 3000;
+             enum Level { Low = 10, High = 20 }
+             function f(x: number): string {
+                 if (x > 42) {
+                     return \"high\";
+                 }
+                 return x > 7 ? \"high\" : `low`;
+             }
+             function g(): string {
+                 return \"high\";
+             }",
+            "foo.ts",
+            |func_space| {
+                let f = &func_space.spaces[0].metrics.literals;
+                let g = &func_space.spaces[1].metrics.literals;
+                assert_eq!((f.magic_numbers(), f.repeated_strings()), (2., 2.));
+                assert_eq!((g.magic_numbers(), g.repeated_strings()), (0., 0.));
+                insta::assert_json_snapshot!(
+                    func_space.metrics.literals,
+                    @r###"
+                    {
+                      "magic_numbers": 2.0,
+                      "repeated_strings": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn allowed_numbers() {
+        let options = MetricsOptions {
+            allowed_numbers: Some(vec![-3., 60.]),
+            ..MetricsOptions::default()
+        };
+        check_func_space_with_options::<JavaParser, _>(
+            "class A {
+                 int f(int x) {
+                     return x * 60 + 1 - 3 + -3;
+                 }
+             }",
+            "foo.java",
+            &options,
+            |func_space| {
+                // The default allowed numbers are replaced
+                assert_eq!(func_space.metrics.literals.magic_numbers_sum(), 2.);
+            },
+        );
+    }
+}
@@ -347,6 +347,23 @@ impl Stats {
         self.blank_max = self.blank_max.max(other.blank() as usize);
     }
 
+    // Moves the lines of a space by `delta` lines, when the code
+    // before the space has been edited
+    pub(crate) fn shift_lines(&mut self, delta: isize) {
+        self.sloc.start = self.sloc.start.saturating_add_signed(delta);
+        self.sloc.end = self.sloc.end.saturating_add_signed(delta);
+        self.ploc.lines = self
+            .ploc
+            .lines
+            .iter()
+            .map(|line| line.saturating_add_signed(delta))
+            .collect();
+        self.cloc.comment_line_end = self
+            .cloc
+            .comment_line_end
+            .map(|line| line.saturating_add_signed(delta));
+    }
+
     /// The `Sloc` metric.
     ///
     /// Counts the number of lines in a scope
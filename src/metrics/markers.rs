@@ -0,0 +1,316 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::debt::MarkerPatterns;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The `Markers` metric.
+///
+/// This metric counts the technical-debt markers,
+/// `TODO`, `FIXME`, `HACK` and `XXX`, contained in the comments of a space.
+/// The markers found by custom patterns are counted as `others`.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    todo: usize,
+    fixme: usize,
+    hack: usize,
+    xxx: usize,
+    others: BTreeMap<String, usize>,
+    todo_sum: usize,
+    fixme_sum: usize,
+    hack_sum: usize,
+    xxx_sum: usize,
+    others_sum: BTreeMap<String, usize>,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("markers", 6)?;
+        st.serialize_field("todo", &self.todo_sum())?;
+        st.serialize_field("fixme", &self.fixme_sum())?;
+        st.serialize_field("hack", &self.hack_sum())?;
+        st.serialize_field("xxx", &self.xxx_sum())?;
+        if self.others_sum.is_empty() {
+            st.skip_field("others")?;
+        } else {
+            let others = self
+                .others_sum
+                .iter()
+                .map(|(marker, count)| (marker, *count as f64))
+                .collect::<BTreeMap<_, _>>();
+            st.serialize_field("others", &others)?;
+        }
+        st.serialize_field("total", &self.total())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "todo: {}, fixme: {}, hack: {}, xxx: {}, total: {}",
+            self.todo_sum(),
+            self.fixme_sum(),
+            self.hack_sum(),
+            self.xxx_sum(),
+            self.total()
+        )?;
+        for (marker, count) in &self.others_sum {
+            write!(f, ", {marker}: {count}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Stats {
+    /// Merges a second `Markers` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.todo_sum += other.todo_sum;
+        self.fixme_sum += other.fixme_sum;
+        self.hack_sum += other.hack_sum;
+        self.xxx_sum += other.xxx_sum;
+        for (marker, count) in &other.others_sum {
+            *self.others_sum.entry(marker.clone()).or_default() += count;
+        }
+    }
+
+    /// Returns the number of `TODO` markers in a space
+    #[inline(always)]
+    pub fn todo(&self) -> f64 {
+        self.todo as f64
+    }
+
+    /// Returns the number of `FIXME` markers in a space
+    #[inline(always)]
+    pub fn fixme(&self) -> f64 {
+        self.fixme as f64
+    }
+
+    /// Returns the number of `HACK` markers in a space
+    #[inline(always)]
+    pub fn hack(&self) -> f64 {
+        self.hack as f64
+    }
+
+    /// Returns the number of `XXX` markers in a space
+    #[inline(always)]
+    pub fn xxx(&self) -> f64 {
+        self.xxx as f64
+    }
+
+    /// Returns the number of markers of a custom kind in a space
+    #[inline(always)]
+    pub fn other(&self, marker: &str) -> f64 {
+        self.others.get(marker).copied().unwrap_or_default() as f64
+    }
+
+    /// Returns the number of `TODO` markers in a space and its subspaces
+    #[inline(always)]
+    pub fn todo_sum(&self) -> f64 {
+        self.todo_sum as f64
+    }
+
+    /// Returns the number of `FIXME` markers in a space and its subspaces
+    #[inline(always)]
+    pub fn fixme_sum(&self) -> f64 {
+        self.fixme_sum as f64
+    }
+
+    /// Returns the number of `HACK` markers in a space and its subspaces
+    #[inline(always)]
+    pub fn hack_sum(&self) -> f64 {
+        self.hack_sum as f64
+    }
+
+    /// Returns the number of `XXX` markers in a space and its subspaces
+    #[inline(always)]
+    pub fn xxx_sum(&self) -> f64 {
+        self.xxx_sum as f64
+    }
+
+    /// Returns the number of markers of a custom kind
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn other_sum(&self, marker: &str) -> f64 {
+        self.others_sum.get(marker).copied().unwrap_or_default() as f64
+    }
+
+    /// Returns the total number of markers in a space and its subspaces
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.todo_sum()
+            + self.fixme_sum()
+            + self.hack_sum()
+            + self.xxx_sum()
+            + self.others_sum.values().sum::<usize>() as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.todo_sum += self.todo;
+        self.fixme_sum += self.fixme;
+        self.hack_sum += self.hack;
+        self.xxx_sum += self.xxx;
+        for (marker, count) in &self.others {
+            *self.others_sum.entry(marker.clone()).or_default() += count;
+        }
+    }
+
+    fn add(&mut self, marker: &str) {
+        match marker {
+            "TODO" => self.todo += 1,
+            "FIXME" => self.fixme += 1,
+            "HACK" => self.hack += 1,
+            "XXX" => self.xxx += 1,
+            _ => *self.others.entry(marker.to_string()).or_default() += 1,
+        }
+    }
+}
+
+pub trait Markers
+where
+    Self: Checker,
+{
+    fn compute(node: &Node, code: &[u8], patterns: &MarkerPatterns, stats: &mut Stats) {
+        if !Self::is_comment(node) {
+            return;
+        }
+        if let Some(text) = node.utf8_text(code) {
+            patterns.scan(text, |_, found| stats.add(found.kind));
+        }
+    }
+}
+
+implement_metric_trait!(
+    [Markers],
+    PythonCode,
+    MozjsCode,
+    JavascriptCode,
+    TypescriptCode,
+    TsxCode,
+    RustCode,
+    CppCode,
+    PreprocCode,
+    CcommentCode,
+    JavaCode,
+    KotlinCode
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn rust_markers() {
+        check_metrics::<RustParser>(
+            "// TODO: first
+             fn f() {
+                 // FIXME(bob) second
+                 // TODO third
+             }
+             /* HACK */",
+            "foo.rs",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.markers,
+                    @r###"
+                    {
+                      "todo": 2.0,
+                      "fixme": 1.0,
+                      "hack": 1.0,
+                      "xxx": 0.0,
+                      "total": 4.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn python_markers() {
+        check_metrics::<PythonParser>(
+            "# XXX outside
+             def f():
+                 # todo is not a marker
+                 s = 'TODO in a string'
+                 return s",
+            "foo.py",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.markers,
+                    @r###"
+                    {
+                      "todo": 0.0,
+                      "fixme": 0.0,
+                      "hack": 0.0,
+                      "xxx": 1.0,
+                      "total": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_markers_in_subspaces() {
+        check_func_space::<JavaParser, _>(
+            "class A {
+                 // TODO one
+                 void f() {
+                     // FIXME two
+                 }
+             }",
+            "foo.java",
+            |space| {
+                let class = &space.spaces[0];
+                assert_eq!(class.metrics.markers.todo(), 1.);
+                assert_eq!(class.metrics.markers.total(), 2.);
+                assert_eq!(class.spaces[0].metrics.markers.fixme(), 1.);
+                assert_eq!(space.metrics.markers.total(), 2.);
+            },
+        );
+    }
+
+    #[test]
+    fn custom_markers() {
+        let source_code =
+            "// NOTE: first\nfn f() {\n    // TODO second\n    // @deprecated-soon\n}\n";
+        let path = std::path::Path::new("foo.rs");
+        let parser = RustParser::new(source_code.into(), path, None).unwrap();
+        let options = MetricsOptions {
+            markers: Some(MarkerPatterns::new(&["NOTE"], &[r"@(deprecated)-soon"]).unwrap()),
+            ..MetricsOptions::default()
+        };
+        let space = metrics_with_options(&parser, path, &options).unwrap();
+
+        // The default markers are replaced
+        assert_eq!(space.metrics.markers.todo_sum(), 0.);
+        assert_eq!(space.spaces[0].metrics.markers.other("deprecated"), 1.);
+        insta::assert_json_snapshot!(
+            space.metrics.markers,
+            @r###"
+            {
+              "todo": 0.0,
+              "fixme": 0.0,
+              "hack": 0.0,
+              "xxx": 0.0,
+              "others": {
+                "NOTE": 1.0,
+                "deprecated": 1.0
+              },
+              "total": 2.0
+            }"###
+        );
+    }
+}
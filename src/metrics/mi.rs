@@ -2,6 +2,7 @@ use serde::Serialize;
 use serde::ser::{SerializeStruct, Serializer};
 use std::fmt;
 
+use super::cognitive;
 use super::cyclomatic;
 use super::halstead;
 use super::loc;
@@ -11,6 +12,151 @@ use crate::macros::implement_metric_trait;
 
 use crate::*;
 
+/// The complexity metric used by a `Mi` formula.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MiComplexity {
+    /// The sum of the `Cyclomatic` complexity
+    #[default]
+    Cyclomatic,
+    /// The sum of the `Cognitive` complexity
+    Cognitive,
+}
+
+/// The logarithm used by a `Mi` formula.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MiLog {
+    /// The natural logarithm
+    #[default]
+    Natural,
+    /// The binary logarithm
+    Binary,
+}
+
+/// The rating of a `Mi` value, from `A`, the most maintainable code,
+/// to `F`, the least maintainable one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MiRating {
+    /// Rating `A`
+    A,
+    /// Rating `B`
+    B,
+    /// Rating `C`
+    C,
+    /// Rating `D`
+    D,
+    /// Rating `E`
+    E,
+    /// Rating `F`
+    F,
+}
+
+impl fmt::Display for MiRating {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// A custom formula of the `Mi` metric, computed as
+///
+/// `constant - volume * log(V) - complexity * C - sloc * log(SLOC)
+///  + comments * sin(sqrt(2.4 * CM))`
+///
+/// where `V` is the `Halstead` volume, `C` the chosen complexity
+/// and `CM` the ratio of comment lines.
+///
+/// The default formula is the one employed by Microsoft Visual Studio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MiFormula {
+    /// The constant term
+    pub constant: f64,
+    /// The coefficient of the `Halstead` volume
+    pub volume: f64,
+    /// The coefficient of the complexity
+    pub complexity: f64,
+    /// The coefficient of the source lines of code
+    pub sloc: f64,
+    /// The weight of the comments, `0` to exclude them
+    pub comments: f64,
+    /// The complexity metric
+    pub complexity_metric: MiComplexity,
+    /// The logarithm applied to the volume and the source lines of code
+    pub log: MiLog,
+    /// Whether the value is normalized between 0 and 100,
+    /// dividing it by the constant term
+    pub normalize: bool,
+    /// The minimum values rated from `A` to `E`,
+    /// the lower values being rated `F`
+    pub ratings: [f64; 5],
+}
+
+impl Default for MiFormula {
+    fn default() -> Self {
+        Self::visual_studio()
+    }
+}
+
+impl MiFormula {
+    // The thresholds of the ratings of a value between 0 and 100,
+    // where D, E and F match the green, yellow and red levels of Visual Studio
+    const NORMALIZED_RATINGS: [f64; 5] = [80., 60., 40., 20., 10.];
+
+    /// The original formula.
+    pub fn original() -> Self {
+        Self {
+            constant: 171.0,
+            volume: 5.2,
+            complexity: 0.23,
+            sloc: 16.2,
+            comments: 0.,
+            complexity_metric: MiComplexity::Cyclomatic,
+            log: MiLog::Natural,
+            normalize: false,
+            ratings: Self::NORMALIZED_RATINGS.map(|rating| rating * 1.71),
+        }
+    }
+
+    /// The derivative formula employed by the Software Engineering Insitute (SEI).
+    pub fn sei() -> Self {
+        Self {
+            comments: 50.0,
+            log: MiLog::Binary,
+            ..Self::original()
+        }
+    }
+
+    /// The derivative formula employed by Microsoft Visual Studio.
+    pub fn visual_studio() -> Self {
+        Self {
+            normalize: true,
+            ratings: Self::NORMALIZED_RATINGS,
+            ..Self::original()
+        }
+    }
+
+    /// Returns the rating of a value computed with this formula.
+    pub fn rating(&self, value: f64) -> MiRating {
+        const RATINGS: [MiRating; 5] = [
+            MiRating::A,
+            MiRating::B,
+            MiRating::C,
+            MiRating::D,
+            MiRating::E,
+        ];
+        RATINGS
+            .into_iter()
+            .zip(self.ratings)
+            .find(|(_, min)| value >= *min)
+            .map_or(MiRating::F, |(rating, _)| rating)
+    }
+
+    fn log(&self, value: f64) -> f64 {
+        match self.log {
+            MiLog::Natural => value.ln(),
+            MiLog::Binary => value.log2(),
+        }
+    }
+}
+
 /// The `Mi` metric.
 #[derive(Default, Clone, Debug)]
 pub struct Stats {
@@ -18,8 +164,10 @@ pub struct Stats {
     halstead_vocabulary: f64,
     halstead_volume: f64,
     cyclomatic: f64,
+    cognitive: f64,
     sloc: f64,
     comments_percentage: f64,
+    formula: Option<MiFormula>,
 }
 
 impl Serialize for Stats {
@@ -27,10 +175,15 @@ impl Serialize for Stats {
     where
         S: Serializer,
     {
-        let mut st = serializer.serialize_struct("maintainability_index", 3)?;
+        let fields = if self.formula.is_some() { 5 } else { 3 };
+        let mut st = serializer.serialize_struct("maintainability_index", fields)?;
         st.serialize_field("mi_original", &self.mi_original())?;
         st.serialize_field("mi_sei", &self.mi_sei())?;
         st.serialize_field("mi_visual_studio", &self.mi_visual_studio())?;
+        if let (Some(mi_custom), Some(rating)) = (self.mi_custom(), self.rating()) {
+            st.serialize_field("mi_custom", &mi_custom)?;
+            st.serialize_field("rating", &rating)?;
+        }
         st.end()
     }
 }
@@ -43,7 +196,11 @@ impl fmt::Display for Stats {
             self.mi_original(),
             self.mi_sei(),
             self.mi_visual_studio()
-        )
+        )?;
+        if let (Some(mi_custom), Some(rating)) = (self.mi_custom(), self.rating()) {
+            write!(f, ", mi_custom: {mi_custom}, rating: {rating}")?;
+        }
+        Ok(())
     }
 }
 
@@ -81,6 +238,38 @@ impl Stats {
             - 16.2 * self.sloc.ln();
         (formula * 100.0 / 171.0).max(0.)
     }
+
+    /// Returns the `Mi` metric calculated using the custom formula.
+    ///
+    /// Returns `None` if no custom formula has been chosen.
+    pub fn mi_custom(&self) -> Option<f64> {
+        let formula = self.formula.as_ref()?;
+        let complexity = match formula.complexity_metric {
+            MiComplexity::Cyclomatic => self.cyclomatic,
+            MiComplexity::Cognitive => self.cognitive,
+        };
+        let mut value = formula.constant
+            - formula.volume * formula.log(self.halstead_volume)
+            - formula.complexity * complexity
+            - formula.sloc * formula.log(self.sloc);
+        // The ratio of comments is not a number when a space is empty
+        if formula.comments != 0. {
+            value += formula.comments * (self.comments_percentage * 2.4).sqrt().sin();
+        }
+        if formula.normalize {
+            value = (value * 100.0 / formula.constant).max(0.);
+        }
+        Some(value)
+    }
+
+    /// Returns the rating of the `Mi` metric calculated
+    /// using the custom formula.
+    ///
+    /// Returns `None` if no custom formula has been chosen.
+    pub fn rating(&self) -> Option<MiRating> {
+        let formula = self.formula.as_ref()?;
+        self.mi_custom().map(|value| formula.rating(value))
+    }
 }
 
 pub trait Mi
@@ -90,15 +279,19 @@ where
     fn compute(
         loc: &loc::Stats,
         cyclomatic: &cyclomatic::Stats,
+        cognitive: &cognitive::Stats,
         halstead: &halstead::Stats,
+        formula: Option<&MiFormula>,
         stats: &mut Stats,
     ) {
         stats.halstead_length = halstead.length();
         stats.halstead_vocabulary = halstead.vocabulary();
         stats.halstead_volume = halstead.volume();
         stats.cyclomatic = cyclomatic.cyclomatic_sum();
+        stats.cognitive = cognitive.cognitive_sum();
         stats.sloc = loc.sloc();
         stats.comments_percentage = loc.cloc() / stats.sloc;
+        stats.formula = formula.copied();
     }
 }
 
@@ -144,4 +337,80 @@ mod tests {
             },
         );
     }
+
+    fn check_mi(source: &str, formula: MiFormula) -> Stats {
+        let path = std::path::Path::new("foo.py");
+        let parser = PythonParser::new(source.as_bytes().to_vec(), path, None).unwrap();
+        let options = MetricsOptions {
+            mi_formula: Some(formula),
+            ..MetricsOptions::default()
+        };
+        metrics_with_options(&parser, path, &options)
+            .unwrap()
+            .metrics
+            .mi
+    }
+
+    #[test]
+    fn check_mi_formulas() {
+        let source = "def f(a):
+                          # Comment
+                          if a:
+                              return 1
+                          return 2";
+
+        let mi = check_mi(source, MiFormula::original());
+        assert_eq!(mi.mi_custom(), Some(mi.mi_original()));
+        let mi = check_mi(source, MiFormula::sei());
+        assert_eq!(mi.mi_custom(), Some(mi.mi_sei()));
+        let mi = check_mi(source, MiFormula::visual_studio());
+        assert_eq!(mi.mi_custom(), Some(mi.mi_visual_studio()));
+        assert_eq!(mi.rating(), Some(MiRating::B));
+
+        // Without the comments, the SEI formula is the original one in base 2
+        let mi = check_mi(
+            source,
+            MiFormula {
+                comments: 0.,
+                ..MiFormula::sei()
+            },
+        );
+        assert!(mi.mi_custom().unwrap() < mi.mi_sei());
+
+        insta::assert_json_snapshot!(
+            check_mi(
+                source,
+                MiFormula {
+                    constant: 100.,
+                    complexity: 1.,
+                    complexity_metric: MiComplexity::Cognitive,
+                    ..MiFormula::visual_studio()
+                },
+            ),
+            @r###"
+            {
+              "mi_original": 131.05880134567775,
+              "mi_sei": 148.65388387563416,
+              "mi_visual_studio": 76.64257388636126,
+              "mi_custom": 59.74880134567774,
+              "rating": "C"
+            }"###
+        );
+    }
+
+    #[test]
+    fn check_mi_ratings() {
+        let formula = MiFormula::visual_studio();
+        assert_eq!(formula.rating(100.), MiRating::A);
+        assert_eq!(formula.rating(80.), MiRating::A);
+        assert_eq!(formula.rating(79.9), MiRating::B);
+        assert_eq!(formula.rating(45.), MiRating::C);
+        assert_eq!(formula.rating(20.), MiRating::D);
+        assert_eq!(formula.rating(15.), MiRating::E);
+        assert_eq!(formula.rating(0.), MiRating::F);
+
+        let formula = MiFormula::original();
+        assert_eq!(formula.rating(136.8), MiRating::A);
+        assert_eq!(formula.rating(-10.), MiRating::F);
+    }
 }
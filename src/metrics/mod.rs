@@ -1,12 +1,22 @@
 pub mod abc;
+pub mod booleans;
 pub mod cognitive;
 pub mod cyclomatic;
+pub mod doc_coverage;
+pub mod duplication;
+pub mod exceptions;
 pub mod exit;
 pub mod halstead;
+pub mod imports;
+pub mod literals;
 pub mod loc;
+pub mod markers;
 pub mod mi;
 pub mod nargs;
 pub mod nom;
 pub mod npa;
 pub mod npm;
+pub mod safety;
+pub mod statements;
+pub mod variables;
 pub mod wmc;
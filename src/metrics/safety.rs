@@ -0,0 +1,348 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The `Safety` metric.
+///
+/// This metric counts the constructs of a space which may lead to
+/// undefined behavior or to a panic at runtime: the `unsafe` blocks,
+/// the `unsafe` functions and implementations, the dereferences in unsafe
+/// code, the `.unwrap()` and `.expect()` calls, the `panic!`,
+/// `unreachable!` and `todo!` invocations and the indexing expressions.
+///
+/// The types of the dereferenced expressions are not known, so every
+/// dereference in unsafe code is counted, the raw pointer ones as well
+/// as the ones of references or boxes.
+///
+/// This metric is only available for Rust.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    unsafe_blocks: usize,
+    unsafe_fns: usize,
+    unsafe_impls: usize,
+    unsafe_derefs: usize,
+    unwraps: usize,
+    panics: usize,
+    indexings: usize,
+    unsafe_blocks_sum: usize,
+    unsafe_fns_sum: usize,
+    unsafe_impls_sum: usize,
+    unsafe_derefs_sum: usize,
+    unwraps_sum: usize,
+    panics_sum: usize,
+    indexings_sum: usize,
+    is_enabled: bool,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("safety", 7)?;
+        st.serialize_field("unsafe_blocks", &self.unsafe_blocks_sum())?;
+        st.serialize_field("unsafe_fns", &self.unsafe_fns_sum())?;
+        st.serialize_field("unsafe_impls", &self.unsafe_impls_sum())?;
+        st.serialize_field("unsafe_derefs", &self.unsafe_derefs_sum())?;
+        st.serialize_field("unwraps", &self.unwraps_sum())?;
+        st.serialize_field("panics", &self.panics_sum())?;
+        st.serialize_field("indexings", &self.indexings_sum())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unsafe_blocks: {}, unsafe_fns: {}, unsafe_impls: {}, unsafe_derefs: {}, unwraps: {}, panics: {}, indexings: {}",
+            self.unsafe_blocks_sum(),
+            self.unsafe_fns_sum(),
+            self.unsafe_impls_sum(),
+            self.unsafe_derefs_sum(),
+            self.unwraps_sum(),
+            self.panics_sum(),
+            self.indexings_sum()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Safety` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.unsafe_blocks_sum += other.unsafe_blocks_sum;
+        self.unsafe_fns_sum += other.unsafe_fns_sum;
+        self.unsafe_impls_sum += other.unsafe_impls_sum;
+        self.unsafe_derefs_sum += other.unsafe_derefs_sum;
+        self.unwraps_sum += other.unwraps_sum;
+        self.panics_sum += other.panics_sum;
+        self.indexings_sum += other.indexings_sum;
+    }
+
+    /// Returns the number of `unsafe` blocks in a space
+    #[inline(always)]
+    pub fn unsafe_blocks(&self) -> f64 {
+        self.unsafe_blocks as f64
+    }
+
+    /// Returns the number of `unsafe` functions in a space
+    #[inline(always)]
+    pub fn unsafe_fns(&self) -> f64 {
+        self.unsafe_fns as f64
+    }
+
+    /// Returns the number of `unsafe` implementations in a space
+    #[inline(always)]
+    pub fn unsafe_impls(&self) -> f64 {
+        self.unsafe_impls as f64
+    }
+
+    /// Returns the number of dereferences in the unsafe code of a space
+    #[inline(always)]
+    pub fn unsafe_derefs(&self) -> f64 {
+        self.unsafe_derefs as f64
+    }
+
+    /// Returns the number of `.unwrap()` and `.expect()` calls in a space
+    #[inline(always)]
+    pub fn unwraps(&self) -> f64 {
+        self.unwraps as f64
+    }
+
+    /// Returns the number of `panic!`, `unreachable!` and `todo!`
+    /// invocations in a space
+    #[inline(always)]
+    pub fn panics(&self) -> f64 {
+        self.panics as f64
+    }
+
+    /// Returns the number of indexing expressions in a space
+    #[inline(always)]
+    pub fn indexings(&self) -> f64 {
+        self.indexings as f64
+    }
+
+    /// Returns the number of `unsafe` blocks in a space and its subspaces
+    #[inline(always)]
+    pub fn unsafe_blocks_sum(&self) -> f64 {
+        self.unsafe_blocks_sum as f64
+    }
+
+    /// Returns the number of `unsafe` functions in a space and its subspaces
+    #[inline(always)]
+    pub fn unsafe_fns_sum(&self) -> f64 {
+        self.unsafe_fns_sum as f64
+    }
+
+    /// Returns the number of `unsafe` implementations
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn unsafe_impls_sum(&self) -> f64 {
+        self.unsafe_impls_sum as f64
+    }
+
+    /// Returns the number of dereferences in the unsafe code
+    /// of a space and its subspaces
+    #[inline(always)]
+    pub fn unsafe_derefs_sum(&self) -> f64 {
+        self.unsafe_derefs_sum as f64
+    }
+
+    /// Returns the number of `.unwrap()` and `.expect()` calls
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn unwraps_sum(&self) -> f64 {
+        self.unwraps_sum as f64
+    }
+
+    /// Returns the number of `panic!`, `unreachable!` and `todo!`
+    /// invocations in a space and its subspaces
+    #[inline(always)]
+    pub fn panics_sum(&self) -> f64 {
+        self.panics_sum as f64
+    }
+
+    /// Returns the number of indexing expressions in a space and its subspaces
+    #[inline(always)]
+    pub fn indexings_sum(&self) -> f64 {
+        self.indexings_sum as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.unsafe_blocks_sum += self.unsafe_blocks;
+        self.unsafe_fns_sum += self.unsafe_fns;
+        self.unsafe_impls_sum += self.unsafe_impls;
+        self.unsafe_derefs_sum += self.unsafe_derefs;
+        self.unwraps_sum += self.unwraps;
+        self.panics_sum += self.panics;
+        self.indexings_sum += self.indexings;
+    }
+
+    // Checks if the `Safety` metric is disabled
+    #[inline(always)]
+    pub(crate) fn is_disabled(&self) -> bool {
+        !self.is_enabled
+    }
+}
+
+pub trait Safety
+where
+    Self: Checker,
+{
+    fn compute(_node: &Node, _code: &[u8], _stats: &mut Stats) {}
+}
+
+// Checks whether a function has the `unsafe` modifier
+fn is_unsafe_fn(node: &Node) -> bool {
+    node.first_child(|id| id == Rust::FunctionModifiers)
+        .is_some_and(|modifiers| modifiers.first_child(|id| id == Rust::Unsafe).is_some())
+}
+
+// Checks whether a node is contained in an `unsafe` block or function,
+// the only places where a raw pointer can be dereferenced
+fn is_in_unsafe_code(node: &Node) -> bool {
+    let mut parent = node.parent();
+    while let Some(node) = parent {
+        match node.kind_id().into() {
+            Rust::UnsafeBlock => return true,
+            Rust::FunctionItem => return is_unsafe_fn(&node),
+            _ => {}
+        }
+        parent = node.parent();
+    }
+    false
+}
+
+impl Safety for RustCode {
+    fn compute(node: &Node, code: &[u8], stats: &mut Stats) {
+        use Rust::*;
+
+        // Enables the `Safety` metric when computing the stats of any space
+        stats.is_enabled = true;
+
+        match node.kind_id().into() {
+            UnsafeBlock => stats.unsafe_blocks += 1,
+            FunctionItem | FunctionSignatureItem => stats.unsafe_fns += is_unsafe_fn(node) as usize,
+            ImplItem => {
+                stats.unsafe_impls += node.first_child(|id| id == Unsafe).is_some() as usize
+            }
+            UnaryExpression => {
+                // Raw pointers can only be dereferenced in unsafe code,
+                // so the dereferences outside of it are ignored
+                let is_deref = node.child(0).is_some_and(|op| op.kind_id() == STAR);
+                stats.unsafe_derefs += (is_deref && is_in_unsafe_code(node)) as usize;
+            }
+            CallExpression => {
+                let is_unwrap = node
+                    .child_by_field_name("function")
+                    .filter(|function| function.kind_id() == FieldExpression)
+                    .and_then(|function| function.child_by_field_name("field"))
+                    .and_then(|field| field.utf8_text(code))
+                    .is_some_and(|field| matches!(field, "unwrap" | "expect"));
+                stats.unwraps += is_unwrap as usize;
+            }
+            MacroInvocation => {
+                // The last segment of a path as `std::panic`
+                let is_panic = node
+                    .child_by_field_name("macro")
+                    .and_then(|name| name.utf8_text(code))
+                    .and_then(|name| name.rsplit("::").next())
+                    .is_some_and(|name| matches!(name, "panic" | "unreachable" | "todo"));
+                stats.panics += is_panic as usize;
+            }
+            IndexExpression => stats.indexings += 1,
+            _ => {}
+        }
+    }
+}
+
+implement_metric_trait!(
+    [Safety],
+    PythonCode,
+    MozjsCode,
+    JavascriptCode,
+    TypescriptCode,
+    TsxCode,
+    JavaCode,
+    KotlinCode,
+    CppCode,
+    PreprocCode,
+    CcommentCode
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::{check_func_space, check_metrics};
+
+    use super::*;
+
+    #[test]
+    fn rust_safety() {
+        check_metrics::<RustParser>(
+            "unsafe impl Send for A {}
+             unsafe fn f(p: *const u8) -> u8 {
+                 *p
+             }
+             fn g(v: Vec<u8>, r: &u8, o: Option<u8>) -> u8 {
+                 let x = unsafe { *v.as_ptr() };
+                 let y = *r + v[0] + v[1];
+                 o.unwrap();
+                 o.expect(\"some\");
+                 if x > y {
+                     std::panic!(\"too big\");
+                 }
+                 todo!()
+             }",
+            "foo.rs",
+            |metric| {
+                insta::assert_json_snapshot!(
+                    metric.safety,
+                    @r###"
+                    {
+                      "unsafe_blocks": 1.0,
+                      "unsafe_fns": 1.0,
+                      "unsafe_impls": 1.0,
+                      "unsafe_derefs": 2.0,
+                      "unwraps": 2.0,
+                      "panics": 2.0,
+                      "indexings": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_safety_per_space() {
+        check_func_space::<RustParser, _>(
+            "fn f(v: &[u8]) -> u8 {
+                 v[0]
+             }
+             fn g(o: Option<u8>) -> u8 {
+                 let h = |x: Option<u8>| x.unwrap();
+                 h(o)
+             }",
+            "foo.rs",
+            |func_space| {
+                let f = &func_space.spaces[0].metrics.safety;
+                let g = &func_space.spaces[1].metrics.safety;
+                assert_eq!((f.indexings(), f.unwraps()), (1., 0.));
+                assert_eq!((g.indexings(), g.unwraps(), g.unwraps_sum()), (0., 0., 1.));
+                assert_eq!(func_space.metrics.safety.unwraps_sum(), 1.);
+            },
+        );
+    }
+
+    #[test]
+    fn python_no_safety() {
+        check_func_space::<PythonParser, _>("def f(a):\n    return a[0]", "foo.py", |func_space| {
+            assert!(func_space.metrics.safety.is_disabled());
+        });
+    }
+}
@@ -0,0 +1,352 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::getter::Getter;
+use crate::macros::implement_metric_trait;
+
+use crate::*;
+
+/// The category of a statement counted by the `Statements` metric.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementKind {
+    /// A declaration of local variables, as `let x = 1;`
+    Declaration,
+    /// An assignment, as `x = 1;` or `x += 1;`
+    Assignment,
+    /// A call whose value is discarded, as `f(x);`
+    Call,
+    /// A branch or a jump, as `if`, `match`, `switch`, `try`,
+    /// `break`, `continue` and `throw`
+    ControlFlow,
+    /// A `return` statement
+    Return,
+    /// A loop, as `for`, `while` and `do`
+    Loop,
+}
+
+// The number of statement categories
+const KINDS: usize = 6;
+
+/// The `Statements` metric.
+///
+/// This metric counts the statements of a space by category:
+/// the declarations, the assignments, the calls, the control flow
+/// statements, the returns and the loops.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    statements: [usize; KINDS],
+    statements_sum: [usize; KINDS],
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("statements", 7)?;
+        st.serialize_field("declarations", &self.declarations())?;
+        st.serialize_field("assignments", &self.assignments())?;
+        st.serialize_field("calls", &self.calls())?;
+        st.serialize_field("control_flow", &self.control_flow())?;
+        st.serialize_field("returns", &self.returns())?;
+        st.serialize_field("loops", &self.loops())?;
+        st.serialize_field("total", &self.total())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "declarations: {}, assignments: {}, calls: {}, control_flow: {}, returns: {}, loops: {}, total: {}",
+            self.declarations(),
+            self.assignments(),
+            self.calls(),
+            self.control_flow(),
+            self.returns(),
+            self.loops(),
+            self.total()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Statements` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        for (sum, other) in self.statements_sum.iter_mut().zip(other.statements_sum) {
+            *sum += other;
+        }
+    }
+
+    /// Returns the number of statements of a given category
+    /// in a space, excluding its subspaces
+    #[inline(always)]
+    pub fn statements(&self, kind: StatementKind) -> f64 {
+        self.statements[kind as usize] as f64
+    }
+
+    /// Returns the number of statements of a given category
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn statements_sum(&self, kind: StatementKind) -> f64 {
+        self.statements_sum[kind as usize] as f64
+    }
+
+    /// Returns the number of declarations in a space and its subspaces
+    #[inline(always)]
+    pub fn declarations(&self) -> f64 {
+        self.statements_sum(StatementKind::Declaration)
+    }
+
+    /// Returns the number of assignments in a space and its subspaces
+    #[inline(always)]
+    pub fn assignments(&self) -> f64 {
+        self.statements_sum(StatementKind::Assignment)
+    }
+
+    /// Returns the number of calls in a space and its subspaces
+    #[inline(always)]
+    pub fn calls(&self) -> f64 {
+        self.statements_sum(StatementKind::Call)
+    }
+
+    /// Returns the number of control flow statements
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn control_flow(&self) -> f64 {
+        self.statements_sum(StatementKind::ControlFlow)
+    }
+
+    /// Returns the number of returns in a space and its subspaces
+    #[inline(always)]
+    pub fn returns(&self) -> f64 {
+        self.statements_sum(StatementKind::Return)
+    }
+
+    /// Returns the number of loops in a space and its subspaces
+    #[inline(always)]
+    pub fn loops(&self) -> f64 {
+        self.statements_sum(StatementKind::Loop)
+    }
+
+    /// Returns the number of statements in a space and its subspaces
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.statements_sum.iter().sum::<usize>() as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        for (sum, statements) in self.statements_sum.iter_mut().zip(self.statements) {
+            *sum += statements;
+        }
+    }
+}
+
+pub trait Statements
+where
+    Self: Checker + Getter,
+{
+    fn compute(node: &Node, stats: &mut Stats) {
+        if let Some(kind) = Self::get_statement_kind(node) {
+            stats.statements[kind as usize] += 1;
+        }
+    }
+}
+
+implement_metric_trait!(
+    [Statements],
+    PythonCode,
+    MozjsCode,
+    JavascriptCode,
+    TypescriptCode,
+    TsxCode,
+    RustCode,
+    CppCode,
+    PreprocCode,
+    CcommentCode,
+    JavaCode,
+    KotlinCode
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_metrics;
+
+    use super::*;
+
+    #[test]
+    fn python_statements() {
+        check_metrics::<PythonParser>(
+            "def f(a):
+                 b = 0
+                 b += a
+                 print(b)
+                 if a:
+                     return 1
+                 elif b:
+                     raise ValueError()
+                 for i in a:
+                     while i:
+                         break
+                 return b",
+            "foo.py",
+            |metric| {
+                // declarations: none in Python
+                // assignments: b = 0, b += a
+                // calls: print(b)
+                // control flow: if, raise, break
+                // returns: return 1, return b
+                // loops: for, while
+                insta::assert_json_snapshot!(
+                    metric.statements,
+                    @r###"
+                    {
+                      "declarations": 0.0,
+                      "assignments": 2.0,
+                      "calls": 1.0,
+                      "control_flow": 3.0,
+                      "returns": 2.0,
+                      "loops": 2.0,
+                      "total": 10.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_statements() {
+        check_metrics::<RustParser>(
+            "fn f(a: u32) -> u32 {
+                 let mut b = 0;
+                 b += a;
+                 println!(\"{}\", b);
+                 g(b);
+                 if a > 0 {
+                     return 1;
+                 } else if b > 0 {
+                     b = 1;
+                 }
+                 match a {
+                     _ => {}
+                 }
+                 loop {
+                     break;
+                 }
+                 b
+             }",
+            "foo.rs",
+            |metric| {
+                // declarations: let
+                // assignments: b += a, b = 1
+                // calls: println!, g(b)
+                // control flow: if, match, break
+                // returns: return 1, b
+                // loops: loop
+                insta::assert_json_snapshot!(
+                    metric.statements,
+                    @r###"
+                    {
+                      "declarations": 1.0,
+                      "assignments": 2.0,
+                      "calls": 2.0,
+                      "control_flow": 3.0,
+                      "returns": 2.0,
+                      "loops": 1.0,
+                      "total": 11.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_statements() {
+        check_metrics::<JavascriptParser>(
+            "function f(a) {
+                 let b = 0, c;
+                 var d = 1;
+                 b++;
+                 console.log(b);
+                 switch (a) {
+                     case 1:
+                         break;
+                 }
+                 for (const x of a) {}
+                 do {} while (b);
+                 try { g(); } catch (e) { throw e; }
+                 return b;
+             }",
+            "foo.js",
+            |metric| {
+                // declarations: let, var, as the for loop binds x itself
+                // assignments: b++
+                // calls: console.log(b), g()
+                // control flow: switch, break, try, throw
+                // returns: return b
+                // loops: for, do
+                insta::assert_json_snapshot!(
+                    metric.statements,
+                    @r###"
+                    {
+                      "declarations": 2.0,
+                      "assignments": 1.0,
+                      "calls": 2.0,
+                      "control_flow": 4.0,
+                      "returns": 1.0,
+                      "loops": 2.0,
+                      "total": 12.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_statements() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 int f(int a) {
+                     int b = 0;
+                     b = a;
+                     System.out.println(b);
+                     new A();
+                     if (a > 0) {
+                         return 1;
+                     } else if (b > 0) {
+                         continue;
+                     }
+                     for (int i : list) {}
+                     while (b > 0) {}
+                     return b;
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                // declarations: int b
+                // assignments: b = a
+                // calls: println, new A()
+                // control flow: if, continue
+                // returns: return 1, return b
+                // loops: for, while
+                insta::assert_json_snapshot!(
+                    metric.statements,
+                    @r###"
+                    {
+                      "declarations": 1.0,
+                      "assignments": 1.0,
+                      "calls": 2.0,
+                      "control_flow": 2.0,
+                      "returns": 2.0,
+                      "loops": 2.0,
+                      "total": 10.0
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+
+use crate::checker::Checker;
+use crate::getter::Getter;
+use crate::halstead::{HalsteadTable, HalsteadType};
+use crate::macros::implement_metric_trait;
+use crate::naming::{IdentifierKind, NamingRules};
+use crate::spaces::SpaceKind;
+
+use crate::*;
+
+/// The `Variables` metric.
+///
+/// This metric measures how far apart the references to the same
+/// variable are in a space: the average and maximum span, as the number
+/// of statements between two consecutive references to a variable, and
+/// the average and maximum number of live variables, as the variables
+/// referenced both before and after a statement, at each statement
+/// referencing a variable.
+///
+/// The variables are the parameters and the local variables declared
+/// in a space, the fields of the types excluded, and their references
+/// are the `Halstead` operands with their names.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    // The references to each declared variable,
+    // as the indices of the statements containing them
+    references: HashMap<String, Vec<usize>>,
+    statement: usize,
+    spans: usize,
+    span_sum: usize,
+    span_max: usize,
+    lines: usize,
+    live_sum: usize,
+    live_max: usize,
+}
+
+impl Serialize for Stats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("variables", 4)?;
+        st.serialize_field("span_average", &self.span_average())?;
+        st.serialize_field("span_max", &self.span_max())?;
+        st.serialize_field("live_average", &self.live_average())?;
+        st.serialize_field("live_max", &self.live_max())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "span_average: {}, span_max: {}, live_average: {}, live_max: {}",
+            self.span_average(),
+            self.span_max(),
+            self.live_average(),
+            self.live_max()
+        )
+    }
+}
+
+impl Stats {
+    /// Merges a second `Variables` metric into the first one
+    pub fn merge(&mut self, other: &Stats) {
+        self.spans += other.spans;
+        self.span_sum += other.span_sum;
+        self.span_max = self.span_max.max(other.span_max);
+        self.lines += other.lines;
+        self.live_sum += other.live_sum;
+        self.live_max = self.live_max.max(other.live_max);
+    }
+
+    /// Returns the average number of statements between two consecutive
+    /// references to a variable
+    #[inline(always)]
+    pub fn span_average(&self) -> f64 {
+        self.span_sum as f64 / self.spans.max(1) as f64
+    }
+
+    /// Returns the maximum number of statements between two consecutive
+    /// references to a variable
+    #[inline(always)]
+    pub fn span_max(&self) -> f64 {
+        self.span_max as f64
+    }
+
+    /// Returns the average number of live variables
+    /// at the statements referencing a variable
+    #[inline(always)]
+    pub fn live_average(&self) -> f64 {
+        self.live_sum as f64 / self.lines.max(1) as f64
+    }
+
+    /// Returns the maximum number of live variables
+    /// at the statements referencing a variable
+    #[inline(always)]
+    pub fn live_max(&self) -> f64 {
+        self.live_max as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        let mut lines = Vec::new();
+        let mut ranges = Vec::with_capacity(self.references.len());
+        for references in self.references.values().filter(|r| !r.is_empty()) {
+            for pair in references.windows(2) {
+                let span = pair[1] - pair[0] - 1;
+                self.spans += 1;
+                self.span_sum += span;
+                self.span_max = self.span_max.max(span);
+            }
+            lines.extend_from_slice(references);
+            ranges.push((references[0], references[references.len() - 1]));
+        }
+        self.references.clear();
+        self.statement = 0;
+
+        lines.sort_unstable();
+        lines.dedup();
+        for line in &lines {
+            let live = ranges
+                .iter()
+                .filter(|(first, last)| first <= line && line <= last)
+                .count();
+            self.live_sum += live;
+            self.live_max = self.live_max.max(live);
+        }
+        self.lines += lines.len();
+    }
+}
+
+pub trait Variables
+where
+    Self: Checker + Getter + NamingRules + LanguageInfo,
+{
+    fn compute(
+        node: &Node,
+        code: &[u8],
+        table: &HalsteadTable,
+        space_kind: SpaceKind,
+        stats: &mut Stats,
+    ) {
+        if Self::get_statement_kind(node).is_some() {
+            stats.statement += 1;
+        }
+
+        // The fields declared in the types are not variables
+        if !matches!(
+            space_kind,
+            SpaceKind::Class
+                | SpaceKind::Struct
+                | SpaceKind::Trait
+                | SpaceKind::Impl
+                | SpaceKind::Namespace
+                | SpaceKind::Interface
+        ) {
+            let mut identifiers = Vec::new();
+            Self::identifiers(node, code, &mut identifiers);
+            for (kind, identifier) in identifiers {
+                if !matches!(kind, IdentifierKind::Variable | IdentifierKind::Parameter) {
+                    continue;
+                }
+                if let Some(name) = identifier.utf8_text(code) {
+                    stats.references.entry(name.to_string()).or_default();
+                }
+            }
+        }
+
+        if table.get_op_type::<Self>(Self::get_lang(), node) != HalsteadType::Operand {
+            return;
+        }
+        let Some(references) = node
+            .utf8_text(code)
+            .and_then(|name| stats.references.get_mut(name))
+        else {
+            return;
+        };
+        // The references in the same statement are counted once
+        if references.last() != Some(&stats.statement) {
+            references.push(stats.statement);
+        }
+    }
+}
+
+implement_metric_trait!(
+    [Variables],
+    PythonCode,
+    MozjsCode,
+    JavascriptCode,
+    TypescriptCode,
+    TsxCode,
+    RustCode,
+    CppCode,
+    PreprocCode,
+    CcommentCode,
+    JavaCode,
+    KotlinCode
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::tools::check_metrics;
+
+    use super::*;
+
+    #[test]
+    fn python_variables() {
+        check_metrics::<PythonParser>(
+            "def f(a):
+                 b = a
+                 print(b)
+
+                 c = a + b
+                 return c",
+            "foo.py",
+            |metric| {
+                // The parameters are referenced before the first statement
+                // a: statements 0, 1, 3 -> spans 0, 1
+                // b: statements 1, 2, 3 -> spans 0, 0
+                // c: statements 3, 4 -> span 0
+                // live variables at statements 0, 1, 2, 3, 4: 1, 2, 2, 3, 1
+                insta::assert_json_snapshot!(
+                    metric.variables,
+                    @r###"
+                    {
+                      "span_average": 0.2,
+                      "span_max": 1.0,
+                      "live_average": 1.8,
+                      "live_max": 3.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_variables() {
+        check_metrics::<RustParser>(
+            "fn f(x: u32) -> u32 {
+                 let y = g(x);
+                 let z = Vec::new();
+                 println!(\"{}\", y);
+                 x + y
+             }",
+            "foo.rs",
+            |metric| {
+                // x: statements 0, 1, 4 -> spans 0, 2
+                // y: statements 1, 3, 4 -> spans 1, 0
+                // z: statement 2, and `g` and `Vec` are not variables
+                // live variables at statements 0, 1, 2, 3, 4: 1, 2, 3, 2, 2
+                insta::assert_json_snapshot!(
+                    metric.variables,
+                    @r###"
+                    {
+                      "span_average": 0.75,
+                      "span_max": 2.0,
+                      "live_average": 2.0,
+                      "live_max": 3.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_variables_per_space() {
+        check_metrics::<JavascriptParser>(
+            "function f(a) {
+                 const g = (b) => {
+                     return b;
+                 };
+                 return g(a);
+             }",
+            "foo.js",
+            |metric| {
+                // f: a at statements 0, 2 -> span 1, g at statements 1, 2 -> span 0
+                // the closure: b at statements 0, 1 -> span 0
+                // live variables at statements 0, 1, 2 of f: 1, 2, 2
+                // live variables at statements 0, 1 of the closure: 1, 1
+                insta::assert_json_snapshot!(
+                    metric.variables,
+                    @r###"
+                    {
+                      "span_average": 0.3333333333333333,
+                      "span_max": 1.0,
+                      "live_average": 1.4,
+                      "live_max": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_variables() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 int f(int a) {
+                     int b = a;
+                     System.out.println(b);
+                     return foo(a, b);
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                // a: statements 0, 1, 3 -> spans 0, 1
+                // b: statements 1, 2, 3 -> spans 0, 0
+                // System is not declared, so it is not a variable
+                // live variables at statements 0, 1, 2, 3: 1, 2, 2, 2
+                insta::assert_json_snapshot!(
+                    metric.variables,
+                    @r###"
+                    {
+                      "span_average": 0.25,
+                      "span_max": 1.0,
+                      "live_average": 1.75,
+                      "live_max": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn java_fields_are_not_variables() {
+        check_metrics::<JavaParser>(
+            "class A {
+                 int count;
+                 int f(int a) {
+                     count += a;
+                     return count;
+                 }
+             }",
+            "foo.java",
+            |metric| {
+                // a: statements 0, 1 -> span 0
+                // live variables at statements 0, 1: 1, 1
+                insta::assert_json_snapshot!(
+                    metric.variables,
+                    @r###"
+                    {
+                      "span_average": 0.0,
+                      "span_max": 0.0,
+                      "live_average": 1.0,
+                      "live_max": 1.0
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,1475 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::checker::Checker;
+use crate::error::Error;
+use crate::getter::Getter;
+use crate::langs::*;
+use crate::languages::*;
+use crate::node::Node;
+use crate::spaces::SpaceKind;
+
+use crate::dump_naming::*;
+use crate::traits::*;
+
+/// The kind of a declared identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdentifierKind {
+    /// The name of a function or a method
+    Function,
+    /// The name of a class, a structure, an interface or a type alias
+    Type,
+    /// The name of a variable or a field
+    Variable,
+    /// The name of a constant
+    Constant,
+    /// The name of a parameter
+    Parameter,
+}
+
+impl IdentifierKind {
+    /// Returns all the kinds of identifiers.
+    pub const fn all() -> &'static [&'static str] {
+        &["function", "type", "variable", "constant", "parameter"]
+    }
+}
+
+impl fmt::Display for IdentifierKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            IdentifierKind::Function => "function",
+            IdentifierKind::Type => "type",
+            IdentifierKind::Variable => "variable",
+            IdentifierKind::Constant => "constant",
+            IdentifierKind::Parameter => "parameter",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for IdentifierKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "function" => Ok(IdentifierKind::Function),
+            "type" => Ok(IdentifierKind::Type),
+            "variable" => Ok(IdentifierKind::Variable),
+            "constant" => Ok(IdentifierKind::Constant),
+            "parameter" => Ok(IdentifierKind::Parameter),
+            kind => Err(format!("{kind:?} is not a kind of identifier")),
+        }
+    }
+}
+
+/// A case convention of an identifier.
+///
+/// The leading and trailing underscores of an identifier,
+/// as in `_unused` or `__init__`, are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Case {
+    /// Lowercase words separated by underscores, as `snake_case`
+    #[serde(rename = "snake_case")]
+    Snake,
+    /// Capitalized words after a lowercase one, as `camelCase`
+    #[serde(rename = "camelCase")]
+    Camel,
+    /// Capitalized words, as `PascalCase`
+    #[serde(rename = "PascalCase")]
+    Pascal,
+    /// Uppercase words separated by underscores, as `SCREAMING_CASE`
+    #[serde(rename = "SCREAMING_CASE")]
+    Screaming,
+}
+
+impl Case {
+    /// Returns all the case conventions.
+    pub const fn all() -> &'static [&'static str] {
+        &["snake_case", "camelCase", "PascalCase", "SCREAMING_CASE"]
+    }
+
+    /// Checks whether a name follows this case convention.
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.trim_matches('_');
+        let mut chars = name.chars();
+        let Some(first) = chars.next() else {
+            return true;
+        };
+        match self {
+            Case::Snake => {
+                first.is_lowercase()
+                    && chars.all(|c| c.is_lowercase() || c.is_ascii_digit() || c == '_')
+            }
+            Case::Camel => first.is_lowercase() && chars.all(|c| c.is_alphanumeric()),
+            Case::Pascal => first.is_uppercase() && chars.all(|c| c.is_alphanumeric()),
+            Case::Screaming => {
+                first.is_uppercase()
+                    && chars.all(|c| c.is_uppercase() || c.is_ascii_digit() || c == '_')
+            }
+        }
+    }
+}
+
+impl fmt::Display for Case {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Case::Snake => "snake_case",
+            Case::Camel => "camelCase",
+            Case::Pascal => "PascalCase",
+            Case::Screaming => "SCREAMING_CASE",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for Case {
+    type Err = String;
+
+    fn from_str(case: &str) -> Result<Self, Self::Err> {
+        match case {
+            "snake_case" => Ok(Case::Snake),
+            "camelCase" => Ok(Case::Camel),
+            "PascalCase" => Ok(Case::Pascal),
+            "SCREAMING_CASE" => Ok(Case::Screaming),
+            case => Err(format!("{case:?} is not a supported case")),
+        }
+    }
+}
+
+/// The naming convention of a kind of identifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Convention {
+    /// The case of the identifiers, if any
+    pub case: Option<Case>,
+    /// The minimum length of the identifiers
+    pub min_length: usize,
+    /// The maximum length of the identifiers, if any
+    pub max_length: Option<usize>,
+}
+
+impl Convention {
+    fn new(case: Option<Case>) -> Self {
+        Self {
+            case,
+            min_length: 1,
+            max_length: None,
+        }
+    }
+}
+
+/// The naming conventions of the identifiers of a code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamingConventions {
+    /// The convention of functions and methods
+    pub functions: Convention,
+    /// The convention of classes, structures, interfaces and type aliases
+    pub types: Convention,
+    /// The convention of variables and fields
+    pub variables: Convention,
+    /// The convention of constants
+    pub constants: Convention,
+    /// The convention of parameters
+    pub parameters: Convention,
+}
+
+impl NamingConventions {
+    /// Returns the usual naming conventions of a language.
+    ///
+    /// No case is enforced for C/C++, since there is no
+    /// prevailing convention.
+    pub fn new(lang: LANG) -> Self {
+        let (functions, types, variables, constants) = match lang {
+            LANG::Rust | LANG::Python => (
+                Some(Case::Snake),
+                Some(Case::Pascal),
+                Some(Case::Snake),
+                Some(Case::Screaming),
+            ),
+            LANG::Mozjs
+            | LANG::Javascript
+            | LANG::Typescript
+            | LANG::Tsx
+            | LANG::Java
+            | LANG::Kotlin => (
+                Some(Case::Camel),
+                Some(Case::Pascal),
+                Some(Case::Camel),
+                Some(Case::Screaming),
+            ),
+            LANG::Cpp | LANG::Preproc | LANG::Ccomment => (None, None, None, None),
+        };
+        Self {
+            functions: Convention::new(functions),
+            types: Convention::new(types),
+            variables: Convention::new(variables),
+            constants: Convention::new(constants),
+            parameters: Convention::new(variables),
+        }
+    }
+
+    /// Returns the convention of a kind of identifiers.
+    pub fn get(&self, kind: IdentifierKind) -> &Convention {
+        match kind {
+            IdentifierKind::Function => &self.functions,
+            IdentifierKind::Type => &self.types,
+            IdentifierKind::Variable => &self.variables,
+            IdentifierKind::Constant => &self.constants,
+            IdentifierKind::Parameter => &self.parameters,
+        }
+    }
+
+    /// Returns the mutable convention of a kind of identifiers.
+    pub fn get_mut(&mut self, kind: IdentifierKind) -> &mut Convention {
+        match kind {
+            IdentifierKind::Function => &mut self.functions,
+            IdentifierKind::Type => &mut self.types,
+            IdentifierKind::Variable => &mut self.variables,
+            IdentifierKind::Constant => &mut self.constants,
+            IdentifierKind::Parameter => &mut self.parameters,
+        }
+    }
+}
+
+/// The naming rule broken by an identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// The identifier does not follow the expected case
+    Case(Case),
+    /// The identifier is shorter than the minimum length
+    MinLength(usize),
+    /// The identifier is longer than the maximum length
+    MaxLength(usize),
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::Case(case) => write!(f, "is not {case}"),
+            Rule::MinLength(length) => write!(f, "is shorter than {length} characters"),
+            Rule::MaxLength(length) => write!(f, "is longer than {length} characters"),
+        }
+    }
+}
+
+/// An identifier breaking a naming convention.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Violation {
+    /// The identifier
+    pub name: String,
+    /// The kind of the identifier
+    pub kind: IdentifierKind,
+    /// The line of the identifier
+    pub line: usize,
+    /// The column of the identifier
+    pub column: usize,
+    /// The broken rule
+    pub rule: Rule,
+}
+
+/// The naming of the identifiers declared in a space.
+#[derive(Debug, Clone, Serialize)]
+pub struct Naming {
+    /// The name of a function space.
+    ///
+    /// If `None`, an error is occurred in parsing
+    /// the name of a function space.
+    pub name: Option<String>,
+    /// The first line of a function space.
+    pub start_line: usize,
+    /// The last line of a function space.
+    pub end_line: usize,
+    /// The space kind.
+    pub kind: SpaceKind,
+    /// All subspaces contained in a function space.
+    pub spaces: Vec<Naming>,
+    /// The number of identifiers declared in the space,
+    /// excluding its subspaces.
+    pub identifiers: usize,
+    /// The average length of the identifiers declared in the space,
+    /// excluding its subspaces.
+    ///
+    /// If no identifier is declared, its value is `NAN`.
+    pub average_length: f64,
+    /// The identifiers of the space breaking a naming convention.
+    pub violations: Vec<Violation>,
+}
+
+impl Naming {
+    fn new<T: Getter>(node: &Node, code: &[u8], kind: SpaceKind) -> Self {
+        let (start_line, end_line) = match kind {
+            SpaceKind::Unit => {
+                if node.child_count() == 0 {
+                    (0, 0)
+                } else {
+                    (node.start_row() + 1, node.end_row())
+                }
+            }
+            _ => (node.start_row() + 1, node.end_row() + 1),
+        };
+        Self {
+            name: T::get_func_space_name(node, code).map(|name| name.to_string()),
+            start_line,
+            end_line,
+            kind,
+            spaces: Vec::new(),
+            identifiers: 0,
+            average_length: f64::NAN,
+            violations: Vec::new(),
+        }
+    }
+}
+
+/// The `Naming` metric.
+///
+/// This metric counts the identifiers declared in a space, their
+/// average length and the identifiers breaking a naming convention,
+/// checked against the [`MetricsOptions::naming`] conventions.
+///
+/// [`MetricsOptions::naming`]: struct.MetricsOptions.html#structfield.naming
+#[derive(Clone, Debug, Default)]
+pub struct NamingStats {
+    identifiers: usize,
+    lengths: usize,
+    violations: usize,
+    identifiers_sum: usize,
+    lengths_sum: usize,
+    violations_sum: usize,
+}
+
+impl Serialize for NamingStats {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("naming", 3)?;
+        st.serialize_field("identifiers", &self.identifiers_sum())?;
+        st.serialize_field("average_length", &self.average_length_sum())?;
+        st.serialize_field("violations", &self.violations_sum())?;
+        st.end()
+    }
+}
+
+impl fmt::Display for NamingStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "identifiers: {}, average_length: {}, violations: {}",
+            self.identifiers_sum(),
+            self.average_length_sum(),
+            self.violations_sum()
+        )
+    }
+}
+
+impl NamingStats {
+    /// Merges a second `Naming` metric into the first one
+    pub fn merge(&mut self, other: &NamingStats) {
+        self.identifiers_sum += other.identifiers_sum;
+        self.lengths_sum += other.lengths_sum;
+        self.violations_sum += other.violations_sum;
+    }
+
+    /// Returns the number of identifiers declared in a space
+    #[inline(always)]
+    pub fn identifiers(&self) -> f64 {
+        self.identifiers as f64
+    }
+
+    /// Returns the average length of the identifiers declared in a space
+    ///
+    /// If no identifier is declared, its value is `NAN`.
+    #[inline(always)]
+    pub fn average_length(&self) -> f64 {
+        self.lengths as f64 / self.identifiers as f64
+    }
+
+    /// Returns the number of violations of the naming conventions
+    /// in a space
+    #[inline(always)]
+    pub fn violations(&self) -> f64 {
+        self.violations as f64
+    }
+
+    /// Returns the number of identifiers declared
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn identifiers_sum(&self) -> f64 {
+        self.identifiers_sum as f64
+    }
+
+    /// Returns the average length of the identifiers declared
+    /// in a space and its subspaces
+    ///
+    /// If no identifier is declared, its value is `NAN`.
+    #[inline(always)]
+    pub fn average_length_sum(&self) -> f64 {
+        self.lengths_sum as f64 / self.identifiers_sum as f64
+    }
+
+    /// Returns the number of violations of the naming conventions
+    /// in a space and its subspaces
+    #[inline(always)]
+    pub fn violations_sum(&self) -> f64 {
+        self.violations_sum as f64
+    }
+
+    #[inline(always)]
+    pub(crate) fn compute_sum(&mut self) {
+        self.identifiers_sum += self.identifiers;
+        self.lengths_sum += self.lengths;
+        self.violations_sum += self.violations;
+    }
+}
+
+pub trait NamingRules
+where
+    Self: Checker,
+{
+    /// Adds the identifiers declared by a node to `identifiers`
+    fn identifiers<'a>(
+        _node: &Node<'a>,
+        _code: &[u8],
+        _identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+    ) {
+    }
+
+    /// Checks the identifiers declared by a node of a space.
+    ///
+    /// When the node is the space itself, its name is declared
+    /// in the parent space, if any, and its other identifiers
+    /// in the space, if any.
+    fn compute(
+        node: &Node,
+        code: &[u8],
+        conventions: &NamingConventions,
+        is_space: bool,
+        mut stats: Option<&mut NamingStats>,
+        mut parent: Option<&mut NamingStats>,
+    ) {
+        let mut identifiers = Vec::new();
+        Self::identifiers(node, code, &mut identifiers);
+        for (kind, identifier) in identifiers {
+            let Some(name) = identifier.utf8_text(code) else {
+                continue;
+            };
+            let is_name = matches!(kind, IdentifierKind::Function | IdentifierKind::Type);
+            let stats = if is_space && is_name {
+                parent.as_deref_mut()
+            } else {
+                stats.as_deref_mut()
+            };
+            if let Some(stats) = stats {
+                stats.identifiers += 1;
+                stats.lengths += name.chars().count();
+                stats.violations += broken_rules(kind, name, conventions).len();
+            }
+        }
+    }
+}
+
+// Adds the identifiers bound by a pattern, as `(a, b)`,
+// descending only into the nodes accepted by `is_pattern`
+fn add_bindings<'a>(
+    node: Node<'a>,
+    kind: IdentifierKind,
+    identifier: u16,
+    is_pattern: fn(u16) -> bool,
+    identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+) {
+    if node.kind_id() == identifier {
+        identifiers.push((kind, node));
+    } else if is_pattern(node.kind_id()) {
+        for child in node.children() {
+            add_bindings(child, kind, identifier, is_pattern, identifiers);
+        }
+    }
+}
+
+// Adds the identifier contained in the `name` field of a node
+#[inline(always)]
+fn add_name<'a>(
+    node: &Node<'a>,
+    kind: IdentifierKind,
+    identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+) {
+    if let Some(name) = node.child_by_field_name("name") {
+        identifiers.push((kind, name));
+    }
+}
+
+impl NamingRules for RustCode {
+    fn identifiers<'a>(
+        node: &Node<'a>,
+        _code: &[u8],
+        identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+    ) {
+        fn is_pattern(id: u16) -> bool {
+            matches!(
+                id.into(),
+                Rust::TuplePattern
+                    | Rust::SlicePattern
+                    | Rust::RefPattern
+                    | Rust::MutPattern
+                    | Rust::ReferencePattern
+            )
+        }
+
+        let bindings = |kind, pattern: Option<Node<'a>>, identifiers: &mut Vec<_>| {
+            if let Some(pattern) = pattern {
+                add_bindings(
+                    pattern,
+                    kind,
+                    Rust::Identifier as u16,
+                    is_pattern,
+                    identifiers,
+                );
+            }
+        };
+
+        match node.kind_id().into() {
+            Rust::FunctionItem | Rust::FunctionSignatureItem => {
+                add_name(node, IdentifierKind::Function, identifiers);
+            }
+            Rust::StructItem
+            | Rust::EnumItem
+            | Rust::UnionItem
+            | Rust::TraitItem
+            | Rust::TypeItem => {
+                add_name(node, IdentifierKind::Type, identifiers);
+            }
+            Rust::ConstItem | Rust::StaticItem => {
+                add_name(node, IdentifierKind::Constant, identifiers);
+            }
+            Rust::LetDeclaration => {
+                bindings(
+                    IdentifierKind::Variable,
+                    node.child_by_field_name("pattern"),
+                    identifiers,
+                );
+            }
+            Rust::Parameter => {
+                bindings(
+                    IdentifierKind::Parameter,
+                    node.child_by_field_name("pattern"),
+                    identifiers,
+                );
+            }
+            Rust::ClosureParameters => {
+                for child in node.children() {
+                    bindings(IdentifierKind::Parameter, Some(child), identifiers);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl NamingRules for PythonCode {
+    fn identifiers<'a>(
+        node: &Node<'a>,
+        code: &[u8],
+        identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+    ) {
+        fn is_pattern(id: u16) -> bool {
+            matches!(
+                id.into(),
+                Python::PatternList
+                    | Python::TuplePattern
+                    | Python::TuplePattern2
+                    | Python::ListPattern
+                    | Python::ListPattern2
+                    | Python::ListSplatPattern
+                    | Python::DictionarySplatPattern
+            )
+        }
+
+        match node.kind_id().into() {
+            Python::FunctionDefinition => {
+                add_name(node, IdentifierKind::Function, identifiers);
+            }
+            Python::ClassDefinition => {
+                add_name(node, IdentifierKind::Type, identifiers);
+            }
+            Python::Assignment => {
+                let Some(left) = node.child_by_field_name("left") else {
+                    return;
+                };
+                // Python has no constants, so the upper case names assigned
+                // outside of a function are considered as constants
+                let mut in_function = false;
+                let mut parent = node.parent();
+                while let Some(p) = parent.filter(|_| !in_function) {
+                    in_function = p.kind_id() == Python::FunctionDefinition;
+                    parent = p.parent();
+                }
+                let kind = if !in_function
+                    && left.kind_id() == Python::Identifier
+                    && left
+                        .utf8_text(code)
+                        .is_some_and(|name| Case::Screaming.matches(name))
+                {
+                    IdentifierKind::Constant
+                } else {
+                    IdentifierKind::Variable
+                };
+                add_bindings(
+                    left,
+                    kind,
+                    Python::Identifier as u16,
+                    is_pattern,
+                    identifiers,
+                );
+            }
+            Python::Parameters | Python::LambdaParameters => {
+                for child in node.children() {
+                    let param = match child.kind_id().into() {
+                        Python::DefaultParameter | Python::TypedDefaultParameter => {
+                            child.child_by_field_name("name")
+                        }
+                        Python::TypedParameter => child.child(0),
+                        _ => Some(child),
+                    };
+                    if let Some(param) = param {
+                        add_bindings(
+                            param,
+                            IdentifierKind::Parameter,
+                            Python::Identifier as u16,
+                            is_pattern,
+                            identifiers,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl NamingRules for JavaCode {
+    fn identifiers<'a>(
+        node: &Node<'a>,
+        _code: &[u8],
+        identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+    ) {
+        match node.kind_id().into() {
+            Java::MethodDeclaration => {
+                add_name(node, IdentifierKind::Function, identifiers);
+            }
+            Java::ClassDeclaration
+            | Java::InterfaceDeclaration
+            | Java::EnumDeclaration
+            | Java::RecordDeclaration
+            | Java::AnnotationTypeDeclaration => {
+                add_name(node, IdentifierKind::Type, identifiers);
+            }
+            Java::EnumConstant => {
+                add_name(node, IdentifierKind::Constant, identifiers);
+            }
+            Java::FieldDeclaration | Java::LocalVariableDeclaration => {
+                // The `static final` fields are constants
+                let is_constant =
+                    node.first_child(|id| id == Java::Modifiers)
+                        .is_some_and(|modifiers| {
+                            modifiers.first_child(|id| id == Java::Static).is_some()
+                                && modifiers.first_child(|id| id == Java::Final).is_some()
+                        });
+                let kind = if is_constant {
+                    IdentifierKind::Constant
+                } else {
+                    IdentifierKind::Variable
+                };
+                for declarator in node
+                    .children()
+                    .filter(|child| child.kind_id() == Java::VariableDeclarator)
+                {
+                    add_name(&declarator, kind, identifiers);
+                }
+            }
+            Java::FormalParameter => {
+                add_name(node, IdentifierKind::Parameter, identifiers);
+            }
+            Java::SpreadParameter => {
+                if let Some(declarator) = node.first_child(|id| id == Java::VariableDeclarator) {
+                    add_name(&declarator, IdentifierKind::Parameter, identifiers);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl NamingRules for KotlinCode {
+    fn identifiers<'a>(
+        node: &Node<'a>,
+        _code: &[u8],
+        identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+    ) {
+        let first_identifier = |node: &Node<'a>| node.first_child(|id| id == Kotlin::Identifier);
+
+        match node.kind_id().into() {
+            Kotlin::FunctionDeclaration => {
+                add_name(node, IdentifierKind::Function, identifiers);
+            }
+            Kotlin::ClassDeclaration | Kotlin::ObjectDeclaration => {
+                add_name(node, IdentifierKind::Type, identifiers);
+            }
+            Kotlin::TypeAlias => {
+                if let Some(name) = first_identifier(node) {
+                    identifiers.push((IdentifierKind::Type, name));
+                }
+            }
+            Kotlin::EnumEntry => {
+                if let Some(name) = first_identifier(node) {
+                    identifiers.push((IdentifierKind::Constant, name));
+                }
+            }
+            Kotlin::PropertyDeclaration => {
+                // The `const val` properties are constants
+                let is_constant = node
+                    .first_child(|id| id == Kotlin::Modifiers)
+                    .and_then(|modifiers| {
+                        modifiers.first_child(|id| id == Kotlin::PropertyModifier)
+                    })
+                    .is_some_and(|modifier| {
+                        modifier.first_child(|id| id == Kotlin::Const).is_some()
+                    });
+                let kind = if is_constant {
+                    IdentifierKind::Constant
+                } else {
+                    IdentifierKind::Variable
+                };
+                for child in node.children() {
+                    let declarations: Vec<_> = match child.kind_id().into() {
+                        Kotlin::VariableDeclaration => vec![child],
+                        Kotlin::MultiVariableDeclaration => child.children().collect(),
+                        _ => continue,
+                    };
+                    for name in declarations.iter().filter_map(first_identifier) {
+                        identifiers.push((kind, name));
+                    }
+                }
+            }
+            Kotlin::Parameter | Kotlin::ClassParameter => {
+                if let Some(name) = first_identifier(node) {
+                    identifiers.push((IdentifierKind::Parameter, name));
+                }
+            }
+            Kotlin::LambdaParameters => {
+                for name in node
+                    .children()
+                    .filter(|child| child.kind_id() == Kotlin::VariableDeclaration)
+                    .filter_map(|child| first_identifier(&child))
+                {
+                    identifiers.push((IdentifierKind::Parameter, name));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl NamingRules for CppCode {
+    fn identifiers<'a>(
+        node: &Node<'a>,
+        code: &[u8],
+        identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+    ) {
+        // The name of a declarator, as `a` in `*a[3]` or `f` in `A::f()`
+        fn declarator_name<'a>(node: Node<'a>) -> Option<Node<'a>> {
+            let mut node = node;
+            while let Some(declarator) = node.child_by_field_name("declarator") {
+                node = declarator;
+            }
+            match node.kind_id().into() {
+                Cpp::QualifiedIdentifier
+                | Cpp::QualifiedIdentifier2
+                | Cpp::QualifiedIdentifier3
+                | Cpp::QualifiedIdentifier4 => node.child_by_field_name("name"),
+                Cpp::Identifier | Cpp::FieldIdentifier => Some(node),
+                _ => None,
+            }
+        }
+
+        match node.kind_id().into() {
+            Cpp::FunctionDefinition
+            | Cpp::FunctionDefinition2
+            | Cpp::FunctionDefinition3
+            | Cpp::FunctionDefinition4 => {
+                if let Some(name) = node
+                    .child_by_field_name("declarator")
+                    .and_then(declarator_name)
+                {
+                    identifiers.push((IdentifierKind::Function, name));
+                }
+            }
+            // Only the definitions, not the uses as `struct A a;`
+            Cpp::ClassSpecifier | Cpp::StructSpecifier | Cpp::UnionSpecifier
+                if node.child_by_field_name("body").is_some() =>
+            {
+                add_name(node, IdentifierKind::Type, identifiers);
+            }
+            Cpp::Declaration
+            | Cpp::Declaration2
+            | Cpp::Declaration3
+            | Cpp::Declaration4
+            | Cpp::FieldDeclaration => {
+                let is_constant = node.children().any(|child| {
+                    child.kind_id() == Cpp::TypeQualifier
+                        && child
+                            .utf8_text(code)
+                            .is_some_and(|text| matches!(text, "const" | "constexpr"))
+                });
+                let kind = if is_constant {
+                    IdentifierKind::Constant
+                } else {
+                    IdentifierKind::Variable
+                };
+                for declarator in node.children_by_field_name("declarator") {
+                    // A declaration of a function is not a variable
+                    let is_function = matches!(
+                        declarator.kind_id().into(),
+                        Cpp::FunctionDeclarator
+                            | Cpp::FunctionDeclarator2
+                            | Cpp::FunctionDeclarator3
+                    );
+                    if let Some(name) = declarator_name(declarator).filter(|_| !is_function) {
+                        identifiers.push((kind, name));
+                    }
+                }
+            }
+            Cpp::ParameterDeclaration | Cpp::OptionalParameterDeclaration => {
+                if let Some(name) = node
+                    .child_by_field_name("declarator")
+                    .and_then(declarator_name)
+                {
+                    identifiers.push((IdentifierKind::Parameter, name));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// The JavaScript family shares the same grammar for declarations,
+// except for the declarations of types
+macro_rules! js_naming {
+    ($code:ident, $lang:ident, [$($types:ident)|+]) => {
+        impl NamingRules for $code {
+            fn identifiers<'a>(
+                node: &Node<'a>,
+                _code: &[u8],
+                identifiers: &mut Vec<(IdentifierKind, Node<'a>)>,
+            ) {
+                match node.kind_id().into() {
+                    $lang::FunctionDeclaration
+                    | $lang::GeneratorFunctionDeclaration
+                    | $lang::MethodDefinition => {
+                        add_name(node, IdentifierKind::Function, identifiers);
+                    }
+                    $($lang::$types)|+ => {
+                        add_name(node, IdentifierKind::Type, identifiers);
+                    }
+                    $lang::VariableDeclarator => {
+                        let Some(name) = node
+                            .child_by_field_name("name")
+                            .filter(|name| name.kind_id() == $lang::Identifier)
+                        else {
+                            return;
+                        };
+                        // A `const` initialized by a literal is a constant
+                        let is_const = node.parent().and_then(|parent| parent.child(0)).is_some_and(
+                            |keyword| keyword.kind_id() == $lang::Const,
+                        );
+                        let is_literal = node.child_by_field_name("value").is_some_and(|value| {
+                            matches!(
+                                value.kind_id().into(),
+                                $lang::Number | $lang::String | $lang::True | $lang::False
+                            )
+                        });
+                        let kind = if is_const && is_literal {
+                            IdentifierKind::Constant
+                        } else {
+                            IdentifierKind::Variable
+                        };
+                        identifiers.push((kind, name));
+                    }
+                    $lang::FormalParameters => {
+                        for child in node.children().filter(|child| child.is_named()) {
+                            // Looks for the identifier in a parameter,
+                            // as `...a`, `a = 1` or `a?: number`
+                            let mut param = Some(child);
+                            while let Some(p) = param {
+                                if p.kind_id() == $lang::Identifier {
+                                    identifiers.push((IdentifierKind::Parameter, p));
+                                    break;
+                                }
+                                param = if p.kind_id() == $lang::RestPattern {
+                                    p.child(1)
+                                } else {
+                                    p.child_by_field_name("pattern")
+                                        .or_else(|| p.child_by_field_name("left"))
+                                };
+                            }
+                        }
+                    }
+                    $lang::ArrowFunction => {
+                        if let Some(param) = node
+                            .child_by_field_name("parameter")
+                            .filter(|param| param.kind_id() == $lang::Identifier)
+                        {
+                            identifiers.push((IdentifierKind::Parameter, param));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    };
+}
+
+js_naming!(MozjsCode, Mozjs, [ClassDeclaration]);
+js_naming!(JavascriptCode, Javascript, [ClassDeclaration]);
+js_naming!(
+    TypescriptCode,
+    Typescript,
+    [ClassDeclaration
+        | AbstractClassDeclaration
+        | InterfaceDeclaration
+        | TypeAliasDeclaration
+        | EnumDeclaration]
+);
+js_naming!(
+    TsxCode,
+    Tsx,
+    [ClassDeclaration
+        | AbstractClassDeclaration
+        | InterfaceDeclaration
+        | TypeAliasDeclaration
+        | EnumDeclaration]
+);
+
+impl NamingRules for PreprocCode {}
+impl NamingRules for CcommentCode {}
+
+// Returns the rules of its convention broken by an identifier
+fn broken_rules(kind: IdentifierKind, name: &str, conventions: &NamingConventions) -> Vec<Rule> {
+    let convention = conventions.get(kind);
+    let length = name.chars().count();
+
+    let mut rules = Vec::new();
+    if let Some(case) = convention.case.filter(|case| !case.matches(name)) {
+        rules.push(Rule::Case(case));
+    }
+    if length < convention.min_length {
+        rules.push(Rule::MinLength(convention.min_length));
+    }
+    if let Some(max_length) = convention.max_length.filter(|max| length > *max) {
+        rules.push(Rule::MaxLength(max_length));
+    }
+    rules
+}
+
+fn check(
+    kind: IdentifierKind,
+    node: &Node,
+    code: &[u8],
+    conventions: &NamingConventions,
+    space: &mut Naming,
+    lengths: &mut usize,
+) {
+    let Some(name) = node.utf8_text(code) else {
+        return;
+    };
+    let (row, column) = node.start_position();
+
+    space.identifiers += 1;
+    *lengths += name.chars().count();
+    space.violations.extend(
+        broken_rules(kind, name, conventions)
+            .into_iter()
+            .map(|rule| Violation {
+                name: name.to_string(),
+                kind,
+                line: row + 1,
+                column: column + 1,
+                rule,
+            }),
+    );
+}
+
+/// Checks the names of all identifiers declared in a code
+/// against some naming conventions.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{naming, NamingConventions, ParserTrait, RustParser, LANG};
+///
+/// let source_code = "fn doIt(x: u8) {}";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Check the names against the usual conventions of Rust
+/// let conventions = NamingConventions::new(LANG::Rust);
+/// let naming = naming(&parser, &path, &conventions).unwrap();
+///
+/// assert_eq!(naming.violations[0].name, "doIt");
+/// ```
+pub fn naming<T: ParserTrait>(
+    parser: &T,
+    path: &Path,
+    conventions: &NamingConventions,
+) -> Result<Naming, Error> {
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let mut cursor = root.cursor();
+    let mut stack = vec![(root, None)];
+    let mut children = Vec::new();
+    let mut identifiers = Vec::new();
+    // The spaces in order of appearance, with their parent and the sum
+    // of the lengths of their identifiers
+    let mut spaces: Vec<(Naming, Option<usize>, usize)> = Vec::new();
+
+    while let Some((node, parent)) = stack.pop() {
+        // The root is a space even for the languages without spaces
+        let is_space =
+            node == root || T::Checker::is_func(&node) || T::Checker::is_func_space(&node);
+        let space = if is_space {
+            let kind = T::Getter::get_space_kind(&node);
+            spaces.push((Naming::new::<T::Getter>(&node, code, kind), parent, 0));
+            Some(spaces.len() - 1)
+        } else {
+            parent
+        };
+
+        if let Some(index) = space {
+            T::NamingRules::identifiers(&node, code, &mut identifiers);
+            for (kind, identifier) in identifiers.drain(..) {
+                // The name of a function or a class is declared
+                // in the space containing it
+                let is_name = matches!(kind, IdentifierKind::Function | IdentifierKind::Type);
+                let index = parent.filter(|_| is_space && is_name).unwrap_or(index);
+                let (naming, _, lengths) = &mut spaces[index];
+                check(kind, &identifier, code, conventions, naming, lengths);
+            }
+        }
+
+        cursor.reset(&node);
+        if cursor.goto_first_child() {
+            loop {
+                children.push((cursor.node(), space));
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+            for child in children.drain(..).rev() {
+                stack.push(child);
+            }
+        }
+    }
+
+    // Subspaces always follow their parent, which is the root one
+    // for the first spaces, so they can be moved into it going backwards
+    while spaces.len() > 1 {
+        let Some((mut naming, parent, lengths)) = spaces.pop() else {
+            break;
+        };
+        naming.average_length = lengths as f64 / naming.identifiers as f64;
+        naming.spaces.reverse();
+        spaces[parent.unwrap_or_default()].0.spaces.push(naming);
+    }
+
+    let (mut naming, _, lengths) = spaces
+        .pop()
+        .ok_or_else(|| Error::Parse(path.to_path_buf()))?;
+    naming.average_length = lengths as f64 / naming.identifiers as f64;
+    naming.spaces.reverse();
+    naming.name = path.to_str().map(|name| name.to_string());
+    Ok(naming)
+}
+
+/// Configuration options for checking the names of
+/// the identifiers declared in a code.
+#[derive(Debug)]
+pub struct NamingCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+    /// The naming conventions to check
+    pub conventions: NamingConventions,
+}
+
+pub struct NamingCode {
+    _guard: (),
+}
+
+impl Callback for NamingCode {
+    type Res = Result<(), Error>;
+    type Cfg = NamingCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        let naming = naming(parser, &cfg.path, &cfg.conventions)?;
+        Ok(dump_naming(&naming)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::tools::check_func_space;
+    use crate::{JavaParser, KotlinParser, PythonParser, RustParser, TypescriptParser};
+
+    use super::*;
+
+    fn violations<T: ParserTrait>(
+        source: &str,
+        file: &str,
+        conventions: &NamingConventions,
+    ) -> (Naming, Vec<(String, IdentifierKind, Rule)>) {
+        let path = PathBuf::from(file);
+        let parser = T::new(source.as_bytes().to_vec(), &path, None).unwrap();
+        let naming = naming(&parser, &path, conventions).unwrap();
+        let mut violations = Vec::new();
+        let mut spaces = vec![&naming];
+        while let Some(space) = spaces.pop() {
+            violations.extend(
+                space
+                    .violations
+                    .iter()
+                    .map(|v| (v.name.clone(), v.kind, v.rule)),
+            );
+            spaces.extend(space.spaces.iter().rev());
+        }
+        (naming, violations)
+    }
+
+    #[test]
+    fn case_matches() {
+        assert!(Case::Snake.matches("snake_case_2"));
+        assert!(Case::Snake.matches("__init__"));
+        assert!(!Case::Snake.matches("camelCase"));
+        assert!(Case::Camel.matches("camelCase2"));
+        assert!(!Case::Camel.matches("camel_case"));
+        assert!(Case::Pascal.matches("HttpServer"));
+        assert!(!Case::Pascal.matches("httpServer"));
+        assert!(Case::Screaming.matches("MAX_SIZE"));
+        assert!(!Case::Screaming.matches("Max_Size"));
+    }
+
+    #[test]
+    fn rust_naming() {
+        let (naming, violations) = violations::<RustParser>(
+            "const maxSize: u8 = 1;
+             struct my_struct;
+             fn doIt(firstArg: u8, (a, B): (u8, u8)) {
+                 let mut total_sum = 1;
+                 let f = |P| P;
+             }",
+            "foo.rs",
+            &NamingConventions::new(LANG::Rust),
+        );
+        assert_eq!(
+            violations,
+            [
+                (
+                    "maxSize".to_string(),
+                    IdentifierKind::Constant,
+                    Rule::Case(Case::Screaming)
+                ),
+                (
+                    "my_struct".to_string(),
+                    IdentifierKind::Type,
+                    Rule::Case(Case::Pascal)
+                ),
+                (
+                    "doIt".to_string(),
+                    IdentifierKind::Function,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "firstArg".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "B".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "P".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Snake)
+                ),
+            ]
+        );
+        // `maxSize`, `my_struct` and `doIt` are declared in the unit space
+        assert_eq!(naming.identifiers, 3);
+        assert_eq!(naming.average_length, 20. / 3.);
+        // `firstArg`, `a`, `B`, `total_sum` and `f` are declared in `doIt`
+        assert_eq!(naming.spaces[0].identifiers, 5);
+        assert_eq!(naming.spaces[0].spaces[0].identifiers, 1);
+    }
+
+    #[test]
+    fn python_naming() {
+        let (naming, violations) = violations::<PythonParser>(
+            "MAX_SIZE = 3
+class my_class:
+    def doIt(self, firstArg, b=1, *Args):
+        MAX_SIZE = 1
+        x, Y = 1, 2",
+            "foo.py",
+            &NamingConventions::new(LANG::Python),
+        );
+        assert_eq!(
+            violations,
+            [
+                (
+                    "my_class".to_string(),
+                    IdentifierKind::Type,
+                    Rule::Case(Case::Pascal)
+                ),
+                (
+                    "doIt".to_string(),
+                    IdentifierKind::Function,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "firstArg".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "Args".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "MAX_SIZE".to_string(),
+                    IdentifierKind::Variable,
+                    Rule::Case(Case::Snake)
+                ),
+                (
+                    "Y".to_string(),
+                    IdentifierKind::Variable,
+                    Rule::Case(Case::Snake)
+                ),
+            ]
+        );
+        // `MAX_SIZE` and `my_class` are declared in the unit space
+        assert_eq!(naming.identifiers, 2);
+    }
+
+    #[test]
+    fn java_naming() {
+        let (_, violations) = violations::<JavaParser>(
+            "class foo {
+                 static final int max = 1;
+                 int Count;
+                 enum Color { RED, green }
+                 void do_it(int firstArg, String... Rest) {
+                     int total = 1, Other;
+                 }
+             }",
+            "Foo.java",
+            &NamingConventions::new(LANG::Java),
+        );
+        assert_eq!(
+            violations,
+            [
+                (
+                    "foo".to_string(),
+                    IdentifierKind::Type,
+                    Rule::Case(Case::Pascal)
+                ),
+                (
+                    "max".to_string(),
+                    IdentifierKind::Constant,
+                    Rule::Case(Case::Screaming)
+                ),
+                (
+                    "Count".to_string(),
+                    IdentifierKind::Variable,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "green".to_string(),
+                    IdentifierKind::Constant,
+                    Rule::Case(Case::Screaming)
+                ),
+                (
+                    "do_it".to_string(),
+                    IdentifierKind::Function,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "Rest".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "Other".to_string(),
+                    IdentifierKind::Variable,
+                    Rule::Case(Case::Camel)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_naming() {
+        let (_, violations) = violations::<TypescriptParser>(
+            "const maxSize = 3, Other = f();
+             interface foo {}
+             function run_it(a, B = 2, ...Rest) {
+                 const y = (Q) => Q;
+             }",
+            "foo.ts",
+            &NamingConventions::new(LANG::Typescript),
+        );
+        assert_eq!(
+            violations,
+            [
+                (
+                    "maxSize".to_string(),
+                    IdentifierKind::Constant,
+                    Rule::Case(Case::Screaming)
+                ),
+                (
+                    "Other".to_string(),
+                    IdentifierKind::Variable,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "foo".to_string(),
+                    IdentifierKind::Type,
+                    Rule::Case(Case::Pascal)
+                ),
+                (
+                    "run_it".to_string(),
+                    IdentifierKind::Function,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "B".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "Rest".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "Q".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Camel)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn naming_lengths() {
+        let mut conventions = NamingConventions::new(LANG::Rust);
+        conventions.variables.min_length = 2;
+        conventions.functions.max_length = Some(5);
+        let (_, violations) = violations::<RustParser>(
+            "fn compute() {
+                 let x = 1;
+                 let xy = 2;
+             }",
+            "foo.rs",
+            &conventions,
+        );
+        assert_eq!(
+            violations,
+            [
+                (
+                    "compute".to_string(),
+                    IdentifierKind::Function,
+                    Rule::MaxLength(5)
+                ),
+                (
+                    "x".to_string(),
+                    IdentifierKind::Variable,
+                    Rule::MinLength(2)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn kotlin_naming() {
+        let (naming, violations) = violations::<KotlinParser>(
+            "fun do_it(Value: Int) {}
+             class point {}",
+            "foo.kt",
+            &NamingConventions::new(LANG::Kotlin),
+        );
+        assert_eq!(naming.name.as_deref(), Some("foo.kt"));
+        assert_eq!(
+            violations,
+            [
+                (
+                    "do_it".to_string(),
+                    IdentifierKind::Function,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "Value".to_string(),
+                    IdentifierKind::Parameter,
+                    Rule::Case(Case::Camel)
+                ),
+                (
+                    "point".to_string(),
+                    IdentifierKind::Type,
+                    Rule::Case(Case::Pascal)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn naming_metric() {
+        check_func_space::<RustParser, _>(
+            "const max: usize = 1;
+             fn compute(x: usize) -> usize {
+                 let FooBar = x;
+                 FooBar
+             }",
+            "foo.rs",
+            |space| {
+                // The function name is declared in the unit space
+                assert_eq!(space.metrics.naming.identifiers(), 2.);
+                assert_eq!(space.metrics.naming.violations(), 1.);
+                let function = &space.spaces[0];
+                assert_eq!(function.metrics.naming.identifiers(), 2.);
+                assert_eq!(function.metrics.naming.average_length(), 3.5);
+                assert_eq!(function.metrics.naming.violations(), 1.);
+                insta::assert_json_snapshot!(
+                    space.metrics.naming,
+                    @r###"
+                    {
+                      "identifiers": 4.0,
+                      "average_length": 4.25,
+                      "violations": 2.0
+                    }"###
+                );
+            },
+        );
+    }
+}
@@ -1,31 +1,122 @@
 use tree_sitter::Node as OtherNode;
 use tree_sitter::Tree as OtherTree;
-use tree_sitter::{Parser, TreeCursor};
+use tree_sitter::{InputEdit, Parser, Point, TreeCursor};
 
 use crate::checker::Checker;
+use crate::langs::LANG;
 use crate::traits::{LanguageInfo, Search};
 
 #[derive(Clone, Debug)]
 pub(crate) struct Tree(OtherTree);
 
 impl Tree {
-    pub(crate) fn new<T: LanguageInfo>(code: &[u8]) -> Self {
+    // Parses a code, returning `None` if the parser fails
+    pub(crate) fn new<T: LanguageInfo>(code: &[u8]) -> Option<Self> {
+        Self::with_language(code, T::get_lang())
+    }
+
+    pub(crate) fn with_language(code: &[u8], lang: LANG) -> Option<Self> {
         let mut parser = Parser::new();
-        parser
-            .set_language(&T::get_lang().get_ts_language())
-            .unwrap();
+        parser.set_language(&lang.get_ts_language()).ok()?;
 
-        Self(parser.parse(code, None).unwrap())
+        parser.parse(code, None).map(Self)
     }
 
     pub(crate) fn get_root(&self) -> Node {
         Node(self.0.root_node())
     }
+
+    // Replaces the bytes `start_byte..old_end_byte` of the code by a text
+    // and updates the tree accordingly
+    pub(crate) fn edit(
+        &mut self,
+        code: &mut Vec<u8>,
+        start_byte: usize,
+        old_end_byte: usize,
+        text: &[u8],
+    ) {
+        let start_position = get_point(code, start_byte);
+        let old_end_position = get_point(code, old_end_byte);
+        code.splice(start_byte..old_end_byte, text.iter().copied());
+        let new_end_byte = start_byte + text.len();
+
+        self.0.edit(&InputEdit {
+            start_byte,
+            old_end_byte,
+            new_end_byte,
+            start_position,
+            old_end_position,
+            new_end_position: get_point(code, new_end_byte),
+        });
+    }
+
+    // Parses the code again, reusing the unchanged nodes of an edited tree
+    pub(crate) fn reparse<T: LanguageInfo>(&self, code: &[u8]) -> Option<Self> {
+        let mut parser = Parser::new();
+        parser.set_language(&T::get_lang().get_ts_language()).ok()?;
+
+        parser.parse(code, Some(&self.0)).map(Self)
+    }
+
+    // Returns the byte ranges whose syntactic structure differs in a new tree
+    pub(crate) fn changed_ranges(&self, new: &Tree) -> Vec<(usize, usize)> {
+        self.0
+            .changed_ranges(&new.0)
+            .map(|range| (range.start_byte, range.end_byte))
+            .collect()
+    }
+}
+
+// Returns the row and the column of a byte of the code
+fn get_point(code: &[u8], byte: usize) -> Point {
+    let before = &code[..byte];
+    let row = before.iter().filter(|c| **c == b'\n').count();
+    let line_start = before
+        .iter()
+        .rposition(|c| *c == b'\n')
+        .map_or(0, |pos| pos + 1);
+    Point::new(row, byte - line_start)
 }
 
 /// An `AST` node.
-#[derive(Clone, Copy, Debug)]
-pub struct Node<'a>(OtherNode<'a>);
+///
+/// A node can be inspected and the tree containing it walked, through
+/// its parent, its children and its siblings, or through the iterators
+/// returned by [`preorder`] and [`postorder`].
+///
+/// The rows and the columns of a node start from 0, the columns
+/// and the offsets being counted in bytes.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{ParserTrait, RustParser};
+///
+/// let source_code = "fn f(a: u32) {}";
+/// let parser = RustParser::new(source_code.as_bytes().to_vec(), Path::new("foo.rs"), None).unwrap();
+/// let root = parser.get_root();
+///
+/// let function = root.child(0).unwrap();
+/// assert_eq!(function.kind(), "function_item");
+///
+/// let name = function.child_by_field_name("name").unwrap();
+/// assert_eq!(name.utf8_text(parser.get_code()), Some("f"));
+/// assert_eq!(name.start_position(), (0, 3));
+/// assert_eq!(name.byte_range(), 3..4);
+///
+/// let identifiers = root
+///     .preorder()
+///     .filter(|node| node.kind() == "identifier")
+///     .count();
+/// assert_eq!(identifiers, 2);
+/// ```
+///
+/// [`preorder`]: #method.preorder
+/// [`postorder`]: #method.postorder
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Node<'a>(pub(crate) OtherNode<'a>);
 
 impl<'a> Node<'a> {
     /// Checks if a node represents a syntax error or contains any syntax errors
@@ -34,49 +125,82 @@ impl<'a> Node<'a> {
         self.0.has_error()
     }
 
-    pub(crate) fn id(&self) -> usize {
+    /// Returns an identifier of a node, unique in its tree.
+    pub fn id(&self) -> usize {
         self.0.id()
     }
 
-    pub(crate) fn kind(&self) -> &'static str {
+    /// Returns the name of the grammar rule of a node.
+    pub fn kind(&self) -> &'static str {
         self.0.kind()
     }
 
-    pub(crate) fn kind_id(&self) -> u16 {
+    /// Returns the numeric identifier of the grammar rule of a node.
+    pub fn kind_id(&self) -> u16 {
         self.0.kind_id()
     }
 
-    pub(crate) fn utf8_text(&self, data: &'a [u8]) -> Option<&'a str> {
+    /// Checks if a node is named, that is it corresponds to a named rule
+    /// of the grammar, and not to an anonymous token such as `(` or `if`.
+    pub fn is_named(&self) -> bool {
+        self.0.is_named()
+    }
+
+    /// Returns the text of a node in the given code, if it is valid UTF-8.
+    pub fn utf8_text(&self, data: &'a [u8]) -> Option<&'a str> {
         self.0.utf8_text(data).ok()
     }
 
-    pub(crate) fn start_byte(&self) -> usize {
+    /// Checks if a node represents a syntax error.
+    pub fn is_error(&self) -> bool {
+        self.0.is_error()
+    }
+
+    /// Checks if a node has been inserted by the parser
+    /// to recover from a syntax error.
+    pub fn is_missing(&self) -> bool {
+        self.0.is_missing()
+    }
+
+    /// Returns the offset of the first byte of a node.
+    pub fn start_byte(&self) -> usize {
         self.0.start_byte()
     }
 
-    pub(crate) fn end_byte(&self) -> usize {
+    /// Returns the offset of the byte following a node.
+    pub fn end_byte(&self) -> usize {
         self.0.end_byte()
     }
 
-    pub(crate) fn start_position(&self) -> (usize, usize) {
+    /// Returns the range of the bytes of a node.
+    pub fn byte_range(&self) -> std::ops::Range<usize> {
+        self.0.byte_range()
+    }
+
+    /// Returns the row and the column of the start of a node.
+    pub fn start_position(&self) -> (usize, usize) {
         let temp = self.0.start_position();
         (temp.row, temp.column)
     }
 
-    pub(crate) fn end_position(&self) -> (usize, usize) {
+    /// Returns the row and the column of the end of a node.
+    pub fn end_position(&self) -> (usize, usize) {
         let temp = self.0.end_position();
         (temp.row, temp.column)
     }
 
-    pub(crate) fn start_row(&self) -> usize {
+    /// Returns the first row of a node, starting from 0.
+    pub fn start_row(&self) -> usize {
         self.0.start_position().row
     }
 
-    pub(crate) fn end_row(&self) -> usize {
+    /// Returns the last row of a node, starting from 0.
+    pub fn end_row(&self) -> usize {
         self.0.end_position().row
     }
 
-    pub(crate) fn parent(&self) -> Option<Node<'a>> {
+    /// Returns the parent of a node, if any.
+    pub fn parent(&self) -> Option<Node<'a>> {
         self.0.parent().map(Node)
     }
 
@@ -89,11 +213,13 @@ impl<'a> Node<'a> {
         })
     }
 
-    pub(crate) fn previous_sibling(&self) -> Option<Node<'a>> {
+    /// Returns the previous sibling of a node, if any.
+    pub fn previous_sibling(&self) -> Option<Node<'a>> {
         self.0.prev_sibling().map(Node)
     }
 
-    pub(crate) fn next_sibling(&self) -> Option<Node<'a>> {
+    /// Returns the next sibling of a node, if any.
+    pub fn next_sibling(&self) -> Option<Node<'a>> {
         self.0.next_sibling().map(Node)
     }
 
@@ -104,19 +230,40 @@ impl<'a> Node<'a> {
             .any(|child| child.kind_id() == id)
     }
 
-    pub(crate) fn child_count(&self) -> usize {
+    /// Returns the number of children of a node.
+    pub fn child_count(&self) -> usize {
         self.0.child_count()
     }
 
-    pub(crate) fn child_by_field_name(&self, name: &str) -> Option<Node> {
+    /// Returns the child of a node with the given field name, if any.
+    pub fn child_by_field_name(&self, name: &str) -> Option<Node<'a>> {
         self.0.child_by_field_name(name).map(Node)
     }
 
-    pub(crate) fn child(&self, pos: usize) -> Option<Node<'a>> {
+    /// Returns all the children of a node with the given field name.
+    pub fn children_by_field_name(&self, name: &str) -> Vec<Node<'a>> {
+        let mut cursor = self.0.walk();
+        self.0
+            .children_by_field_name(name, &mut cursor)
+            .map(Node)
+            .collect()
+    }
+
+    /// Returns the field name of the child of a node at the given position,
+    /// if any.
+    pub fn field_name_for_child(&self, pos: usize) -> Option<&'static str> {
+        u32::try_from(pos)
+            .ok()
+            .and_then(|pos| self.0.field_name_for_child(pos))
+    }
+
+    /// Returns the child of a node at the given position, if any.
+    pub fn child(&self, pos: usize) -> Option<Node<'a>> {
         self.0.child(pos).map(Node)
     }
 
-    pub(crate) fn children(&self) -> impl ExactSizeIterator<Item = Node<'a>> + use<'a> {
+    /// Returns the children of a node.
+    pub fn children(&self) -> impl ExactSizeIterator<Item = Node<'a>> + use<'a> {
         let mut cursor = self.cursor();
         cursor.goto_first_child();
         (0..self.child_count()).map(move |_| {
@@ -130,6 +277,31 @@ impl<'a> Node<'a> {
         Cursor(self.0.walk())
     }
 
+    /// Returns an iterator over a node and its descendants,
+    /// each node coming before its children.
+    pub fn preorder(&self) -> Preorder<'a> {
+        Preorder {
+            cursor: self.cursor(),
+            depth: 0,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator over a node and its descendants,
+    /// each node coming after its children.
+    pub fn postorder(&self) -> Postorder<'a> {
+        let mut cursor = self.cursor();
+        let mut depth = 0;
+        while cursor.goto_first_child() {
+            depth += 1;
+        }
+        Postorder {
+            cursor,
+            depth,
+            done: false,
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn get_parent(&self, level: usize) -> Option<Node<'a>> {
         let mut level = level;
@@ -199,11 +371,96 @@ impl<'a> Cursor<'a> {
         self.0.goto_first_child()
     }
 
+    pub(crate) fn goto_parent(&mut self) -> bool {
+        self.0.goto_parent()
+    }
+
     pub(crate) fn node(&self) -> Node<'a> {
         Node(self.0.node())
     }
 }
 
+/// An iterator over a node and its descendants in pre-order.
+///
+/// It is returned by [`Node::preorder`].
+///
+/// [`Node::preorder`]: struct.Node.html#method.preorder
+#[derive(Clone)]
+pub struct Preorder<'a> {
+    cursor: Cursor<'a>,
+    // The depth of the current node from the first one
+    depth: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for Preorder<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let node = self.cursor.node();
+
+        if self.cursor.goto_first_child() {
+            self.depth += 1;
+        } else {
+            // Go to the next sibling of the closest ancestor having one
+            loop {
+                if self.depth == 0 {
+                    self.done = true;
+                    break;
+                }
+                if self.cursor.goto_next_sibling() {
+                    break;
+                }
+                self.cursor.goto_parent();
+                self.depth -= 1;
+            }
+        }
+
+        Some(node)
+    }
+}
+
+/// An iterator over a node and its descendants in post-order.
+///
+/// It is returned by [`Node::postorder`].
+///
+/// [`Node::postorder`]: struct.Node.html#method.postorder
+#[derive(Clone)]
+pub struct Postorder<'a> {
+    cursor: Cursor<'a>,
+    // The depth of the current node from the first one
+    depth: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for Postorder<'a> {
+    type Item = Node<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let node = self.cursor.node();
+
+        if self.depth == 0 {
+            self.done = true;
+        } else if self.cursor.goto_next_sibling() {
+            // Go to the first leaf of the next sibling
+            while self.cursor.goto_first_child() {
+                self.depth += 1;
+            }
+        } else {
+            self.cursor.goto_parent();
+            self.depth -= 1;
+        }
+
+        Some(node)
+    }
+}
+
 impl<'a> Search<'a> for Node<'a> {
     fn first_occurrence(&self, pred: fn(u16) -> bool) -> Option<Node<'a>> {
         let mut cursor = self.cursor();
@@ -267,3 +524,77 @@ impl<'a> Search<'a> for Node<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{ParserTrait, PythonParser};
+
+    fn kinds<'a>(nodes: impl Iterator<Item = super::Node<'a>>) -> Vec<&'static str> {
+        nodes
+            .filter(|node| node.is_named())
+            .map(|node| node.kind())
+            .collect()
+    }
+
+    #[test]
+    fn node_traversal() {
+        let parser = PythonParser::new(b"a = f(b)\n".to_vec(), Path::new("foo.py"), None).unwrap();
+        let root = parser.get_root();
+
+        assert_eq!(
+            kinds(root.preorder()),
+            [
+                "module",
+                "expression_statement",
+                "assignment",
+                "identifier",
+                "call",
+                "identifier",
+                "argument_list",
+                "identifier"
+            ]
+        );
+        assert_eq!(
+            kinds(root.postorder()),
+            [
+                "identifier",
+                "identifier",
+                "identifier",
+                "argument_list",
+                "call",
+                "assignment",
+                "expression_statement",
+                "module"
+            ]
+        );
+
+        // The traversal of a node does not go out of it
+        let call = root.preorder().find(|node| node.kind() == "call").unwrap();
+        assert_eq!(call.preorder().count(), call.postorder().count());
+        assert_eq!(kinds(call.postorder()).last(), Some(&"call"));
+    }
+
+    #[test]
+    fn node_navigation() {
+        let parser = PythonParser::new(b"a = f(b)\n".to_vec(), Path::new("foo.py"), None).unwrap();
+        let code = parser.get_code();
+        let root = parser.get_root();
+
+        let assignment = root.child(0).unwrap().child(0).unwrap();
+        let left = assignment.child_by_field_name("left").unwrap();
+        let right = assignment.child_by_field_name("right").unwrap();
+
+        assert_eq!(left.utf8_text(code), Some("a"));
+        assert_eq!(right.utf8_text(code), Some("f(b)"));
+        assert_eq!(right.start_position(), (0, 4));
+        assert_eq!(right.end_position(), (0, 8));
+        assert_eq!(right.byte_range(), 4..8);
+        assert_eq!(assignment.field_name_for_child(0), Some("left"));
+        assert_eq!(left.next_sibling().unwrap().kind(), "=");
+        assert_eq!(right.previous_sibling().unwrap().kind(), "=");
+        assert_eq!(right.parent(), Some(assignment));
+        assert_eq!(assignment.children().count(), assignment.child_count());
+    }
+}
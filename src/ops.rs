@@ -4,11 +4,12 @@ use std::path::{Path, PathBuf};
 use serde::Serialize;
 
 use crate::checker::Checker;
+use crate::error::Error;
 use crate::getter::Getter;
 use crate::node::Node;
-use crate::spaces::SpaceKind;
+use crate::spaces::{CodeRange, SpaceKind, get_space_range};
 
-use crate::halstead::{Halstead, HalsteadMaps};
+use crate::halstead::{Halstead, HalsteadMaps, HalsteadTable};
 
 use crate::dump_ops::*;
 use crate::traits::*;
@@ -25,6 +26,19 @@ pub struct Ops {
     pub start_line: usize,
     /// The last line of a function space.
     pub end_line: usize,
+    /// The column of the first byte of a function space, starting from 1.
+    pub start_column: usize,
+    /// The column following the last byte of the last line
+    /// of a function space.
+    pub end_column: usize,
+    /// The offset of the first byte of a function space, starting from 0.
+    pub start_byte: usize,
+    /// The offset following the last byte of a function space.
+    pub end_byte: usize,
+    /// The range of the name of a function space.
+    ///
+    /// If `None`, the name has not been found in the code.
+    pub name_range: Option<CodeRange>,
     /// The space kind.
     pub kind: SpaceKind,
     /// All subspaces contained in a function space.
@@ -37,22 +51,19 @@ pub struct Ops {
 
 impl Ops {
     fn new<T: Getter>(node: &Node, code: &[u8], kind: SpaceKind) -> Self {
-        let (start_position, end_position) = match kind {
-            SpaceKind::Unit => {
-                if node.child_count() == 0 {
-                    (0, 0)
-                } else {
-                    (node.start_row() + 1, node.end_row())
-                }
-            }
-            _ => (node.start_row() + 1, node.end_row() + 1),
-        };
+        let range = get_space_range(node, code, kind);
+        let name = T::get_func_space_name(node, code);
         Self {
-            name: T::get_func_space_name(node, code).map(|name| name.to_string()),
+            name: name.map(|name| name.to_string()),
+            name_range: T::get_func_space_name_node(node).map(|name| CodeRange::new(&name)),
             spaces: Vec::new(),
             kind,
-            start_line: start_position,
-            end_line: end_position,
+            start_line: range.start_line,
+            end_line: range.end_line,
+            start_column: range.start_column,
+            end_column: range.end_column,
+            start_byte: range.start_byte,
+            end_byte: range.end_byte,
             operators: Vec::new(),
             operands: Vec::new(),
         }
@@ -131,8 +142,8 @@ fn finalize<T: ParserTrait>(state_stack: &mut Vec<State>, diff_level: usize) {
 
 /// Retrieves all the operators and operands of a code.
 ///
-/// If `None`, it was not possible to retrieve the operators and operands
-/// of a code.
+/// Returns an error if it was not possible to retrieve the operators
+/// and operands of a code.
 ///
 /// # Examples
 ///
@@ -149,13 +160,16 @@ fn finalize<T: ParserTrait>(state_stack: &mut Vec<State>, diff_level: usize) {
 /// let source_as_vec = source_code.as_bytes().to_vec();
 ///
 /// // The parser of the code, in this case a CPP parser
-/// let parser = CppParser::new(source_as_vec, &path, None);
+/// let parser = CppParser::new(source_as_vec, &path, None).unwrap();
 ///
 /// // Returns the operands and operators of each space in a code.
 /// operands_and_operators(&parser, &path).unwrap();
 /// # }
 /// ```
-pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Ops> {
+pub fn operands_and_operators<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+) -> Result<Ops, Error> {
     let code = parser.get_code();
     let node = parser.get_root();
     let mut cursor = node.cursor();
@@ -163,6 +177,7 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
     let mut children = Vec::new();
     let mut state_stack: Vec<State> = Vec::new();
     let mut last_level = 0;
+    let table = HalsteadTable::new();
 
     stack.push((node, 0));
 
@@ -190,7 +205,7 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
         };
 
         if let Some(state) = state_stack.last_mut() {
-            T::Halstead::compute(&node, code, &mut state.halstead_maps);
+            T::Halstead::compute(&node, code, &table, &mut state.halstead_maps);
             if T::Checker::is_primitive(node.kind_id()) {
                 let code = &code[node.start_byte()..node.end_byte()];
                 let primitive_string = String::from_utf8(code.to_vec())
@@ -215,10 +230,13 @@ pub fn operands_and_operators<'a, T: ParserTrait>(parser: &'a T, path: &'a Path)
 
     finalize::<T>(&mut state_stack, usize::MAX);
 
-    state_stack.pop().map(|mut state| {
-        state.ops.name = path.to_str().map(|name| name.to_string());
-        state.ops
-    })
+    state_stack
+        .pop()
+        .map(|mut state| {
+            state.ops.name = path.to_str().map(|name| name.to_string());
+            state.ops
+        })
+        .ok_or_else(|| Error::Parse(path.to_path_buf()))
 }
 
 /// Configuration options for retrieving
@@ -234,15 +252,12 @@ pub struct OpsCode {
 }
 
 impl Callback for OpsCode {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = OpsCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        if let Some(ops) = operands_and_operators(parser, &cfg.path) {
-            dump_ops(&ops)
-        } else {
-            Ok(())
-        }
+        let ops = operands_and_operators(parser, &cfg.path)?;
+        Ok(dump_ops(&ops)?)
     }
 }
 
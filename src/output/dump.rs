@@ -2,6 +2,7 @@ use std::io::Write;
 
 use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
 
+use crate::error::Error;
 use crate::node::Node;
 use crate::tools::{color, intense_color};
 
@@ -25,7 +26,7 @@ use crate::traits::*;
 /// let source_as_vec = source_code.as_bytes().to_vec();
 ///
 /// // The parser of the code, in this case a CPP parser
-/// let parser = CppParser::new(source_as_vec.clone(), &path, None);
+/// let parser = CppParser::new(source_as_vec.clone(), &path, None).unwrap();
 ///
 /// // The root of the AST
 /// let root = parser.get_root();
@@ -177,16 +178,16 @@ pub struct Dump {
 }
 
 impl Callback for Dump {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = DumpCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        dump_node(
+        Ok(dump_node(
             parser.get_code(),
             &parser.get_root(),
             -1,
             cfg.line_start,
             cfg.line_end,
-        )
+        )?)
     }
 }
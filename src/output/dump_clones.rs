@@ -0,0 +1,85 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::clones::{CloneGroup, CloneInstance, Clones};
+
+use crate::tools::{color, intense_color};
+
+/// Dumps all groups of clones found by a clone detector.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{dump_clones, CloneDetector, ParserTrait, RustParser};
+///
+/// let source_code = "fn f() { let a = 1; }";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Search the clones
+/// let mut detector = CloneDetector::default();
+/// detector.add(&parser, &path);
+///
+/// // Dump all clones
+/// dump_clones(&detector.clones()).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_clones(clones: &Clones) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+
+    for group in &clones.groups {
+        dump_group(group, &mut stdout)?;
+    }
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_group(group: &CloneGroup, stdout: &mut StandardStreamLock) -> std::io::Result<()> {
+    intense_color(stdout, Color::Yellow)?;
+    write!(stdout, "clone")?;
+
+    color(stdout, Color::White)?;
+    write!(stdout, " (tokens: {}, lines: {}", group.tokens, group.lines)?;
+    if let Some(similarity) = group.similarity {
+        write!(stdout, ", similarity: {similarity}")?;
+    }
+    writeln!(stdout, ")")?;
+
+    if let Some((last, instances)) = group.instances.split_last() {
+        for instance in instances {
+            dump_instance(instance, false, stdout)?;
+        }
+        dump_instance(last, true, stdout)?;
+    }
+
+    Ok(())
+}
+
+fn dump_instance(
+    instance: &CloneInstance,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let pref = if last { "`- " } else { "|- " };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{pref}")?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(stdout, "{}", instance.path.display())?;
+
+    intense_color(stdout, Color::Red)?;
+    writeln!(stdout, " @{}-{}", instance.start_line, instance.end_line)
+}
@@ -0,0 +1,98 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::debt::{DebtMarker, DebtMarkers};
+
+use crate::tools::{color, intense_color};
+
+/// Dumps all technical-debt markers of a code.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{debt_markers, dump_debt, MarkerPatterns, ParserTrait, RustParser};
+///
+/// let source_code = "// FIXME: BUG-1234\nfn f() {}";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Retrieve all markers
+/// let debt = debt_markers(&parser, &path, &MarkerPatterns::default());
+///
+/// // Dump all markers
+/// dump_debt(&debt).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_debt(debt: &DebtMarkers) -> std::io::Result<()> {
+    if debt.markers.is_empty() {
+        return Ok(());
+    }
+
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+
+    intense_color(&mut stdout, Color::Yellow)?;
+    writeln!(stdout, "{}", debt.name.as_ref().map_or("", |name| name))?;
+
+    if let Some((last, markers)) = debt.markers.split_last() {
+        for marker in markers {
+            dump_marker(marker, false, &mut stdout)?;
+        }
+        dump_marker(last, true, &mut stdout)?;
+    }
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_marker(
+    marker: &DebtMarker,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let pref = if last { "`- " } else { "|- " };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{pref}")?;
+
+    intense_color(stdout, Color::Red)?;
+    write!(stdout, "{}:{} ", marker.line, marker.column)?;
+
+    intense_color(stdout, Color::Green)?;
+    write!(stdout, "{}", marker.marker)?;
+
+    if let Some(author) = &marker.author {
+        intense_color(stdout, Color::Magenta)?;
+        write!(stdout, " @{author}")?;
+    }
+    if let Some(ticket) = &marker.ticket {
+        intense_color(stdout, Color::Magenta)?;
+        write!(stdout, " [{ticket}]")?;
+    }
+
+    color(stdout, Color::White)?;
+    write!(stdout, " {}", marker.text)?;
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, " in {} ", marker.space_kind)?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(
+        stdout,
+        "{}",
+        marker.space_name.as_ref().map_or("", |name| name)
+    )?;
+
+    intense_color(stdout, Color::Red)?;
+    writeln!(stdout, " (@{})", marker.space_line)
+}
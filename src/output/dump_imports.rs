@@ -0,0 +1,75 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::dependencies::Dependencies;
+use crate::imports::Import;
+
+use crate::tools::{color, intense_color};
+
+/// Dumps all imports of a code.
+///
+/// Each import is printed on a line, prefixed by its position.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{dump_imports, imports, ParserTrait, RustParser};
+///
+/// let source_code = "use std::io::{Read, Write};";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Retrieve all imports
+/// let dependencies = imports(&parser, &path);
+///
+/// // Dump all imports
+/// dump_imports(&dependencies).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_imports(dependencies: &Dependencies) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+
+    let name = dependencies.name.as_ref().map_or("", |name| name);
+    for import in &dependencies.imports {
+        dump_import(name, import, &mut stdout)?;
+    }
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_import(
+    name: &str,
+    import: &Import,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    intense_color(stdout, Color::Yellow)?;
+    write!(stdout, "{name}")?;
+
+    intense_color(stdout, Color::Red)?;
+    write!(stdout, ":{}:{}: ", import.line, import.column)?;
+
+    intense_color(stdout, Color::Green)?;
+    write!(stdout, "{} ", import.kind)?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(stdout, "{}", import.path)?;
+
+    if !import.symbols.is_empty() {
+        color(stdout, Color::White)?;
+        write!(stdout, " {{{}}}", import.symbols.join(", "))?;
+    }
+
+    writeln!(stdout)
+}
@@ -0,0 +1,160 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::increments::Increments;
+use crate::spaces::{FuncSpace, SpaceKind};
+
+use crate::tools::{color, intense_color};
+
+/// Dumps the code of each function annotated with the increments
+/// of its complexity metrics.
+///
+/// The left margin of each line contains the `Cognitive Complexity`
+/// and the `Cyclomatic` increments caused by that line, while their
+/// reasons are written at its end.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{dump_increments, metrics_with_increments, ParserTrait, RustParser};
+///
+/// let source_code = "fn f(a: bool) {\n    if a {}\n}\n";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec.clone(), &path, None).unwrap();
+///
+/// // Compute metrics and their increments
+/// let space = metrics_with_increments(&parser, &path).unwrap();
+///
+/// // Dump the annotated code of each function
+/// dump_increments(&space, &source_as_vec).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_increments(space: &FuncSpace, code: &[u8]) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+    let lines: Vec<&[u8]> = code.split(|c| *c == b'\n').collect();
+    dump_space(space, &lines, &mut stdout)?;
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_space(
+    space: &FuncSpace,
+    lines: &[&[u8]],
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    if let (SpaceKind::Function, Some(increments)) = (space.kind, &space.increments) {
+        dump_function(space, increments, lines, stdout)?;
+    }
+
+    for space in space.spaces.iter() {
+        dump_space(space, lines, stdout)?;
+    }
+
+    Ok(())
+}
+
+fn dump_function(
+    space: &FuncSpace,
+    increments: &Increments,
+    lines: &[&[u8]],
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    intense_color(stdout, Color::Yellow)?;
+    write!(stdout, "{}: ", space.kind)?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(stdout, "{}", space.name.as_ref().map_or("", |name| name))?;
+
+    intense_color(stdout, Color::Red)?;
+    write!(stdout, " (@{})", space.start_line)?;
+
+    color(stdout, Color::Green)?;
+    writeln!(
+        stdout,
+        " cognitive: {}, cyclomatic: {}",
+        space.metrics.cognitive.cognitive(),
+        space.metrics.cyclomatic.cyclomatic()
+    )?;
+
+    color(stdout, Color::Blue)?;
+    writeln!(stdout, "{:>6} {:>4} {:>4} |", "line", "cog", "cyc")?;
+
+    let end_line = space.end_line.min(lines.len());
+    for line in space.start_line..=end_line {
+        let cognitive = increments.cognitive_on_line(line);
+        let cyclomatic = increments.cyclomatic_on_line(line);
+
+        color(stdout, Color::Blue)?;
+        write!(stdout, "{line:>6} ")?;
+
+        intense_color(stdout, Color::Red)?;
+        write!(stdout, "{:>4} ", fmt_increment(cognitive))?;
+
+        intense_color(stdout, Color::Magenta)?;
+        write!(stdout, "{:>4}", fmt_increment(cyclomatic))?;
+
+        color(stdout, Color::Blue)?;
+        write!(stdout, " | ")?;
+
+        color(stdout, Color::White)?;
+        write!(stdout, "{}", String::from_utf8_lossy(lines[line - 1]))?;
+
+        if cognitive != 0 || cyclomatic != 0 {
+            color(stdout, Color::Green)?;
+            write!(stdout, "  <- {}", reasons(increments, line))?;
+        }
+        writeln!(stdout)?;
+    }
+    writeln!(stdout)
+}
+
+#[inline(always)]
+fn fmt_increment(value: usize) -> String {
+    if value == 0 {
+        String::new()
+    } else {
+        format!("+{value}")
+    }
+}
+
+fn reasons(increments: &Increments, line: usize) -> String {
+    let cognitive: Vec<_> = increments
+        .cognitive
+        .iter()
+        .filter(|inc| inc.line == line)
+        .map(|inc| {
+            if inc.nesting > 0 {
+                format!("{} (+{}, nesting {})", inc.reason, inc.value, inc.nesting)
+            } else {
+                format!("{} (+{})", inc.reason, inc.value)
+            }
+        })
+        .collect();
+    let cyclomatic: Vec<_> = increments
+        .cyclomatic
+        .iter()
+        .filter(|inc| inc.line == line)
+        .map(|inc| inc.reason.as_str())
+        .collect();
+
+    let mut res = Vec::new();
+    if !cognitive.is_empty() {
+        res.push(format!("cognitive: {}", cognitive.join(", ")));
+    }
+    if !cyclomatic.is_empty() {
+        res.push(format!("cyclomatic: {}", cyclomatic.join(", ")));
+    }
+    res.join("; ")
+}
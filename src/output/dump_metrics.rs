@@ -2,16 +2,29 @@ use std::io::Write;
 use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
 
 use crate::abc;
+use crate::booleans;
 use crate::cognitive;
+use crate::custom_metrics::CustomMetrics;
 use crate::cyclomatic;
+use crate::doc_coverage;
+use crate::duplication;
+use crate::exceptions;
 use crate::exit;
 use crate::halstead;
+use crate::imports;
+use crate::literals;
 use crate::loc;
+use crate::markers;
+use crate::metric_set::MetricKind;
 use crate::mi;
+use crate::naming::NamingStats;
 use crate::nargs;
 use crate::nom;
 use crate::npa;
 use crate::npm;
+use crate::safety;
+use crate::statements;
+use crate::variables;
 use crate::wmc;
 
 use crate::spaces::{CodeMetrics, FuncSpace};
@@ -36,7 +49,7 @@ use crate::tools::{color, intense_color};
 /// let source_as_vec = source_code.as_bytes().to_vec();
 ///
 /// // The parser of the code, in this case a CPP parser
-/// let parser = CppParser::new(source_as_vec, &path, None);
+/// let parser = CppParser::new(source_as_vec, &path, None).unwrap();
 ///
 /// // Compute metrics
 /// let space = metrics(&parser, &path).unwrap();
@@ -73,7 +86,11 @@ fn dump_space(
     write!(stdout, "{}", space.name.as_ref().map_or("", |name| name))?;
 
     intense_color(stdout, Color::Red)?;
-    writeln!(stdout, " (@{})", space.start_line)?;
+    write!(stdout, " (@{})", space.start_line)?;
+    if !space.syntax_errors.is_empty() {
+        write!(stdout, " [syntax errors: {}]", space.syntax_errors.count())?;
+    }
+    writeln!(stdout)?;
 
     let prefix = format!("{prefix}{pref_child}");
     dump_metrics(&space.metrics, &prefix, space.spaces.is_empty(), stdout)?;
@@ -103,20 +120,74 @@ fn dump_metrics(
     writeln!(stdout, "metrics")?;
 
     let prefix = format!("{prefix}{pref_child}");
-    dump_cognitive(&metrics.cognitive, &prefix, false, stdout)?;
-    dump_cyclomatic(&metrics.cyclomatic, &prefix, false, stdout)?;
-    dump_nargs(&metrics.nargs, &prefix, false, stdout)?;
-    dump_nexits(&metrics.nexits, &prefix, false, stdout)?;
-    dump_halstead(&metrics.halstead, &prefix, false, stdout)?;
-    dump_loc(&metrics.loc, &prefix, false, stdout)?;
-    dump_nom(&metrics.nom, &prefix, false, stdout)?;
-    dump_mi(&metrics.mi, &prefix, false, stdout)?;
-    dump_abc(&metrics.abc, &prefix, false, stdout)?;
-    dump_wmc(&metrics.wmc, &prefix, false, stdout)?;
-    dump_npm(&metrics.npm, &prefix, false, stdout)?;
-    dump_npa(&metrics.npa, &prefix, true, stdout)
+    let mut kinds = DUMP_ORDER
+        .iter()
+        .filter(|kind| metrics.is_serialized(**kind))
+        .peekable();
+    while let Some(kind) = kinds.next() {
+        let last = kinds.peek().is_none();
+        match kind {
+            MetricKind::Cognitive => dump_cognitive(&metrics.cognitive, &prefix, last, stdout),
+            MetricKind::Cyclomatic => dump_cyclomatic(&metrics.cyclomatic, &prefix, last, stdout),
+            MetricKind::NArgs => dump_nargs(&metrics.nargs, &prefix, last, stdout),
+            MetricKind::NExits => dump_nexits(&metrics.nexits, &prefix, last, stdout),
+            MetricKind::Halstead => dump_halstead(&metrics.halstead, &prefix, last, stdout),
+            MetricKind::Loc => dump_loc(&metrics.loc, &prefix, last, stdout),
+            MetricKind::Nom => dump_nom(&metrics.nom, &prefix, last, stdout),
+            MetricKind::Mi => dump_mi(&metrics.mi, &prefix, last, stdout),
+            MetricKind::Abc => dump_abc(&metrics.abc, &prefix, last, stdout),
+            MetricKind::Wmc => dump_wmc(&metrics.wmc, &prefix, last, stdout),
+            MetricKind::Npm => dump_npm(&metrics.npm, &prefix, last, stdout),
+            MetricKind::Npa => dump_npa(&metrics.npa, &prefix, last, stdout),
+            MetricKind::Duplication => {
+                dump_duplication(&metrics.duplication, &prefix, last, stdout)
+            }
+            MetricKind::Markers => dump_markers(&metrics.markers, &prefix, last, stdout),
+            MetricKind::DocCoverage => {
+                dump_doc_coverage(&metrics.doc_coverage, &prefix, last, stdout)
+            }
+            MetricKind::Exceptions => dump_exceptions(&metrics.exceptions, &prefix, last, stdout),
+            MetricKind::Safety => dump_safety(&metrics.safety, &prefix, last, stdout),
+            MetricKind::Literals => dump_literals(&metrics.literals, &prefix, last, stdout),
+            MetricKind::Booleans => dump_booleans(&metrics.booleans, &prefix, last, stdout),
+            MetricKind::Imports => dump_imports(&metrics.imports, &prefix, last, stdout),
+            MetricKind::Variables => dump_variables(&metrics.variables, &prefix, last, stdout),
+            MetricKind::Statements => dump_statements(&metrics.statements, &prefix, last, stdout),
+            MetricKind::Naming => dump_naming(&metrics.naming, &prefix, last, stdout),
+            MetricKind::Custom => dump_custom(&metrics.custom, &prefix, stdout),
+        }?;
+    }
+    Ok(())
 }
 
+// The order in which the metrics are dumped, the custom ones last
+const DUMP_ORDER: &[MetricKind] = &[
+    MetricKind::Cognitive,
+    MetricKind::Cyclomatic,
+    MetricKind::NArgs,
+    MetricKind::NExits,
+    MetricKind::Halstead,
+    MetricKind::Loc,
+    MetricKind::Nom,
+    MetricKind::Mi,
+    MetricKind::Abc,
+    MetricKind::Wmc,
+    MetricKind::Npm,
+    MetricKind::Npa,
+    MetricKind::Duplication,
+    MetricKind::Markers,
+    MetricKind::DocCoverage,
+    MetricKind::Exceptions,
+    MetricKind::Safety,
+    MetricKind::Literals,
+    MetricKind::Booleans,
+    MetricKind::Imports,
+    MetricKind::Variables,
+    MetricKind::Statements,
+    MetricKind::Naming,
+    MetricKind::Custom,
+];
+
 fn dump_cognitive(
     stats: &cognitive::Stats,
     prefix: &str,
@@ -255,13 +326,29 @@ fn dump_mi(
     let prefix = format!("{prefix}{pref_child}");
     dump_value("mi_original", stats.mi_original(), &prefix, false, stdout)?;
     dump_value("mi_sei", stats.mi_sei(), &prefix, false, stdout)?;
+    let custom = stats.mi_custom().zip(stats.rating());
     dump_value(
         "mi_visual_studio",
         stats.mi_visual_studio(),
         &prefix,
-        true,
+        custom.is_none(),
         stdout,
-    )
+    )?;
+
+    if let Some((mi_custom, rating)) = custom {
+        dump_value("mi_custom", mi_custom, &prefix, false, stdout)?;
+
+        color(stdout, Color::Blue)?;
+        write!(stdout, "{prefix}`- ")?;
+
+        intense_color(stdout, Color::Magenta)?;
+        write!(stdout, "rating: ")?;
+
+        color(stdout, Color::White)?;
+        writeln!(stdout, "{rating}")?;
+    }
+
+    Ok(())
 }
 
 fn dump_nargs(
@@ -423,6 +510,182 @@ fn dump_npa(
     dump_value("average", stats.total_cda(), &prefix, true, stdout)
 }
 
+fn dump_duplication(
+    stats: &duplication::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    if stats.is_disabled() {
+        return Ok(());
+    }
+
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "duplication")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value(
+        "duplicated_lines",
+        stats.duplicated_lines(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("ratio", stats.ratio(), &prefix, true, stdout)
+}
+
+fn dump_markers(
+    stats: &markers::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "markers")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("todo", stats.todo_sum(), &prefix, false, stdout)?;
+    dump_value("fixme", stats.fixme_sum(), &prefix, false, stdout)?;
+    dump_value("hack", stats.hack_sum(), &prefix, false, stdout)?;
+    dump_value("xxx", stats.xxx_sum(), &prefix, false, stdout)?;
+    dump_value("total", stats.total(), &prefix, true, stdout)
+}
+
+fn dump_doc_coverage(
+    stats: &doc_coverage::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "doc_coverage")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("documented", stats.documented_sum(), &prefix, false, stdout)?;
+    dump_value("total", stats.total_sum(), &prefix, false, stdout)?;
+    dump_value("coverage", stats.coverage(), &prefix, true, stdout)
+}
+
+fn dump_exceptions(
+    stats: &exceptions::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "exceptions")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("tries", stats.tries_sum(), &prefix, false, stdout)?;
+    dump_value("catches", stats.catches_sum(), &prefix, false, stdout)?;
+    dump_value("throws", stats.throws_sum(), &prefix, false, stdout)?;
+    dump_value("catch_alls", stats.catch_alls_sum(), &prefix, false, stdout)?;
+    dump_value(
+        "empty_catches",
+        stats.empty_catches_sum(),
+        &prefix,
+        true,
+        stdout,
+    )
+}
+
+fn dump_safety(
+    stats: &safety::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    if stats.is_disabled() {
+        return Ok(());
+    }
+
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "safety")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value(
+        "unsafe_blocks",
+        stats.unsafe_blocks_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("unsafe_fns", stats.unsafe_fns_sum(), &prefix, false, stdout)?;
+    dump_value(
+        "unsafe_impls",
+        stats.unsafe_impls_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value(
+        "unsafe_derefs",
+        stats.unsafe_derefs_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("unwraps", stats.unwraps_sum(), &prefix, false, stdout)?;
+    dump_value("panics", stats.panics_sum(), &prefix, false, stdout)?;
+    dump_value("indexings", stats.indexings_sum(), &prefix, true, stdout)
+}
+
+fn dump_literals(
+    stats: &literals::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "literals")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value(
+        "magic_numbers",
+        stats.magic_numbers_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value(
+        "repeated_strings",
+        stats.repeated_strings_sum(),
+        &prefix,
+        true,
+        stdout,
+    )
+}
+
 fn dump_value(
     name: &str,
     val: f64,
@@ -441,3 +704,194 @@ fn dump_value(
     color(stdout, Color::White)?;
     writeln!(stdout, "{val}")
 }
+
+fn dump_booleans(
+    stats: &booleans::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "booleans")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("max_operands", stats.operands_max(), &prefix, false, stdout)?;
+    dump_value(
+        "complex_conditions",
+        stats.complex_conditions_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value(
+        "max_negation_depth",
+        stats.negation_depth_max(),
+        &prefix,
+        true,
+        stdout,
+    )
+}
+
+fn dump_imports(
+    stats: &imports::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "imports")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("static", stats.static_imports_sum(), &prefix, false, stdout)?;
+    dump_value(
+        "dynamic",
+        stats.dynamic_imports_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("total", stats.total(), &prefix, true, stdout)
+}
+
+fn dump_variables(
+    stats: &variables::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "variables")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("span_average", stats.span_average(), &prefix, false, stdout)?;
+    dump_value("span_max", stats.span_max(), &prefix, false, stdout)?;
+    dump_value("live_average", stats.live_average(), &prefix, false, stdout)?;
+    dump_value("live_max", stats.live_max(), &prefix, true, stdout)
+}
+
+fn dump_statements(
+    stats: &statements::Stats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "statements")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value("declarations", stats.declarations(), &prefix, false, stdout)?;
+    dump_value("assignments", stats.assignments(), &prefix, false, stdout)?;
+    dump_value("calls", stats.calls(), &prefix, false, stdout)?;
+    dump_value("control_flow", stats.control_flow(), &prefix, false, stdout)?;
+    dump_value("returns", stats.returns(), &prefix, false, stdout)?;
+    dump_value("loops", stats.loops(), &prefix, false, stdout)?;
+    dump_value("total", stats.total(), &prefix, true, stdout)
+}
+
+fn dump_naming(
+    stats: &NamingStats,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "naming")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    dump_value(
+        "identifiers",
+        stats.identifiers_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value(
+        "average_length",
+        stats.average_length_sum(),
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value("violations", stats.violations_sum(), &prefix, true, stdout)
+}
+
+fn dump_custom(
+    metrics: &CustomMetrics,
+    prefix: &str,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let mut values = metrics.values().peekable();
+    while let Some((name, value)) = values.next() {
+        dump_json(name, &value, prefix, values.peek().is_none(), stdout)?;
+    }
+    Ok(())
+}
+
+fn dump_json(
+    name: &str,
+    value: &serde_json::Value,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    match value {
+        serde_json::Value::Object(fields) => {
+            color(stdout, Color::Blue)?;
+            write!(stdout, "{prefix}{pref}")?;
+
+            intense_color(stdout, Color::Green)?;
+            writeln!(stdout, "{name}")?;
+
+            let prefix = format!("{prefix}{pref_child}");
+            let mut fields = fields.iter().peekable();
+            while let Some((name, value)) = fields.next() {
+                dump_json(name, value, &prefix, fields.peek().is_none(), stdout)?;
+            }
+            Ok(())
+        }
+        serde_json::Value::Number(number) => dump_value(
+            name,
+            number.as_f64().unwrap_or_default(),
+            prefix,
+            last,
+            stdout,
+        ),
+        _ => {
+            color(stdout, Color::Blue)?;
+            write!(stdout, "{prefix}{pref}")?;
+
+            intense_color(stdout, Color::Magenta)?;
+            write!(stdout, "{name}: ")?;
+
+            color(stdout, Color::White)?;
+            writeln!(stdout, "{value}")
+        }
+    }
+}
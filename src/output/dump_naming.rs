@@ -0,0 +1,148 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::naming::{Naming, Violation};
+
+use crate::tools::{color, intense_color};
+
+/// Dumps the naming of the identifiers declared in a code.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{dump_naming, naming, NamingConventions, ParserTrait, RustParser, LANG};
+///
+/// let source_code = "fn doIt() {}";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Rust parser
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Check the names of the identifiers
+/// let naming = naming(&parser, &path, &NamingConventions::new(LANG::Rust)).unwrap();
+///
+/// // Dump the naming of the identifiers
+/// dump_naming(&naming).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_naming(naming: &Naming) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+    dump_space(naming, "", true, &mut stdout)?;
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_space(
+    space: &Naming,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Yellow)?;
+    write!(stdout, "{}: ", space.kind)?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(stdout, "{}", space.name.as_ref().map_or("", |name| name))?;
+
+    intense_color(stdout, Color::Red)?;
+    writeln!(stdout, " (@{})", space.start_line)?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    let no_spaces = space.spaces.is_empty();
+
+    dump_value(
+        "identifiers",
+        space.identifiers as f64,
+        &prefix,
+        false,
+        stdout,
+    )?;
+    dump_value(
+        "average_length",
+        space.average_length,
+        &prefix,
+        no_spaces && space.violations.is_empty(),
+        stdout,
+    )?;
+    if !space.violations.is_empty() {
+        dump_violations(&space.violations, &prefix, no_spaces, stdout)?;
+    }
+
+    if let Some((last, spaces)) = space.spaces.split_last() {
+        for space in spaces {
+            dump_space(space, &prefix, false, stdout)?;
+        }
+        dump_space(last, &prefix, true, stdout)?;
+    }
+
+    Ok(())
+}
+
+fn dump_value(
+    name: &str,
+    value: f64,
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let pref = if last { "`- " } else { "|- " };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    write!(stdout, "{name}: ")?;
+
+    color(stdout, Color::White)?;
+    writeln!(stdout, "{value}")
+}
+
+fn dump_violations(
+    violations: &[Violation],
+    prefix: &str,
+    last: bool,
+    stdout: &mut StandardStreamLock,
+) -> std::io::Result<()> {
+    let (pref_child, pref) = if last { ("   ", "`- ") } else { ("|  ", "|- ") };
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, "{prefix}{pref}")?;
+
+    intense_color(stdout, Color::Green)?;
+    writeln!(stdout, "violations")?;
+
+    let prefix = format!("{prefix}{pref_child}");
+    let count = violations.len();
+    for (i, violation) in violations.iter().enumerate() {
+        let pref = if i + 1 == count { "`- " } else { "|- " };
+
+        color(stdout, Color::Blue)?;
+        write!(stdout, "{prefix}{pref}")?;
+
+        intense_color(stdout, Color::Red)?;
+        write!(stdout, "{}:{}: ", violation.line, violation.column)?;
+
+        intense_color(stdout, Color::Cyan)?;
+        write!(stdout, "{} ", violation.kind)?;
+
+        color(stdout, Color::White)?;
+        writeln!(stdout, "{} {}", violation.name, violation.rule)?;
+    }
+
+    Ok(())
+}
@@ -24,7 +24,7 @@ use crate::tools::{color, intense_color};
 /// let source_as_vec = source_code.as_bytes().to_vec();
 ///
 /// // The parser of the code, in this case a CPP parser
-/// let parser = CppParser::new(source_as_vec, &path, None);
+/// let parser = CppParser::new(source_as_vec, &path, None).unwrap();
 ///
 /// // Retrieve all operands and operators
 /// let ops = operands_and_operators(&parser, &path).unwrap();
@@ -0,0 +1,78 @@
+use std::io::Write;
+use termcolor::{Color, ColorChoice, StandardStream, StandardStreamLock};
+
+use crate::smells::{Smell, Smells};
+
+use crate::tools::{color, intense_color};
+
+/// Dumps all code smells of a code.
+///
+/// Each smell is printed on a line, prefixed by its position.
+///
+/// Returns a [`Result`] value, when an error occurs.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{dump_smells, smells, ParserTrait, PythonParser, DEFAULT_ALLOWED_NUMBERS};
+///
+/// let source_code = "try:\n    f()\nexcept:\n    pass\n";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("foo.py");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Python parser
+/// let parser = PythonParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Retrieve all smells
+/// let smells = smells(&parser, &path, DEFAULT_ALLOWED_NUMBERS);
+///
+/// // Dump all smells
+/// dump_smells(&smells).unwrap();
+/// ```
+///
+/// [`Result`]: #variant.Result
+pub fn dump_smells(smells: &Smells) -> std::io::Result<()> {
+    let stdout = StandardStream::stdout(ColorChoice::Always);
+    let mut stdout = stdout.lock();
+
+    let name = smells.name.as_ref().map_or("", |name| name);
+    for smell in &smells.smells {
+        dump_smell(name, smell, &mut stdout)?;
+    }
+    color(&mut stdout, Color::White)?;
+
+    Ok(())
+}
+
+fn dump_smell(name: &str, smell: &Smell, stdout: &mut StandardStreamLock) -> std::io::Result<()> {
+    intense_color(stdout, Color::Yellow)?;
+    write!(stdout, "{name}")?;
+
+    intense_color(stdout, Color::Red)?;
+    write!(stdout, ":{}:{}: ", smell.line, smell.column)?;
+
+    intense_color(stdout, Color::Green)?;
+    write!(stdout, "{}", smell.kind)?;
+
+    if let Some(text) = &smell.text {
+        color(stdout, Color::White)?;
+        write!(stdout, " {text}")?;
+    }
+
+    color(stdout, Color::Blue)?;
+    write!(stdout, " in {} ", smell.space_kind)?;
+
+    intense_color(stdout, Color::Cyan)?;
+    write!(
+        stdout,
+        "{}",
+        smell.space_name.as_ref().map_or("", |name| name)
+    )?;
+
+    intense_color(stdout, Color::Red)?;
+    writeln!(stdout, " (@{})", smell.space_line)
+}
@@ -1,8 +1,26 @@
 pub(crate) mod dump;
 pub use dump::*;
 
+pub(crate) mod dump_clones;
+pub use dump_clones::*;
+
+pub(crate) mod dump_debt;
+pub use dump_debt::*;
+
+pub(crate) mod dump_imports;
+pub use dump_imports::*;
+
+pub(crate) mod dump_increments;
+pub use dump_increments::*;
+
 pub(crate) mod dump_metrics;
 pub use dump_metrics::*;
 
+pub(crate) mod dump_naming;
+pub use dump_naming::*;
+
 pub(crate) mod dump_ops;
 pub use dump_ops::*;
+
+pub(crate) mod dump_smells;
+pub use dump_smells::*;
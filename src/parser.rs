@@ -3,17 +3,29 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::abc::Abc;
+use crate::booleans::Booleans;
 use crate::checker::Checker;
 use crate::cognitive::Cognitive;
 use crate::cyclomatic::Cyclomatic;
+use crate::doc_coverage::DocCoverage;
+use crate::document::InputEdit;
+use crate::error::Error;
+use crate::exceptions::Exceptions;
 use crate::exit::Exit;
 use crate::halstead::Halstead;
+use crate::imports::Imports;
+use crate::literals::Literals;
 use crate::loc::Loc;
+use crate::markers::Markers;
 use crate::mi::Mi;
+use crate::naming::NamingRules;
 use crate::nargs::NArgs;
 use crate::nom::Nom;
 use crate::npa::Npa;
 use crate::npm::Npm;
+use crate::safety::Safety;
+use crate::statements::Statements;
+use crate::variables::Variables;
 use crate::wmc::Wmc;
 
 use crate::alterator::Alterator;
@@ -34,9 +46,19 @@ pub struct Parser<
         + Abc
         + Cognitive
         + Cyclomatic
+        + DocCoverage
+        + Exceptions
+        + Literals
+        + Safety
+        + Booleans
+        + Imports
+        + Variables
+        + Statements
+        + NamingRules
         + Exit
         + Halstead
         + Loc
+        + Markers
         + Mi
         + NArgs
         + Nom
@@ -103,9 +125,19 @@ impl<
         + Abc
         + Cognitive
         + Cyclomatic
+        + DocCoverage
+        + Exceptions
+        + Literals
+        + Safety
+        + Booleans
+        + Imports
+        + Variables
+        + Statements
+        + NamingRules
         + Exit
         + Halstead
         + Loc
+        + Markers
         + Mi
         + NArgs
         + Nom
@@ -120,6 +152,16 @@ impl<
     type Cyclomatic = T;
     type Halstead = T;
     type Loc = T;
+    type Markers = T;
+    type DocCoverage = T;
+    type Exceptions = T;
+    type Literals = T;
+    type Safety = T;
+    type Booleans = T;
+    type Imports = T;
+    type Variables = T;
+    type Statements = T;
+    type NamingRules = T;
     type Nom = T;
     type Mi = T;
     type NArgs = T;
@@ -129,7 +171,7 @@ impl<
     type Npm = T;
     type Npa = T;
 
-    fn new(code: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Self {
+    fn new(code: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Result<Self, Error> {
         let fake_code = get_fake_code::<T>(&code, path, pr);
         let code = if let Some(fake) = fake_code {
             fake
@@ -137,13 +179,13 @@ impl<
             code
         };
 
-        let tree = Tree::new::<T>(&code);
+        let tree = Tree::new::<T>(&code).ok_or_else(|| Error::Parse(path.to_path_buf()))?;
 
-        Self {
+        Ok(Self {
             code,
             tree,
             phantom: PhantomData,
-        }
+        })
     }
 
     #[inline(always)]
@@ -190,4 +232,20 @@ impl<
 
         Filter { filters: res }
     }
+
+    fn edit(&mut self, edit: &InputEdit) {
+        self.tree.edit(
+            &mut self.code,
+            edit.start_byte,
+            edit.old_end_byte,
+            edit.text.as_bytes(),
+        );
+    }
+
+    fn reparse(&mut self) -> Option<Vec<(usize, usize)>> {
+        let tree = self.tree.reparse::<T>(&self.code)?;
+        let changed_ranges = self.tree.changed_ranges(&tree);
+        self.tree = tree;
+        Some(changed_ranges)
+    }
 }
@@ -0,0 +1,182 @@
+use tree_sitter::{QueryCursor, StreamingIterator};
+
+use crate::error::Error;
+use crate::langs::LANG;
+use crate::node::Node;
+use crate::traits::*;
+
+/// A tree-sitter query, compiled for a language.
+///
+/// A query is a list of S-expression patterns matching the nodes of a tree,
+/// whose nodes of interest are captured with a name, as `@name`.
+/// The patterns can be restricted with the `#eq?`, `#not-eq?`, `#match?`,
+/// `#not-match?` and `#any-of?` predicates on the text of the captured nodes.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{find_query, ParserTrait, PythonParser, Query, LANG};
+///
+/// let source_code = "if a:\n    f()\nelif b:\n    g()\n";
+/// let path = Path::new("foo.py");
+/// let parser = PythonParser::new(source_code.as_bytes().to_vec(), &path, None).unwrap();
+///
+/// let query = Query::new(
+///     LANG::Python,
+///     "(call function: (identifier) @name (#eq? @name \"g\"))",
+/// )
+/// .unwrap();
+/// let captures = find_query(&parser, &query).unwrap();
+///
+/// assert_eq!(captures.len(), 1);
+/// assert_eq!(captures[0].name, "name");
+/// assert_eq!(captures[0].node.start_position(), (3, 4));
+/// ```
+#[derive(Debug)]
+pub struct Query {
+    query: tree_sitter::Query,
+    lang: LANG,
+}
+
+impl Query {
+    /// Compiles a query for a language.
+    ///
+    /// Returns an error if the query is not valid for the language.
+    pub fn new(lang: LANG, source: &str) -> Result<Self, Error> {
+        tree_sitter::Query::new(&lang.get_ts_language(), source)
+            .map(|query| Self { query, lang })
+            .map_err(|e| Error::InvalidInput(format!("Invalid query for {}: {e}", lang.get_name())))
+    }
+
+    /// Returns the language of a query.
+    pub fn language(&self) -> LANG {
+        self.lang
+    }
+
+    pub(crate) fn ts_query(&self) -> &tree_sitter::Query {
+        &self.query
+    }
+
+    /// Returns the names of the captures of a query.
+    pub fn capture_names(&self) -> &[&str] {
+        self.query.capture_names()
+    }
+}
+
+/// A node captured by a query.
+#[derive(Clone, Debug)]
+pub struct QueryCapture<'a> {
+    /// The name of the capture, without the `@`
+    pub name: String,
+    /// The captured node
+    pub node: Node<'a>,
+}
+
+/// Runs a query on a code.
+///
+/// Returns the captured nodes in the order of the code.
+/// A node is returned once for each pattern capturing it.
+///
+/// Returns an error if the query is not compiled for the language
+/// of the code.
+pub fn find_query<'a, T: ParserTrait>(
+    parser: &'a T,
+    query: &Query,
+) -> Result<Vec<QueryCapture<'a>>, Error> {
+    if query.lang != parser.get_language() {
+        return Err(Error::InvalidInput(format!(
+            "The query is compiled for {} and not for {}",
+            query.lang.get_name(),
+            parser.get_language().get_name()
+        )));
+    }
+
+    let code = parser.get_code();
+    let names = query.capture_names();
+    let mut cursor = QueryCursor::new();
+    let mut captures = cursor.captures(&query.query, parser.get_root().0, code);
+
+    let mut good = Vec::new();
+    while let Some((query_match, index)) = captures.next() {
+        let capture = query_match.captures[*index];
+        good.push(QueryCapture {
+            name: names[capture.index as usize].to_string(),
+            node: Node(capture.node),
+        });
+    }
+    Ok(good)
+}
+
+/// Counts the nodes captured by a query and the number of nodes in a code.
+///
+/// Returns an error if the query is not compiled for the language
+/// of the code.
+pub fn count_query<T: ParserTrait>(parser: &T, query: &Query) -> Result<(usize, usize), Error> {
+    let good = find_query(parser, query)?.len();
+    let total = parser.get_root().preorder().count();
+    Ok((good, total))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{PythonParser, RustParser};
+
+    #[test]
+    fn query_captures() {
+        let source_code = "if a:\n    pass\nelif b:\n    pass\n";
+        let parser = PythonParser::new(source_code.into(), Path::new("foo.py"), None).unwrap();
+
+        // Unlike the `if` filter, only the if statements are found
+        let query =
+            Query::new(LANG::Python, "(if_statement condition: (_) @condition) @if").unwrap();
+        let captures = find_query(&parser, &query).unwrap();
+
+        assert_eq!(
+            captures
+                .iter()
+                .map(|capture| (capture.name.as_str(), capture.node.kind()))
+                .collect::<Vec<_>>(),
+            [("if", "if_statement"), ("condition", "identifier")]
+        );
+        assert_eq!(count_query(&parser, &query).unwrap().0, 2);
+    }
+
+    #[test]
+    fn query_predicates() {
+        let source_code = "fn f() { foo(); bar(); foo_bar(); }";
+        let parser = RustParser::new(source_code.into(), Path::new("foo.rs"), None).unwrap();
+
+        let query = Query::new(
+            LANG::Rust,
+            "(call_expression function: (identifier) @callee (#match? @callee \"^foo\"))",
+        )
+        .unwrap();
+        let captures = find_query(&parser, &query).unwrap();
+
+        assert_eq!(
+            captures
+                .iter()
+                .map(|capture| capture.node.utf8_text(parser.get_code()).unwrap())
+                .collect::<Vec<_>>(),
+            ["foo", "foo_bar"]
+        );
+    }
+
+    #[test]
+    fn query_errors() {
+        assert!(Query::new(LANG::Rust, "(call_expression").is_err());
+        assert!(Query::new(LANG::Rust, "(foo_statement)").is_err());
+
+        let parser = PythonParser::new(b"f()".to_vec(), Path::new("foo.py"), None).unwrap();
+        let query = Query::new(LANG::Rust, "(call_expression) @call").unwrap();
+        assert_eq!(
+            find_query(&parser, &query).unwrap_err().to_string(),
+            "The query is compiled for rust and not for python"
+        );
+    }
+}
@@ -0,0 +1,636 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use similar::TextDiff;
+use tree_sitter::{QueryCursor, StreamingIterator};
+
+use crate::checker::Checker;
+use crate::document::InputEdit;
+use crate::error::Error;
+use crate::langs::LANG;
+use crate::node::{Node, Tree};
+use crate::query::Query;
+use crate::tools::*;
+use crate::traits::*;
+
+// The prefix of the identifiers replacing the metavariables of a pattern
+const METAVAR_PREFIX: &str = "__rca_metavar_";
+
+// The name of the capture of the nodes to rewrite in a query
+const MATCH_CAPTURE: &str = "match";
+
+// The name of the metavariable written `$$`, which stands for a `$`
+const ESCAPED_SIGIL: &str = "$";
+
+/// How the code to rewrite is matched.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RewriteMatcher {
+    /// A tree-sitter query, capturing the nodes to rewrite with `@match`.
+    ///
+    /// The other captures are the metavariables of the template.
+    Query(String),
+    /// A code written in the language of the rewritten code,
+    /// as `foo($A, $B)`, where `$A` and `$B` are metavariables
+    /// matching any node.
+    ///
+    /// A metavariable used more than once matches the same text.
+    /// A `$` which does not start a metavariable, as in the JavaScript
+    /// identifier `$el`, is written `$$`.
+    Pattern(String),
+}
+
+/// A structural search-and-replace rule.
+///
+/// The code matched by the matcher is replaced by the template, where
+/// the `$NAME` metavariables are replaced by the text of the nodes they
+/// have matched. A metavariable name contains letters, digits and `_`,
+/// and `$$` is replaced by `$`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RewriteRule {
+    /// The matcher of the code to rewrite
+    pub matcher: RewriteMatcher,
+    /// The template of the new code
+    pub template: String,
+}
+
+// A pattern parsed in the language of the rewritten code
+#[derive(Debug)]
+struct Pattern {
+    code: Vec<u8>,
+    tree: Tree,
+    // The position of the first node of the pattern in the pre-order
+    // traversal of the tree
+    index: usize,
+}
+
+impl Pattern {
+    fn root(&self) -> Node<'_> {
+        self.tree.get_root().preorder().nth(self.index).unwrap()
+    }
+}
+
+#[derive(Debug)]
+enum Matcher {
+    Query(Query),
+    Pattern(Pattern),
+}
+
+/// A [`RewriteRule`] compiled for a language.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{
+///     apply_edits, rewrite, ParserTrait, PythonParser, RewriteMatcher, RewriteRule, Rewriter,
+///     LANG,
+/// };
+///
+/// let source_code = "x = foo(a, b)\ny = foo(c, c)\n";
+/// let path = Path::new("foo.py");
+/// let parser = PythonParser::new(source_code.as_bytes().to_vec(), &path, None).unwrap();
+///
+/// let rule = RewriteRule {
+///     matcher: RewriteMatcher::Pattern("foo($A, $B)".to_string()),
+///     template: "bar($B, $A)".to_string(),
+/// };
+/// let rewriter = Rewriter::new(LANG::Python, &rule).unwrap();
+///
+/// let edits = rewrite(&parser, &rewriter).unwrap();
+/// let new_code = apply_edits(parser.get_code(), &edits).unwrap();
+///
+/// assert_eq!(new_code, b"x = bar(b, a)\ny = bar(c, c)\n");
+/// ```
+///
+/// [`RewriteRule`]: struct.RewriteRule.html
+#[derive(Debug)]
+pub struct Rewriter {
+    matcher: Matcher,
+    template: String,
+    lang: LANG,
+}
+
+impl Rewriter {
+    /// Compiles a rule for a language.
+    ///
+    /// Returns an error if the query or the pattern is not valid for
+    /// the language, or if the template uses an unknown metavariable.
+    pub fn new(lang: LANG, rule: &RewriteRule) -> Result<Self, Error> {
+        let (matcher, names) = match &rule.matcher {
+            RewriteMatcher::Query(query) => {
+                let query = Query::new(lang, query)?;
+                if !query.capture_names().contains(&MATCH_CAPTURE) {
+                    return Err(Error::InvalidInput(format!(
+                        "The query must capture the nodes to rewrite with @{MATCH_CAPTURE}"
+                    )));
+                }
+                let names = query
+                    .capture_names()
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect::<Vec<_>>();
+                (Matcher::Query(query), names)
+            }
+            RewriteMatcher::Pattern(pattern) => {
+                let names = metavariables(pattern.as_bytes())
+                    .into_iter()
+                    .filter(|(_, name)| *name != ESCAPED_SIGIL)
+                    .map(|(_, name)| name.to_string())
+                    .collect::<Vec<_>>();
+                (Matcher::Pattern(parse_pattern(lang, pattern)?), names)
+            }
+        };
+
+        if let Some((_, name)) = metavariables(rule.template.as_bytes())
+            .into_iter()
+            .find(|(_, name)| *name != ESCAPED_SIGIL && !names.iter().any(|n| n == name))
+        {
+            return Err(Error::InvalidInput(format!(
+                "Unknown metavariable `${name}` in the template"
+            )));
+        }
+
+        Ok(Self {
+            matcher,
+            template: rule.template.clone(),
+            lang,
+        })
+    }
+
+    /// Returns the language of a rewriter.
+    pub fn language(&self) -> LANG {
+        self.lang
+    }
+}
+
+// Returns the positions and the names of the metavariables of a text,
+// where the escaped `$` is a metavariable named `$`
+fn metavariables(text: &[u8]) -> Vec<(usize, &str)> {
+    let mut metavars = Vec::new();
+    let mut pos = 0;
+    while let Some(start) = text[pos..].iter().position(|c| *c == b'$') {
+        let start = pos + start;
+        let len = if text.get(start + 1) == Some(&b'$') {
+            1
+        } else {
+            text[start + 1..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || **c == b'_')
+                .count()
+        };
+        if len != 0 {
+            // The name is made of ASCII characters
+            let name = std::str::from_utf8(&text[start + 1..start + 1 + len]).unwrap();
+            metavars.push((start, name));
+        }
+        pos = start + 1 + len;
+    }
+    metavars
+}
+
+// Replaces the metavariables of a text, using the given function,
+// and the escaped `$` by `$`
+fn expand<'a>(text: &'a str, mut value: impl FnMut(&'a str) -> String) -> String {
+    let mut expanded = String::with_capacity(text.len());
+    let mut pos = 0;
+    for (start, name) in metavariables(text.as_bytes()) {
+        expanded.push_str(&text[pos..start]);
+        if name == ESCAPED_SIGIL {
+            expanded.push_str(ESCAPED_SIGIL);
+        } else {
+            expanded.push_str(&value(name));
+        }
+        pos = start + 1 + name.len();
+    }
+    expanded.push_str(&text[pos..]);
+    expanded
+}
+
+fn parse_pattern(lang: LANG, pattern: &str) -> Result<Pattern, Error> {
+    let code = expand(pattern, |name| format!("{METAVAR_PREFIX}{name}"));
+    let text = code.trim();
+
+    // An expression is not a valid statement in some languages without `;`
+    for code in [code.clone(), format!("{code};")] {
+        let code = code.into_bytes();
+        let Some(tree) = Tree::with_language(&code, lang) else {
+            continue;
+        };
+        let root = tree.get_root();
+        if root.has_error() {
+            continue;
+        }
+
+        // Skip the nodes wrapping the pattern, as the module of a Python code
+        let has_text =
+            |node: &Node| node.is_named() && node.utf8_text(&code).map(str::trim) == Some(text);
+        let Some(mut index) = root.preorder().position(|node| has_text(&node)) else {
+            continue;
+        };
+        let mut node = root.preorder().nth(index).unwrap();
+        while node.child_count() == 1 && has_text(&node.child(0).unwrap()) {
+            node = node.child(0).unwrap();
+            index += 1;
+        }
+
+        return Ok(Pattern { code, tree, index });
+    }
+
+    Err(Error::InvalidInput(format!(
+        "The pattern `{pattern}` is not valid {} code",
+        lang.get_name()
+    )))
+}
+
+// Matches a node against a pattern, binding the metavariables to the text
+// of the matched nodes
+fn match_pattern<'a, T: ParserTrait>(
+    pattern: &Node,
+    pattern_code: &[u8],
+    node: &Node,
+    code: &'a [u8],
+    bindings: &mut HashMap<String, &'a [u8]>,
+) -> bool {
+    let text = &code[node.byte_range()];
+    let pattern_text = &pattern_code[pattern.byte_range()];
+
+    let metavar = pattern_text
+        .strip_prefix(METAVAR_PREFIX.as_bytes())
+        .filter(|name| {
+            !name.is_empty() && name.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'_')
+        });
+    if let Some(name) = metavar {
+        let name = String::from_utf8_lossy(name).into_owned();
+        return *bindings.entry(name).or_insert(text) == text;
+    }
+
+    if pattern.kind_id() != node.kind_id() {
+        return false;
+    }
+
+    let pattern_children = pattern
+        .children()
+        .filter(|child| !T::Checker::is_comment(child))
+        .collect::<Vec<_>>();
+    let children = node
+        .children()
+        .filter(|child| !T::Checker::is_comment(child))
+        .collect::<Vec<_>>();
+
+    if pattern_children.is_empty() {
+        return children.is_empty() && pattern_text == text;
+    }
+
+    pattern_children.len() == children.len()
+        && pattern_children
+            .iter()
+            .zip(children.iter())
+            .all(|(pattern, node)| match_pattern::<T>(pattern, pattern_code, node, code, bindings))
+}
+
+/// Finds the code matched by a rewriter and returns the edits
+/// replacing it by the template of the rewriter.
+///
+/// The edits are sorted and do not overlap: a match nested
+/// in another match is not rewritten.
+///
+/// Returns an error if the rewriter is not compiled for the language
+/// of the code.
+pub fn rewrite<T: ParserTrait>(parser: &T, rewriter: &Rewriter) -> Result<Vec<InputEdit>, Error> {
+    if rewriter.lang != parser.get_language() {
+        return Err(Error::InvalidInput(format!(
+            "The rewriter is compiled for {} and not for {}",
+            rewriter.lang.get_name(),
+            parser.get_language().get_name()
+        )));
+    }
+
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let mut edits = Vec::new();
+
+    match &rewriter.matcher {
+        Matcher::Query(query) => {
+            let names = query.capture_names();
+            let mut cursor = QueryCursor::new();
+            let mut matches = cursor.matches(query.ts_query(), root.0, code);
+            while let Some(query_match) = matches.next() {
+                let mut bindings = HashMap::new();
+                let mut matched = None;
+                for capture in query_match.captures {
+                    let name = names[capture.index as usize];
+                    if name == MATCH_CAPTURE {
+                        matched = Some(Node(capture.node));
+                    }
+                    bindings
+                        .entry(name)
+                        .or_insert(&code[capture.node.byte_range()]);
+                }
+                if let Some(node) = matched {
+                    let text = expand(&rewriter.template, |name| {
+                        bindings
+                            .get(name)
+                            .map(|text| String::from_utf8_lossy(text).into_owned())
+                            .unwrap_or_default()
+                    });
+                    edits.push(InputEdit {
+                        start_byte: node.start_byte(),
+                        old_end_byte: node.end_byte(),
+                        text,
+                    });
+                }
+            }
+            // The matches are sorted by their first capture
+            edits.sort_by_key(|edit| (edit.start_byte, std::cmp::Reverse(edit.old_end_byte)));
+        }
+        Matcher::Pattern(pattern) => {
+            let pattern_root = pattern.root();
+            for node in root.preorder() {
+                let mut bindings = HashMap::new();
+                if match_pattern::<T>(&pattern_root, &pattern.code, &node, code, &mut bindings) {
+                    let text = expand(&rewriter.template, |name| {
+                        bindings
+                            .get(name)
+                            .map(|text| String::from_utf8_lossy(text).into_owned())
+                            .unwrap_or_default()
+                    });
+                    edits.push(InputEdit {
+                        start_byte: node.start_byte(),
+                        old_end_byte: node.end_byte(),
+                        text,
+                    });
+                }
+            }
+        }
+    }
+
+    // Remove the matches nested in a previous one
+    let mut end = 0;
+    edits.retain(|edit| {
+        let keep = edit.start_byte >= end;
+        if keep {
+            end = edit.old_end_byte;
+        }
+        keep
+    });
+
+    Ok(edits)
+}
+
+/// Applies some edits to a code and returns the new code.
+///
+/// Unlike the edits of a [`Document`], the bytes of all the edits
+/// refer to the original code, so the edits must not overlap.
+///
+/// Returns an error if an edit is out of the code or overlaps another edit.
+///
+/// [`Document`]: struct.Document.html
+pub fn apply_edits(code: &[u8], edits: &[InputEdit]) -> Result<Vec<u8>, Error> {
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|edit| (edit.start_byte, edit.old_end_byte));
+
+    let mut new_code = Vec::with_capacity(code.len());
+    let mut code_start = 0;
+    for edit in edits {
+        if edit.start_byte > edit.old_end_byte || edit.old_end_byte > code.len() {
+            return Err(Error::InvalidInput(format!(
+                "The edit {}..{} is out of the code of {} bytes",
+                edit.start_byte,
+                edit.old_end_byte,
+                code.len()
+            )));
+        }
+        if edit.start_byte < code_start {
+            return Err(Error::InvalidInput(format!(
+                "The edit {}..{} overlaps another edit",
+                edit.start_byte, edit.old_end_byte
+            )));
+        }
+        new_code.extend(&code[code_start..edit.start_byte]);
+        new_code.extend(edit.text.as_bytes());
+        code_start = edit.old_end_byte;
+    }
+    if code_start < code.len() {
+        new_code.extend(&code[code_start..]);
+    }
+    Ok(new_code)
+}
+
+/// Returns the unified diff between two versions of a file.
+pub fn unified_diff(path: &Path, old_code: &[u8], new_code: &[u8]) -> String {
+    let path = path.to_string_lossy();
+    let path = path.trim_start_matches('/');
+    let old_code = String::from_utf8_lossy(old_code);
+    let new_code = String::from_utf8_lossy(new_code);
+    TextDiff::from_lines(old_code.as_ref(), new_code.as_ref())
+        .unified_diff()
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+/// Configuration options for rewriting a code.
+///
+/// The edits are applied to the code of the parser, so the parser
+/// must be created without the preprocessor data, which masks the macros.
+#[derive(Debug)]
+pub struct RewriteCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+    /// The rule applied to the code
+    pub rule: RewriteRule,
+    /// If `true`, the rewritten code is saved on the file,
+    /// otherwise the unified diff of the changes is printed
+    pub in_place: bool,
+}
+
+pub struct Rewrite {
+    _guard: (),
+}
+
+impl Callback for Rewrite {
+    type Res = Result<(), Error>;
+    type Cfg = RewriteCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        let rewriter = Rewriter::new(parser.get_language(), &cfg.rule)?;
+        let edits = rewrite(parser, &rewriter)?;
+        if edits.is_empty() {
+            return Ok(());
+        }
+
+        let new_code = apply_edits(parser.get_code(), &edits)?;
+        if cfg.in_place {
+            write_file(&cfg.path, &new_code)?;
+        } else {
+            let diff = unified_diff(&cfg.path, parser.get_code(), &new_code);
+            io::stdout().write_all(diff.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::{JavascriptParser, PythonParser, RustParser};
+
+    use super::*;
+
+    fn check_rewrite<T: ParserTrait>(
+        source: &str,
+        filename: &str,
+        matcher: RewriteMatcher,
+        template: &str,
+        expected: &str,
+    ) {
+        let path = Path::new(filename);
+        let parser = T::new(source.as_bytes().to_vec(), path, None).unwrap();
+        let rule = RewriteRule {
+            matcher,
+            template: template.to_string(),
+        };
+        let rewriter = Rewriter::new(parser.get_language(), &rule).unwrap();
+        let edits = rewrite(&parser, &rewriter).unwrap();
+
+        assert_eq!(
+            String::from_utf8(apply_edits(parser.get_code(), &edits).unwrap()).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn rewrite_pattern() {
+        check_rewrite::<RustParser>(
+            "fn f() {\n    let a = x.unwrap();\n    let b = y.z().unwrap();\n}\n",
+            "foo.rs",
+            RewriteMatcher::Pattern("$A.unwrap()".to_string()),
+            "$A?",
+            "fn f() {\n    let a = x?;\n    let b = y.z()?;\n}\n",
+        );
+    }
+
+    #[test]
+    fn rewrite_pattern_same_metavariable() {
+        // Only the comparisons of a value with itself are matched
+        check_rewrite::<JavascriptParser>(
+            "if (a === a || a === b) {}",
+            "foo.js",
+            RewriteMatcher::Pattern("$X === $X".to_string()),
+            "Number.isNaN($X)",
+            "if (Number.isNaN(a) || a === b) {}",
+        );
+    }
+
+    #[test]
+    fn rewrite_pattern_escaped_sigil() {
+        // `$$el` is the identifier `$el` and not a metavariable
+        check_rewrite::<JavascriptParser>(
+            "let a = $el.find(x);\nlet b = el.find(y);\n",
+            "foo.js",
+            RewriteMatcher::Pattern("$$el.find($X)".to_string()),
+            "$$($X)",
+            "let a = $(x);\nlet b = el.find(y);\n",
+        );
+    }
+
+    #[test]
+    fn rewrite_pattern_nested() {
+        // Only the outer call is rewritten
+        check_rewrite::<PythonParser>(
+            "x = foo(foo(1))\n",
+            "foo.py",
+            RewriteMatcher::Pattern("foo($A)".to_string()),
+            "bar($A)",
+            "x = bar(foo(1))\n",
+        );
+    }
+
+    #[test]
+    fn rewrite_query() {
+        check_rewrite::<PythonParser>(
+            "print(a)\nlog(b)\nprint(c, d)\n",
+            "foo.py",
+            RewriteMatcher::Query(
+                "(call function: (identifier) @f (#eq? @f \"print\") arguments: (argument_list (_) @arg .)) @match"
+                    .to_string(),
+            ),
+            "logger.info($arg)",
+            "logger.info(a)\nlog(b)\nlogger.info(d)\n",
+        );
+    }
+
+    #[test]
+    fn rewrite_errors() {
+        let rule = |matcher, template: &str| RewriteRule {
+            matcher,
+            template: template.to_string(),
+        };
+
+        assert_eq!(
+            Rewriter::new(
+                LANG::Python,
+                &rule(RewriteMatcher::Pattern("foo($A)".to_string()), "bar($B)")
+            )
+            .unwrap_err()
+            .to_string(),
+            "Unknown metavariable `$B` in the template"
+        );
+        assert_eq!(
+            Rewriter::new(
+                LANG::Python,
+                &rule(RewriteMatcher::Query("(call) @call".to_string()), "")
+            )
+            .unwrap_err()
+            .to_string(),
+            "The query must capture the nodes to rewrite with @match"
+        );
+        assert!(
+            Rewriter::new(
+                LANG::Python,
+                &rule(RewriteMatcher::Pattern("foo(".to_string()), "")
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn apply_edits_errors() {
+        let edit = |start_byte, old_end_byte| InputEdit {
+            start_byte,
+            old_end_byte,
+            text: String::new(),
+        };
+
+        assert_eq!(
+            apply_edits(b"abcdef", &[edit(4, 6), edit(0, 2)]).unwrap(),
+            b"cd"
+        );
+        assert_eq!(
+            apply_edits(b"abcdef", &[edit(0, 3), edit(2, 4)])
+                .unwrap_err()
+                .to_string(),
+            "The edit 2..4 overlaps another edit"
+        );
+        assert_eq!(
+            apply_edits(b"abcdef", &[edit(4, 8)])
+                .unwrap_err()
+                .to_string(),
+            "The edit 4..8 is out of the code of 6 bytes"
+        );
+    }
+
+    #[test]
+    fn rewrite_diff() {
+        let diff = unified_diff(Path::new("foo.py"), b"a = 1\nb = 2\n", b"a = 1\nb = 3\n");
+
+        assert_eq!(
+            diff,
+            "--- a/foo.py\n+++ b/foo.py\n@@ -1,2 +1,2 @@\n a = 1\n-b = 2\n+b = 3\n"
+        );
+    }
+}
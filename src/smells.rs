@@ -0,0 +1,311 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use crate::error::Error;
+use crate::exceptions::{Exception, Exceptions};
+use crate::literals::{HardCoded, hard_coded};
+use crate::node::Node;
+use crate::spaces::{SpaceKind, WalkedSpace, walk_spaces};
+
+use crate::dump_smells::*;
+use crate::traits::*;
+
+/// The numbers which are not reported as magic numbers by default.
+pub const DEFAULT_ALLOWED_NUMBERS: &[f64] = &[-1., 0., 1., 2.];
+
+/// The kind of a code smell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmellKind {
+    /// A handler catching every exception,
+    /// as `catch (Exception e)`, bare `except:` or `catch (...)`
+    CatchAll,
+    /// A handler with an empty body
+    EmptyCatch,
+    /// A numeric literal which is not in the list of allowed numbers
+    MagicNumber,
+    /// A string literal repeated in the same space
+    RepeatedString,
+}
+
+impl fmt::Display for SmellKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            SmellKind::CatchAll => "catch_all",
+            SmellKind::EmptyCatch => "empty_catch",
+            SmellKind::MagicNumber => "magic_number",
+            SmellKind::RepeatedString => "repeated_string",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// A code smell found in a code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Smell {
+    /// The kind of the smell
+    pub kind: SmellKind,
+    /// The first line of the code containing the smell
+    pub line: usize,
+    /// The column of the code containing the smell
+    pub column: usize,
+    /// The code of the smell, for the smells about literals
+    pub text: Option<String>,
+    /// The name of the space containing the smell
+    pub space_name: Option<String>,
+    /// The first line of the space containing the smell
+    pub space_line: usize,
+    /// The kind of the space containing the smell
+    pub space_kind: SpaceKind,
+}
+
+/// All code smells of a code.
+#[derive(Debug, Clone, Serialize)]
+pub struct Smells {
+    /// The name of the analyzed file
+    pub name: Option<String>,
+    /// The smells, in order of appearance
+    pub smells: Vec<Smell>,
+}
+
+/// Retrieves all code smells of a code.
+///
+/// The numeric literals contained in `allowed_numbers` are not
+/// reported as magic numbers.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::PathBuf;
+///
+/// use rust_code_analysis::{smells, JavaParser, ParserTrait, SmellKind, DEFAULT_ALLOWED_NUMBERS};
+///
+/// let source_code = "class A { void f() { try { g(); } catch (Exception e) { } } }";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = PathBuf::from("A.java");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// // The parser of the code, in this case a Java parser
+/// let parser = JavaParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Retrieve all smells
+/// let smells = smells(&parser, &path, DEFAULT_ALLOWED_NUMBERS);
+///
+/// assert_eq!(smells.smells[0].kind, SmellKind::CatchAll);
+/// assert_eq!(smells.smells[1].kind, SmellKind::EmptyCatch);
+/// ```
+pub fn smells<T: ParserTrait>(parser: &T, path: &Path, allowed_numbers: &[f64]) -> Smells {
+    let code = parser.get_code();
+    let mut smells = Vec::new();
+    let mut strings: HashMap<(Option<usize>, &str), Vec<Node>> = HashMap::new();
+
+    let spaces = walk_spaces(parser, path, |node, space, spaces| {
+        if let Some(Exception::Catch { catch_all, empty }) = T::Exceptions::exception(node, code) {
+            let space = space.map(|i| &spaces[i]);
+            if catch_all {
+                smells.push(new_smell(SmellKind::CatchAll, node, None, space));
+            }
+            if empty {
+                smells.push(new_smell(SmellKind::EmptyCatch, node, None, space));
+            }
+        }
+
+        match hard_coded::<T::Literals>(node, code, allowed_numbers) {
+            Some(HardCoded::Number(number)) => {
+                let text = number.utf8_text(code);
+                let space = space.map(|i| &spaces[i]);
+                smells.push(new_smell(SmellKind::MagicNumber, &number, text, space));
+            }
+            Some(HardCoded::String(string)) => {
+                if let Some(text) = string.utf8_text(code) {
+                    strings.entry((space, text)).or_default().push(string);
+                }
+            }
+            None => {}
+        }
+    });
+
+    for ((space, text), nodes) in strings {
+        if nodes.len() > 1 {
+            let space = space.map(|i| &spaces[i]);
+            for node in nodes {
+                smells.push(new_smell(
+                    SmellKind::RepeatedString,
+                    &node,
+                    Some(text),
+                    space,
+                ));
+            }
+        }
+    }
+    smells.sort_by_key(|smell| (smell.line, smell.column));
+
+    Smells {
+        name: path.to_str().map(|name| name.to_string()),
+        smells,
+    }
+}
+
+fn new_smell(
+    kind: SmellKind,
+    node: &Node,
+    text: Option<&str>,
+    space: Option<&WalkedSpace>,
+) -> Smell {
+    let (row, column) = node.start_position();
+    Smell {
+        kind,
+        line: row + 1,
+        column: column + 1,
+        text: text.map(|text| text.to_string()),
+        space_name: space.and_then(|s| s.name.clone()),
+        space_line: space.map_or(0, |s| s.line),
+        space_kind: space.map_or(SpaceKind::Unknown, |s| s.kind),
+    }
+}
+
+/// Configuration options for retrieving the code smells of a code.
+#[derive(Debug)]
+pub struct SmellsCfg {
+    /// Path to the file containing the code
+    pub path: PathBuf,
+    /// The numbers which are not reported as magic numbers
+    pub allowed_numbers: Vec<f64>,
+}
+
+pub struct SmellsCode {
+    _guard: (),
+}
+
+impl Callback for SmellsCode {
+    type Res = Result<(), Error>;
+    type Cfg = SmellsCfg;
+
+    fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
+        Ok(dump_smells(&smells(
+            parser,
+            &cfg.path,
+            &cfg.allowed_numbers,
+        ))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{JavaParser, KotlinParser, PythonParser, RustParser, TypescriptParser};
+
+    use super::*;
+
+    fn kinds<T: ParserTrait>(source: &str, file: &str) -> Vec<(SmellKind, usize, Option<String>)> {
+        let path = PathBuf::from(file);
+        let parser = T::new(source.as_bytes().to_vec(), &path, None).unwrap();
+        smells(&parser, &path, DEFAULT_ALLOWED_NUMBERS)
+            .smells
+            .into_iter()
+            .map(|s| (s.kind, s.line, s.space_name))
+            .collect()
+    }
+
+    #[test]
+    fn python_smells() {
+        let smells = kinds::<PythonParser>(
+            "def f():
+    try:
+        g()
+    except ValueError:
+        pass
+    except:
+        log()",
+            "foo.py",
+        );
+        assert_eq!(
+            smells,
+            [
+                (SmellKind::EmptyCatch, 4, Some("f".to_string())),
+                (SmellKind::CatchAll, 6, Some("f".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn typescript_smells() {
+        let smells =
+            kinds::<TypescriptParser>("try { f(); } catch (e) { /* ignored */ }", "foo.ts");
+        assert_eq!(
+            smells,
+            [(SmellKind::EmptyCatch, 1, Some("foo.ts".to_string()))]
+        );
+    }
+
+    #[test]
+    fn java_smells() {
+        let smells = kinds::<JavaParser>(
+            "class A {
+                 void f() {
+                     try { g(); } catch (java.lang.Exception e) { }
+                     try { g(); } catch (IOException e) { log(e); }
+                 }
+             }",
+            "A.java",
+        );
+        assert_eq!(
+            smells,
+            [
+                (SmellKind::CatchAll, 3, Some("f".to_string())),
+                (SmellKind::EmptyCatch, 3, Some("f".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn kotlin_smells() {
+        let smells = kinds::<KotlinParser>(
+            "fun f() {
+                 try {
+                     g()
+                 } catch (e: Throwable) {
+                 }
+             }",
+            "foo.kt",
+        );
+        assert_eq!(
+            smells,
+            [
+                (SmellKind::CatchAll, 4, None),
+                (SmellKind::EmptyCatch, 4, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn literal_smells() {
+        let path = PathBuf::from("foo.rs");
+        let source = "const N: u8 = 12;
+fn f(x: u8) -> &'static str {
+    if x > 12 && x < 100 {
+        return \"high\";
+    }
+    if x == 7 { \"high\" } else { \"low\" }
+}";
+        let parser = RustParser::new(source.as_bytes().to_vec(), &path, None).unwrap();
+        let smells: Vec<_> = smells(&parser, &path, &[0., 100.])
+            .smells
+            .into_iter()
+            .map(|s| (s.kind, s.line, s.column, s.text.unwrap()))
+            .collect();
+        assert_eq!(
+            smells,
+            [
+                (SmellKind::MagicNumber, 3, 12, "12".to_string()),
+                (SmellKind::RepeatedString, 4, 16, "\"high\"".to_string()),
+                (SmellKind::MagicNumber, 6, 13, "7".to_string()),
+                (SmellKind::RepeatedString, 6, 17, "\"high\"".to_string()),
+            ]
+        );
+    }
+}
@@ -1,27 +1,50 @@
 use std::collections::HashMap;
 
 use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
 use std::fmt;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use crate::checker::Checker;
+use crate::custom_metrics::{CustomMetrics, MetricRegistry};
+use crate::debt::MarkerPatterns;
+use crate::document::{CachedSpace, SpaceCache};
+use crate::error::Error;
+use crate::increments::Increments;
+use crate::metric_set::{MetricKind, MetricSet};
+use crate::naming::{NamingConventions, NamingRules, NamingStats};
 use crate::node::Node;
+use crate::syntax_errors::SyntaxErrors;
 
 use crate::abc::{self, Abc};
+use crate::booleans::{self, Booleans};
 use crate::cognitive::{self, Cognitive};
 use crate::cyclomatic::{self, Cyclomatic};
+use crate::doc_coverage::{self, DocCoverage};
+use crate::duplication;
+use crate::exceptions::{self, Exceptions};
 use crate::exit::{self, Exit};
 use crate::getter::Getter;
-use crate::halstead::{self, Halstead, HalsteadMaps};
+use crate::halstead::{self, Halstead, HalsteadMaps, HalsteadTable};
+use crate::imports::{self, Imports};
+use crate::literals::{self, Literals};
 use crate::loc::{self, Loc};
-use crate::mi::{self, Mi};
+use crate::markers::{self, Markers};
+use crate::mi::{self, Mi, MiFormula};
 use crate::nargs::{self, NArgs};
 use crate::nom::{self, Nom};
 use crate::npa::{self, Npa};
 use crate::npm::{self, Npm};
+use crate::safety::{self, Safety};
+use crate::statements::{self, Statements};
+use crate::variables::{self, Variables};
 use crate::wmc::{self, Wmc};
 
 use crate::dump_metrics::*;
+use crate::langs::try_action;
+use crate::smells::DEFAULT_ALLOWED_NUMBERS;
+use crate::tools::{guess_language, read_file};
 use crate::traits::*;
 
 /// The list of supported space kinds.
@@ -67,7 +90,7 @@ impl fmt::Display for SpaceKind {
 }
 
 /// All metrics data.
-#[derive(Default, Debug, Clone, Serialize)]
+#[derive(Default, Debug, Clone)]
 pub struct CodeMetrics {
     /// `NArgs` data
     pub nargs: nargs::Stats,
@@ -87,14 +110,86 @@ pub struct CodeMetrics {
     /// `Abc` data
     pub abc: abc::Stats,
     /// `Wmc` data
-    #[serde(skip_serializing_if = "wmc::Stats::is_disabled")]
     pub wmc: wmc::Stats,
     /// `Npm` data
-    #[serde(skip_serializing_if = "npm::Stats::is_disabled")]
     pub npm: npm::Stats,
     /// `Npa` data
-    #[serde(skip_serializing_if = "npa::Stats::is_disabled")]
     pub npa: npa::Stats,
+    /// `Duplication` data
+    pub duplication: duplication::Stats,
+    /// `Markers` data
+    pub markers: markers::Stats,
+    /// `DocCoverage` data
+    pub doc_coverage: doc_coverage::Stats,
+    /// `Exceptions` data
+    pub exceptions: exceptions::Stats,
+    /// `Literals` data
+    pub literals: literals::Stats,
+    /// `Safety` data
+    pub safety: safety::Stats,
+    /// `Booleans` data
+    pub booleans: booleans::Stats,
+    /// `Imports` data
+    pub imports: imports::Stats,
+    /// `Variables` data
+    pub variables: variables::Stats,
+    /// `Statements` data
+    pub statements: statements::Stats,
+    /// `Naming` data
+    pub naming: NamingStats,
+    /// The data of the metrics registered in a [`MetricRegistry`]
+    ///
+    /// [`MetricRegistry`]: struct.MetricRegistry.html
+    pub custom: CustomMetrics,
+    /// The metrics to serialize, all of them by default
+    pub selection: MetricSet,
+}
+
+impl Serialize for CodeMetrics {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = MetricKind::ALL
+            .iter()
+            .filter(|kind| self.is_serialized(**kind))
+            .count();
+        let mut st = serializer.serialize_struct("CodeMetrics", len)?;
+        for kind in MetricKind::ALL {
+            let name = kind.name();
+            if !self.is_serialized(*kind) {
+                st.skip_field(name)?;
+                continue;
+            }
+            match kind {
+                MetricKind::NArgs => st.serialize_field(name, &self.nargs),
+                MetricKind::NExits => st.serialize_field(name, &self.nexits),
+                MetricKind::Cognitive => st.serialize_field(name, &self.cognitive),
+                MetricKind::Cyclomatic => st.serialize_field(name, &self.cyclomatic),
+                MetricKind::Halstead => st.serialize_field(name, &self.halstead),
+                MetricKind::Loc => st.serialize_field(name, &self.loc),
+                MetricKind::Nom => st.serialize_field(name, &self.nom),
+                MetricKind::Mi => st.serialize_field(name, &self.mi),
+                MetricKind::Abc => st.serialize_field(name, &self.abc),
+                MetricKind::Wmc => st.serialize_field(name, &self.wmc),
+                MetricKind::Npm => st.serialize_field(name, &self.npm),
+                MetricKind::Npa => st.serialize_field(name, &self.npa),
+                MetricKind::Duplication => st.serialize_field(name, &self.duplication),
+                MetricKind::Markers => st.serialize_field(name, &self.markers),
+                MetricKind::DocCoverage => st.serialize_field(name, &self.doc_coverage),
+                MetricKind::Exceptions => st.serialize_field(name, &self.exceptions),
+                MetricKind::Literals => st.serialize_field(name, &self.literals),
+                MetricKind::Safety => st.serialize_field(name, &self.safety),
+                MetricKind::Booleans => st.serialize_field(name, &self.booleans),
+                MetricKind::Imports => st.serialize_field(name, &self.imports),
+                MetricKind::Variables => st.serialize_field(name, &self.variables),
+                MetricKind::Statements => st.serialize_field(name, &self.statements),
+                MetricKind::Naming => st.serialize_field(name, &self.naming),
+                MetricKind::Custom => st.serialize_field(name, &self.custom),
+            }?;
+        }
+        st.end()
+    }
 }
 
 impl fmt::Display for CodeMetrics {
@@ -111,6 +206,20 @@ impl fmt::Display for CodeMetrics {
 }
 
 impl CodeMetrics {
+    // Checks whether a metric is selected and has data for the language
+    pub(crate) fn is_serialized(&self, kind: MetricKind) -> bool {
+        self.selection.contains(kind)
+            && match kind {
+                MetricKind::Wmc => !self.wmc.is_disabled(),
+                MetricKind::Npm => !self.npm.is_disabled(),
+                MetricKind::Npa => !self.npa.is_disabled(),
+                MetricKind::Duplication => !self.duplication.is_disabled(),
+                MetricKind::Safety => !self.safety.is_disabled(),
+                MetricKind::Custom => !self.custom.is_empty(),
+                _ => true,
+            }
+    }
+
     pub fn merge(&mut self, other: &CodeMetrics) {
         self.cognitive.merge(&other.cognitive);
         self.cyclomatic.merge(&other.cyclomatic);
@@ -124,7 +233,87 @@ impl CodeMetrics {
         self.wmc.merge(&other.wmc);
         self.npm.merge(&other.npm);
         self.npa.merge(&other.npa);
+        self.markers.merge(&other.markers);
+        self.doc_coverage.merge(&other.doc_coverage);
+        self.exceptions.merge(&other.exceptions);
+        self.literals.merge(&other.literals);
+        self.safety.merge(&other.safety);
+        self.booleans.merge(&other.booleans);
+        self.imports.merge(&other.imports);
+        self.variables.merge(&other.variables);
+        self.statements.merge(&other.statements);
+        self.naming.merge(&other.naming);
+        self.custom.merge(&other.custom);
+    }
+}
+
+/// The position of some code in a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CodeRange {
+    /// The first line, starting from 1
+    pub start_line: usize,
+    /// The last line
+    pub end_line: usize,
+    /// The column of the first byte, starting from 1
+    pub start_column: usize,
+    /// The column following the last byte
+    pub end_column: usize,
+    /// The offset of the first byte, starting from 0
+    pub start_byte: usize,
+    /// The offset following the last byte
+    pub end_byte: usize,
+}
+
+impl CodeRange {
+    pub(crate) fn new(node: &Node) -> Self {
+        let (start_row, start_column) = node.start_position();
+        let (end_row, end_column) = node.end_position();
+        Self {
+            start_line: start_row + 1,
+            end_line: end_row + 1,
+            start_column: start_column + 1,
+            end_column: end_column + 1,
+            start_byte: node.start_byte(),
+            end_byte: node.end_byte(),
+        }
+    }
+
+    // Moves a range by `lines` lines and `bytes` bytes
+    pub(crate) fn shift(&mut self, lines: isize, bytes: isize) {
+        self.start_line = self.start_line.saturating_add_signed(lines);
+        self.end_line = self.end_line.saturating_add_signed(lines);
+        self.start_byte = self.start_byte.saturating_add_signed(bytes);
+        self.end_byte = self.end_byte.saturating_add_signed(bytes);
+    }
+}
+
+// Returns the range of a space.
+//
+// A unit ends on the line preceding its final newline,
+// and an empty unit has no lines.
+pub(crate) fn get_space_range(node: &Node, code: &[u8], kind: SpaceKind) -> CodeRange {
+    let mut range = CodeRange::new(node);
+    if kind == SpaceKind::Unit {
+        if node.child_count() == 0 {
+            range.start_line = 0;
+            range.end_line = 0;
+            range.start_column = 0;
+            range.end_column = 0;
+        } else {
+            range.end_line -= 1;
+            range.end_column = get_last_line_end_column(&code[..range.end_byte]);
+        }
     }
+    range
+}
+
+// Returns the column following the last line of a code, final newline excluded
+fn get_last_line_end_column(code: &[u8]) -> usize {
+    let line_end = code.iter().rposition(|c| *c == b'\n').unwrap_or(code.len());
+    let line = &code[..line_end];
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    let line_start = line.iter().rposition(|c| *c == b'\n').map_or(0, |i| i + 1);
+    line.len() - line_start + 1
 }
 
 /// Function space data.
@@ -135,52 +324,298 @@ pub struct FuncSpace {
     /// If `None`, an error is occurred in parsing
     /// the name of a function space
     pub name: Option<String>,
+    /// The fully qualified name of a function space, made of the names
+    /// of the enclosing spaces and namespaces, as `module::Type::method`
+    /// or `package.Class.method`
+    ///
+    /// If `None`, the space is a unit without a package
+    /// or its name has not been found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub qualified_name: Option<String>,
     /// The first line of a function space
     pub start_line: usize,
     /// The last line of a function space
     pub end_line: usize,
+    /// The column of the first byte of a function space, starting from 1
+    pub start_column: usize,
+    /// The column following the last byte of the last line
+    /// of a function space
+    pub end_column: usize,
+    /// The offset of the first byte of a function space, starting from 0
+    pub start_byte: usize,
+    /// The offset following the last byte of a function space
+    pub end_byte: usize,
+    /// The range of the name of a function space
+    ///
+    /// If `None`, the name has not been found in the code
+    pub name_range: Option<CodeRange>,
     /// The space kind
     pub kind: SpaceKind,
     /// All subspaces contained in a function space
     pub spaces: Vec<FuncSpace>,
     /// All metrics of a function space
     pub metrics: CodeMetrics,
+    /// The syntax errors of a function space
+    ///
+    /// Not serialized when the space and its subspaces have no errors
+    #[serde(skip_serializing_if = "SyntaxErrors::is_empty")]
+    pub syntax_errors: SyntaxErrors,
+    /// The increments of the complexity metrics of a function space
+    ///
+    /// If `None`, the increments have not been recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub increments: Option<Increments>,
 }
 
 impl FuncSpace {
-    fn new<T: Getter>(node: &Node, code: &[u8], kind: SpaceKind) -> Self {
-        let (start_position, end_position) = match kind {
-            SpaceKind::Unit => {
-                if node.child_count() == 0 {
-                    (0, 0)
-                } else {
-                    (node.start_row() + 1, node.end_row())
-                }
-            }
-            _ => (node.start_row() + 1, node.end_row() + 1),
-        };
+    fn new<T: ParserTrait>(
+        node: &Node,
+        code: &[u8],
+        kind: SpaceKind,
+        names: &mut QualifiedNames,
+    ) -> Self {
+        let range = get_space_range(node, code, kind);
+        let (name, name_range) = get_space_name::<T::Getter>(node, code);
 
         Self {
-            name: T::get_func_space_name(node, code)
-                .map(|name| name.split_whitespace().collect::<Vec<_>>().join(" ")),
+            name,
+            qualified_name: names.get::<T>(node, code),
             spaces: Vec::new(),
             metrics: CodeMetrics::default(),
+            syntax_errors: SyntaxErrors::default(),
+            increments: None,
             kind,
-            start_line: start_position,
-            end_line: end_position,
+            start_line: range.start_line,
+            end_line: range.end_line,
+            start_column: range.start_column,
+            end_column: range.end_column,
+            start_byte: range.start_byte,
+            end_byte: range.end_byte,
+            name_range,
+        }
+    }
+
+    // Moves a space and its subspaces by `lines` lines and `bytes` bytes.
+    //
+    // The custom metrics are not moved, since their stats are opaque:
+    // the spaces containing them are not reused when they are moved.
+    pub(crate) fn shift(&mut self, lines: isize, bytes: isize) {
+        self.start_line = self.start_line.saturating_add_signed(lines);
+        self.end_line = self.end_line.saturating_add_signed(lines);
+        self.start_byte = self.start_byte.saturating_add_signed(bytes);
+        self.end_byte = self.end_byte.saturating_add_signed(bytes);
+        if let Some(name_range) = self.name_range.as_mut() {
+            name_range.shift(lines, bytes);
+        }
+        self.metrics.loc.shift_lines(lines);
+        self.syntax_errors.shift_lines(lines);
+        if let Some(increments) = self.increments.as_mut() {
+            increments.shift_lines(lines);
+        }
+        for space in self.spaces.iter_mut() {
+            space.shift(lines, bytes);
         }
     }
 }
 
+// Returns the name of a space, with its whitespaces collapsed, and its range
 #[inline(always)]
-fn compute_halstead_mi_and_wmc<T: ParserTrait>(state: &mut State) {
+fn get_space_name<T: Getter>(node: &Node, code: &[u8]) -> (Option<String>, Option<CodeRange>) {
+    let name = T::get_func_space_name(node, code);
+    (
+        name.map(|name| name.split_whitespace().collect::<Vec<_>>().join(" ")),
+        T::get_func_space_name_node(node).map(|name| CodeRange::new(&name)),
+    )
+}
+
+// A space containing the items found by walking a code, as smells or markers
+pub(crate) struct WalkedSpace {
+    pub(crate) name: Option<String>,
+    pub(crate) line: usize,
+    pub(crate) kind: SpaceKind,
+}
+
+// Visits the nodes of a code in order, with the index of the innermost space
+// containing each node in the spaces found so far, and returns all spaces.
+// The first space is the file itself, named by its path.
+pub(crate) fn walk_spaces<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &Path,
+    mut visit: impl FnMut(&Node<'a>, Option<usize>, &[WalkedSpace]),
+) -> Vec<WalkedSpace> {
+    let code = parser.get_code();
+    let root = parser.get_root();
+    let mut spaces = Vec::new();
+    let mut cursor = root.cursor();
+    let mut stack = vec![(root, None)];
+    let mut children = Vec::new();
+
+    while let Some((node, space)) = stack.pop() {
+        let space = if T::Checker::is_func(&node) || T::Checker::is_func_space(&node) {
+            let name = if spaces.is_empty() {
+                path.to_str().map(|name| name.to_string())
+            } else {
+                get_space_name::<T::Getter>(&node, code).0
+            };
+            spaces.push(WalkedSpace {
+                name,
+                line: node.start_row() + 1,
+                kind: T::Getter::get_space_kind(&node),
+            });
+            Some(spaces.len() - 1)
+        } else {
+            space
+        };
+
+        visit(&node, space, &spaces);
+
+        cursor.reset(&node);
+        if cursor.goto_first_child() {
+            loop {
+                children.push((cursor.node(), space));
+                if !cursor.goto_next_sibling() {
+                    break;
+                }
+            }
+            for child in children.drain(..).rev() {
+                stack.push(child);
+            }
+        }
+    }
+
+    spaces
+}
+
+// The qualified names of the spaces of a file
+struct QualifiedNames {
+    // The package or the module of the file
+    prefix: Option<String>,
+    // The number of spaces of the file having each qualified name
+    counts: HashMap<String, usize>,
+}
+
+impl QualifiedNames {
+    fn new<T: ParserTrait>(unit: &Node, code: &[u8], path: &Path, root: Option<&Path>) -> Self {
+        // The path of the file relative to the root of the project,
+        // or its name when there is no root
+        let relative = root
+            .and_then(|root| path.strip_prefix(root).ok())
+            .or_else(|| path.file_name().map(Path::new))
+            .unwrap_or(path);
+        let module: Vec<String> = relative
+            .with_extension("")
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        Self {
+            prefix: T::Getter::get_package_name(unit, code)
+                .map(String::from)
+                .or_else(|| T::Getter::get_module_name(&module)),
+            counts: HashMap::new(),
+        }
+    }
+
+    // Returns the qualified name of a space, joining the names of the
+    // enclosing spaces and namespaces and the package or the module
+    // of the file, and numbered if it is not the first of the file
+    fn get<T: ParserTrait>(&mut self, node: &Node, code: &[u8]) -> Option<String> {
+        let mut names = Vec::new();
+        for ancestor in std::iter::successors(Some(*node), |node| node.parent()) {
+            let name = if T::Getter::get_space_kind(&ancestor) == SpaceKind::Unit {
+                None
+            } else if T::Checker::is_func(&ancestor) || T::Checker::is_func_space(&ancestor) {
+                T::Getter::get_qualified_space_name(&ancestor, code)
+            } else {
+                T::Getter::get_namespace_name(&ancestor, code).map(String::from)
+            };
+            match name {
+                Some(name) => names.push(name),
+                // A space without a name has no qualified name
+                None if ancestor == *node && T::Getter::get_space_kind(node) != SpaceKind::Unit => {
+                    return None;
+                }
+                None => {}
+            }
+        }
+        names.extend(self.prefix.clone());
+        if names.is_empty() {
+            return None;
+        }
+        names.reverse();
+        let name = names.join(T::Getter::get_qualified_name_separator());
+        Some(self.number(name.split_whitespace().collect::<Vec<_>>().join(" ")))
+    }
+
+    // Appends `#n` to the n-th qualified name of the file equal to `name`,
+    // as for overloads and closures, so that the names are unique
+    fn number(&mut self, name: String) -> String {
+        let count = self.counts.entry(name.clone()).or_default();
+        *count += 1;
+        if *count == 1 {
+            name
+        } else {
+            format!("{name}#{count}")
+        }
+    }
+
+    // Renames a reused space, replacing the qualifier of its subspaces
+    // and numbering their names again
+    fn requalify(&mut self, space: &mut FuncSpace, qualified_name: Option<String>) {
+        let old = space.qualified_name.as_deref().map(unnumbered);
+        let new = qualified_name.as_deref().map(unnumbered);
+        let renames = old
+            .zip(new)
+            .map(|(old, new)| (old.to_string(), new.to_string()));
+        space.qualified_name = qualified_name;
+        for space in space.spaces.iter_mut() {
+            self.rename(space, renames.as_ref());
+        }
+    }
+
+    fn rename(&mut self, space: &mut FuncSpace, renames: Option<&(String, String)>) {
+        space.qualified_name = space.qualified_name.as_deref().map(|name| {
+            let name = unnumbered(name);
+            let renamed = renames.and_then(|(old, new)| {
+                name.strip_prefix(old.as_str())
+                    .map(|rest| format!("{new}{rest}"))
+            });
+            self.number(renamed.unwrap_or_else(|| name.to_string()))
+        });
+        for space in space.spaces.iter_mut() {
+            self.rename(space, renames);
+        }
+    }
+}
+
+// Returns a qualified name without its number
+fn unnumbered(name: &str) -> &str {
+    match name.rsplit_once('#') {
+        Some((name, number)) if number.bytes().all(|b| b.is_ascii_digit()) => name,
+        _ => name,
+    }
+}
+
+// Returns the kinds of the ancestors of a node
+fn get_ancestors(node: &Node) -> Vec<u16> {
+    std::iter::successors(node.parent(), |node| node.parent())
+        .map(|node| node.kind_id())
+        .collect()
+}
+
+#[inline(always)]
+fn compute_halstead_mi_and_wmc<T: ParserTrait>(state: &mut State, options: &MetricsOptions) {
     state
         .halstead_maps
         .finalize(&mut state.space.metrics.halstead);
     T::Mi::compute(
         &state.space.metrics.loc,
         &state.space.metrics.cyclomatic,
+        &state.space.metrics.cognitive,
         &state.space.metrics.halstead,
+        options.mi_formula.as_ref(),
         &mut state.space.metrics.mi,
     );
     T::Wmc::compute(
@@ -218,14 +653,61 @@ fn compute_minmax(state: &mut State) {
     state.space.metrics.abc.compute_minmax();
 }
 
+#[inline(always)]
+fn collect_increments(state: &mut State) {
+    let cognitive = state.space.metrics.cognitive.take_increments();
+    let cyclomatic = state.space.metrics.cyclomatic.take_increments();
+    if let (Some(cognitive), Some(cyclomatic)) = (cognitive, cyclomatic) {
+        state.space.increments = Some(Increments {
+            cognitive,
+            cyclomatic,
+        });
+    }
+}
+
 #[inline(always)]
 fn compute_sum(state: &mut State) {
     state.space.metrics.wmc.compute_sum();
     state.space.metrics.npm.compute_sum();
     state.space.metrics.npa.compute_sum();
+    state.space.metrics.markers.compute_sum();
+    state.space.metrics.doc_coverage.compute_sum();
+    state.space.metrics.exceptions.compute_sum();
+    state.space.metrics.literals.compute_sum();
+    state.space.metrics.safety.compute_sum();
+    state.space.metrics.booleans.compute_sum();
+    state.space.metrics.imports.compute_sum();
+    state.space.metrics.variables.compute_sum();
+    state.space.metrics.statements.compute_sum();
+    state.space.metrics.naming.compute_sum();
+    state.space.metrics.custom.finalize();
+}
+
+// Adds a space to its parent space
+fn merge_space<'a, T: ParserTrait>(
+    last_state: &mut State<'a>,
+    space: FuncSpace,
+    halstead_maps: &HalsteadMaps<'a>,
+    options: &MetricsOptions,
+) {
+    last_state.space.syntax_errors.merge(&space.syntax_errors);
+    // The metrics of a space containing syntax errors can be left out
+    if !options.exclude_syntax_errors || space.syntax_errors.errors.is_empty() {
+        last_state.halstead_maps.merge(halstead_maps);
+        compute_halstead_mi_and_wmc::<T>(last_state, options);
+
+        // Merge function spaces
+        last_state.space.metrics.merge(&space.metrics);
+    }
+    last_state.space.spaces.push(space);
 }
 
-fn finalize<T: ParserTrait>(state_stack: &mut Vec<State>, diff_level: usize) {
+fn finalize<T: ParserTrait>(
+    state_stack: &mut Vec<State>,
+    diff_level: usize,
+    options: &MetricsOptions,
+    mut cache: Option<&mut SpaceCache>,
+) {
     if state_stack.is_empty() {
         return;
     }
@@ -234,31 +716,98 @@ fn finalize<T: ParserTrait>(state_stack: &mut Vec<State>, diff_level: usize) {
             let last_state = state_stack.last_mut().unwrap();
             compute_minmax(last_state);
             compute_sum(last_state);
-            compute_halstead_mi_and_wmc::<T>(last_state);
+            compute_halstead_mi_and_wmc::<T>(last_state, options);
             compute_averages(last_state);
+            collect_increments(last_state);
             break;
         } else {
             let mut state = state_stack.pop().unwrap();
             compute_minmax(&mut state);
             compute_sum(&mut state);
-            compute_halstead_mi_and_wmc::<T>(&mut state);
+            compute_halstead_mi_and_wmc::<T>(&mut state, options);
             compute_averages(&mut state);
+            collect_increments(&mut state);
 
-            let last_state = state_stack.last_mut().unwrap();
-            last_state.halstead_maps.merge(&state.halstead_maps);
-            compute_halstead_mi_and_wmc::<T>(last_state);
+            if let Some(cache) = cache.as_deref_mut() {
+                cache.insert(
+                    state.range,
+                    CachedSpace {
+                        space: state.space.clone(),
+                        halstead_maps: state.halstead_maps.to_owned_maps(),
+                        ancestors: std::mem::take(&mut state.ancestors),
+                        context_start: state.context_start,
+                    },
+                );
+            }
 
-            // Merge function spaces
-            last_state.space.metrics.merge(&state.space.metrics);
-            last_state.space.spaces.push(state.space);
+            let last_state = state_stack.last_mut().unwrap();
+            last_state.subspaces_end = state.range.1;
+            merge_space::<T>(last_state, state.space, &state.halstead_maps, options);
         }
     }
 }
 
+/// Options of the computation of the metrics of a code.
+#[derive(Debug, Default, Clone)]
+pub struct MetricsOptions {
+    /// Whether the increments of the `Cognitive Complexity`
+    /// and `Cyclomatic` metrics are recorded
+    pub increments: bool,
+    /// The classification of the nodes as `Halstead` operators and operands
+    pub halstead_table: HalsteadTable,
+    /// The custom formula of the `Mi` metric, if any.
+    ///
+    /// When chosen, the value of the formula and its rating are added
+    /// to the `Mi` metric.
+    pub mi_formula: Option<MiFormula>,
+    /// The metrics defined outside of this crate to compute
+    pub registry: MetricRegistry,
+    /// The metrics to compute and serialize, all of them by default.
+    ///
+    /// The metrics needed to compute the selected ones are computed too,
+    /// but they are not serialized.
+    pub selection: MetricSet,
+    /// The maximum time of the computation, if any.
+    ///
+    /// When exceeded, the computation is stopped and
+    /// an [`Error::Timeout`] is returned.
+    pub timeout: Option<Duration>,
+    /// Whether the metrics of the spaces directly containing
+    /// syntax errors are left out of the metrics of their parents
+    pub exclude_syntax_errors: bool,
+    /// The patterns of the markers counted by the `Markers` metric.
+    ///
+    /// If `None`, the default markers are searched.
+    pub markers: Option<MarkerPatterns>,
+    /// The naming conventions checked by the `Naming` metric.
+    ///
+    /// If `None`, the usual conventions of the language are checked.
+    pub naming: Option<NamingConventions>,
+    /// The numbers not counted as magic numbers by the `Literals` metric.
+    ///
+    /// If `None`, the [`DEFAULT_ALLOWED_NUMBERS`] are allowed.
+    pub allowed_numbers: Option<Vec<f64>>,
+    /// The root directory of the project, from which the modules
+    /// of the files are derived.
+    ///
+    /// If `None`, the module of a file is derived from its name.
+    pub root: Option<PathBuf>,
+}
+
 #[derive(Debug, Clone)]
 struct State<'a> {
     space: FuncSpace,
     halstead_maps: HalsteadMaps<'a>,
+    // The byte range of the space
+    range: (usize, usize),
+    // The kinds of the ancestors of the space, only needed by the cache
+    ancestors: Vec<u16>,
+    // The first byte of the code preceding the space which is read by its
+    // metrics, as its documentation: the end of the previous subspace of
+    // its parent, or the start of its parent
+    context_start: usize,
+    // The end of the last subspace of the space
+    subspaces_end: usize,
 }
 
 /// Returns all function spaces data of a code. This function needs a parser to
@@ -278,28 +827,230 @@ struct State<'a> {
 /// let source_as_vec = source_code.as_bytes().to_vec();
 ///
 /// // The parser of the code, in this case a CPP parser
-/// let parser = CppParser::new(source_as_vec, &path, None);
+/// let parser = CppParser::new(source_as_vec, &path, None).unwrap();
 ///
 /// // Gets all function spaces data of the code contained in foo.c
 /// metrics(&parser, &path).unwrap();
 /// ```
-pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<FuncSpace> {
+pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Result<FuncSpace, Error> {
+    compute_metrics(parser, path, &MetricsOptions::default())
+}
+
+/// Returns all function spaces data of a code, recording for each space
+/// the increments of the `Cognitive Complexity` and `Cyclomatic` metrics.
+///
+/// The increments are stored in the [`FuncSpace::increments`] field and
+/// explain how the values of these metrics have been obtained.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{metrics_with_increments, ParserTrait, RustParser};
+///
+/// let source_code = "fn f(a: bool) { if a {} }";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = Path::new("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// let space = metrics_with_increments(&parser, &path).unwrap();
+/// let increments = space.spaces[0].increments.as_ref().unwrap();
+/// assert_eq!(increments.cognitive[0].reason, "if");
+/// ```
+///
+/// [`FuncSpace::increments`]: struct.FuncSpace.html#structfield.increments
+pub fn metrics_with_increments<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+) -> Result<FuncSpace, Error> {
+    let options = MetricsOptions {
+        increments: true,
+        ..MetricsOptions::default()
+    };
+    compute_metrics(parser, path, &options)
+}
+
+/// Returns all function spaces data of a code, classifying the nodes
+/// as `Halstead` operators and operands with a given table.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::halstead::{HalsteadTable, HalsteadType};
+/// use rust_code_analysis::{metrics_with_halstead_table, ParserTrait, RustParser, LANG};
+///
+/// let source_code = "fn f() { let a = 1; }";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = Path::new("foo.rs");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// let parser = RustParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Do not count the semicolons as operators
+/// let mut table = HalsteadTable::new();
+/// table.set(LANG::Rust, ";", HalsteadType::Unknown).unwrap();
+///
+/// let space = metrics_with_halstead_table(&parser, &path, &table).unwrap();
+/// assert_eq!(space.metrics.halstead.operators(), 5.);
+/// ```
+pub fn metrics_with_halstead_table<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    table: &HalsteadTable,
+) -> Result<FuncSpace, Error> {
+    let options = MetricsOptions {
+        halstead_table: table.clone(),
+        ..MetricsOptions::default()
+    };
+    compute_metrics(parser, path, &options)
+}
+
+/// Returns all function spaces data of a code, computed with the given options.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::mi::{MiComplexity, MiFormula, MiRating};
+/// use rust_code_analysis::{metrics_with_options, MetricsOptions, ParserTrait, PythonParser};
+///
+/// let source_code = "def f():\n    pass";
+///
+/// // The path to a dummy file used to contain the source code
+/// let path = Path::new("foo.py");
+/// let source_as_vec = source_code.as_bytes().to_vec();
+///
+/// let parser = PythonParser::new(source_as_vec, &path, None).unwrap();
+///
+/// // Use the Cognitive Complexity in the Visual Studio formula
+/// let options = MetricsOptions {
+///     mi_formula: Some(MiFormula {
+///         complexity_metric: MiComplexity::Cognitive,
+///         ..MiFormula::visual_studio()
+///     }),
+///     ..MetricsOptions::default()
+/// };
+///
+/// let space = metrics_with_options(&parser, &path, &options).unwrap();
+/// assert_eq!(space.metrics.mi.rating(), Some(MiRating::A));
+/// ```
+pub fn metrics_with_options<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    options: &MetricsOptions,
+) -> Result<FuncSpace, Error> {
+    compute_metrics(parser, path, options)
+}
+
+/// Reads a file and returns all function spaces data of its code,
+/// computed with the given options.
+///
+/// The language of the code is guessed from the file.
+/// An error is returned if the file cannot be read, if its code
+/// is not valid UTF-8 or if its language is unknown.
+///
+/// # Examples
+///
+/// ```
+/// use std::path::Path;
+///
+/// use rust_code_analysis::{metrics_from_file, MetricsOptions};
+///
+/// let path = Path::new("src/lib.rs");
+///
+/// let space = metrics_from_file(&path, &MetricsOptions::default()).unwrap();
+/// assert_eq!(space.name.as_deref(), Some("src/lib.rs"));
+/// ```
+pub fn metrics_from_file(path: &Path, options: &MetricsOptions) -> Result<FuncSpace, Error> {
+    let source = read_file(path)?;
+    std::str::from_utf8(&source)?;
+    let language = guess_language(&source, path)
+        .0
+        .ok_or_else(|| Error::UnknownLanguage(path.to_path_buf()))?;
+
+    let cfg = (path.to_path_buf(), options.clone());
+    try_action::<MetricsWithOptions, _>(&language, source, path, None, cfg)
+}
+
+struct MetricsWithOptions;
+
+impl Callback for MetricsWithOptions {
+    type Res = Result<FuncSpace, Error>;
+    type Cfg = (PathBuf, MetricsOptions);
+
+    fn call<T: ParserTrait>((path, options): Self::Cfg, parser: &T) -> Self::Res {
+        compute_metrics(parser, &path, &options)
+    }
+}
+
+fn compute_metrics<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    options: &MetricsOptions,
+) -> Result<FuncSpace, Error> {
+    compute_spaces(parser, path, options, None)
+}
+
+// Computes the metrics of a code, reusing the spaces stored in the cache
+// when their code has not changed. The cache is then filled with the
+// computed spaces.
+pub(crate) fn compute_spaces<'a, T: ParserTrait>(
+    parser: &'a T,
+    path: &'a Path,
+    options: &MetricsOptions,
+    mut cache: Option<&mut SpaceCache>,
+) -> Result<FuncSpace, Error> {
+    let mut old_cache = cache.as_deref_mut().map(SpaceCache::take);
+    let mut reused_ranges = Vec::new();
     let code = parser.get_code();
     let node = parser.get_root();
+    let mut qualified_names = QualifiedNames::new::<T>(&node, code, path, options.root.as_deref());
     let mut cursor = node.cursor();
     let mut stack = Vec::new();
     let mut children = Vec::new();
     let mut state_stack: Vec<State> = Vec::new();
     let mut last_level = 0;
+    let selection = options
+        .selection
+        .with_dependencies(options.mi_formula.as_ref());
+    let markers = options
+        .markers
+        .as_ref()
+        .unwrap_or_else(|| MarkerPatterns::default_ref());
+    let allowed_numbers = options
+        .allowed_numbers
+        .as_deref()
+        .unwrap_or(DEFAULT_ALLOWED_NUMBERS);
+    let naming_conventions = options
+        .naming
+        .clone()
+        .unwrap_or_else(|| NamingConventions::new(parser.get_language()));
     // Initialize nesting_map used for storing nesting information for cognitive
     // Three type of nesting info: conditionals, functions and lambdas
     let mut nesting_map = HashMap::<usize, (usize, usize, usize)>::default();
     nesting_map.insert(node.id(), (0, 0, 0));
-    stack.push((node, 0));
+    stack.push((node, 0, false));
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
 
-    while let Some((node, level)) = stack.pop() {
+    while let Some((node, level, in_error)) = stack.pop() {
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(Error::Timeout(options.timeout.unwrap_or_default()));
+        }
         if level < last_level {
-            finalize::<T>(&mut state_stack, last_level - level);
+            finalize::<T>(
+                &mut state_stack,
+                last_level - level,
+                options,
+                cache.as_deref_mut(),
+            );
             last_level = level;
         }
 
@@ -307,12 +1058,77 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
 
         let func_space = T::Checker::is_func(&node) || T::Checker::is_func_space(&node);
         let unit = kind == SpaceKind::Unit;
+        let range = (node.start_byte(), node.end_byte());
+        let ancestors = if func_space && cache.is_some() {
+            get_ancestors(&node)
+        } else {
+            Vec::new()
+        };
+        let context_start = state_stack
+            .last()
+            .map_or(range.0, |state| state.subspaces_end);
+
+        // Reuse the space, skipping its nodes, if its code has not changed
+        let cached = if func_space && !state_stack.is_empty() {
+            old_cache
+                .as_mut()
+                .and_then(|old_cache| old_cache.remove(range, context_start, kind, &ancestors))
+        } else {
+            None
+        };
+        if let Some(cached) = cached {
+            let mut space = cached.space;
+            // The name can be found outside of the space
+            (space.name, space.name_range) = get_space_name::<T::Getter>(&node, code);
+            let qualified_name = qualified_names.get::<T>(&node, code);
+            qualified_names.requalify(&mut space, qualified_name);
+            if let Some(cache) = cache.as_deref_mut() {
+                cache.insert(
+                    range,
+                    CachedSpace {
+                        space: space.clone(),
+                        halstead_maps: cached.halstead_maps.clone(),
+                        ancestors,
+                        context_start,
+                    },
+                );
+                cache.reused += 1;
+            }
+            let last_state = state_stack.last_mut().unwrap();
+            last_state.subspaces_end = range.1;
+            if selection.contains(MetricKind::Naming) {
+                // The name of the space is declared in its parent
+                T::NamingRules::compute(
+                    &node,
+                    code,
+                    &naming_conventions,
+                    true,
+                    None,
+                    Some(&mut last_state.space.metrics.naming),
+                );
+            }
+            merge_space::<T>(last_state, space, &cached.halstead_maps, options);
+            reused_ranges.push(range);
+            continue;
+        }
 
         let new_level = if func_space {
-            let state = State {
-                space: FuncSpace::new::<T::Getter>(&node, code, kind),
+            let mut state = State {
+                space: FuncSpace::new::<T>(&node, code, kind, &mut qualified_names),
                 halstead_maps: HalsteadMaps::new(),
+                range,
+                ancestors,
+                context_start,
+                subspaces_end: range.0,
             };
+            if options.increments {
+                state.space.metrics.cognitive.record_increments();
+                state.space.metrics.cyclomatic.record_increments();
+            }
+            if selection.contains(MetricKind::Custom) {
+                state.space.metrics.custom = options.registry.new_stats();
+            }
+            state.space.metrics.selection = options.selection;
             state_stack.push(state);
             last_level = level + 1;
             last_level
@@ -320,24 +1136,103 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
             level
         };
 
+        // The nodes of an `ERROR` node are errors too
+        let in_error = in_error || node.is_error();
         if let Some(state) = state_stack.last_mut() {
             let last = &mut state.space;
-            T::Cognitive::compute(&node, &mut last.metrics.cognitive, &mut nesting_map);
-            T::Cyclomatic::compute(&node, &mut last.metrics.cyclomatic);
-            T::Halstead::compute(&node, code, &mut state.halstead_maps);
-            T::Loc::compute(&node, &mut last.metrics.loc, func_space, unit);
-            T::Nom::compute(&node, &mut last.metrics.nom);
-            T::NArgs::compute(&node, &mut last.metrics.nargs);
-            T::Exit::compute(&node, &mut last.metrics.nexits);
-            T::Abc::compute(&node, &mut last.metrics.abc);
-            T::Npm::compute(&node, &mut last.metrics.npm);
-            T::Npa::compute(&node, &mut last.metrics.npa);
+            last.syntax_errors.compute(&node, in_error);
+            if selection.contains(MetricKind::Cognitive) {
+                T::Cognitive::compute(&node, &mut last.metrics.cognitive, &mut nesting_map);
+            }
+            if selection.contains(MetricKind::Cyclomatic) {
+                T::Cyclomatic::compute(&node, &mut last.metrics.cyclomatic);
+            }
+            if selection.contains(MetricKind::Halstead) {
+                T::Halstead::compute(
+                    &node,
+                    code,
+                    &options.halstead_table,
+                    &mut state.halstead_maps,
+                );
+            }
+            if selection.contains(MetricKind::Loc) {
+                T::Loc::compute(&node, &mut last.metrics.loc, func_space, unit);
+            }
+            if selection.contains(MetricKind::Nom) {
+                T::Nom::compute(&node, &mut last.metrics.nom);
+            }
+            if selection.contains(MetricKind::NArgs) {
+                T::NArgs::compute(&node, &mut last.metrics.nargs);
+            }
+            if selection.contains(MetricKind::NExits) {
+                T::Exit::compute(&node, &mut last.metrics.nexits);
+            }
+            if selection.contains(MetricKind::Abc) {
+                T::Abc::compute(&node, &mut last.metrics.abc);
+            }
+            if selection.contains(MetricKind::Npm) {
+                T::Npm::compute(&node, &mut last.metrics.npm);
+            }
+            if selection.contains(MetricKind::Npa) {
+                T::Npa::compute(&node, &mut last.metrics.npa);
+            }
+            if selection.contains(MetricKind::Markers) {
+                T::Markers::compute(&node, code, markers, &mut last.metrics.markers);
+            }
+            if selection.contains(MetricKind::DocCoverage) {
+                T::DocCoverage::compute(&node, code, &mut last.metrics.doc_coverage);
+            }
+            if selection.contains(MetricKind::Exceptions) {
+                T::Exceptions::compute(&node, code, &mut last.metrics.exceptions);
+            }
+            if selection.contains(MetricKind::Literals) {
+                T::Literals::compute(&node, code, allowed_numbers, &mut last.metrics.literals);
+            }
+            if selection.contains(MetricKind::Safety) {
+                T::Safety::compute(&node, code, &mut last.metrics.safety);
+            }
+            if selection.contains(MetricKind::Booleans) {
+                T::Booleans::compute(&node, &mut last.metrics.booleans);
+            }
+            if selection.contains(MetricKind::Imports) {
+                T::Imports::compute(&node, code, &mut last.metrics.imports);
+            }
+            if selection.contains(MetricKind::Variables) {
+                // The `Variables` trait extends the `NamingRules` one
+                <T::Variables as Variables>::compute(
+                    &node,
+                    code,
+                    &options.halstead_table,
+                    last.kind,
+                    &mut last.metrics.variables,
+                );
+            }
+            if selection.contains(MetricKind::Statements) {
+                T::Statements::compute(&node, &mut last.metrics.statements);
+            }
+            if selection.contains(MetricKind::Custom) {
+                last.metrics
+                    .custom
+                    .compute(&node, code, parser.get_language());
+            }
+        }
+        if selection.contains(MetricKind::Naming) {
+            let (last, parents) = state_stack.split_last_mut().unzip();
+            let parent = parents.and_then(|parents| parents.last_mut());
+            T::NamingRules::compute(
+                &node,
+                code,
+                &naming_conventions,
+                func_space,
+                last.map(|state| &mut state.space.metrics.naming),
+                parent.map(|state| &mut state.space.metrics.naming),
+            );
         }
 
         cursor.reset(&node);
         if cursor.goto_first_child() {
             loop {
-                children.push((cursor.node(), new_level));
+                children.push((cursor.node(), new_level, in_error));
                 if !cursor.goto_next_sibling() {
                     break;
                 }
@@ -348,12 +1243,20 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
         }
     }
 
-    finalize::<T>(&mut state_stack, usize::MAX);
+    finalize::<T>(&mut state_stack, usize::MAX, options, cache.as_deref_mut());
 
-    state_stack.pop().map(|mut state| {
-        state.space.name = path.to_str().map(|name| name.to_string());
-        state.space
-    })
+    // Keep the subspaces of the reused spaces in the cache
+    if let (Some(cache), Some(old_cache)) = (cache, old_cache) {
+        cache.keep_nested(old_cache, &reused_ranges);
+    }
+
+    state_stack
+        .pop()
+        .map(|mut state| {
+            state.space.name = path.to_str().map(|name| name.to_string());
+            state.space
+        })
+        .ok_or_else(|| Error::Parse(path.to_path_buf()))
 }
 
 /// Configuration options for computing
@@ -362,6 +1265,8 @@ pub fn metrics<'a, T: ParserTrait>(parser: &'a T, path: &'a Path) -> Option<Func
 pub struct MetricsCfg {
     /// Path to the file containing the code
     pub path: PathBuf,
+    /// The metrics to compute
+    pub selection: MetricSet,
 }
 
 pub struct Metrics {
@@ -369,20 +1274,37 @@ pub struct Metrics {
 }
 
 impl Callback for Metrics {
-    type Res = std::io::Result<()>;
+    type Res = Result<(), Error>;
     type Cfg = MetricsCfg;
 
     fn call<T: ParserTrait>(cfg: Self::Cfg, parser: &T) -> Self::Res {
-        match metrics(parser, &cfg.path) {
-            Some(space) => dump_root(&space),
-            _ => Ok(()),
-        }
+        let options = MetricsOptions {
+            selection: cfg.selection,
+            ..MetricsOptions::default()
+        };
+        let space = compute_metrics(parser, &cfg.path, &options)?;
+        Ok(dump_root(&space)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{CppParser, check_func_space};
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use crate::{
+        CppParser, Error, JavaParser, MetricsOptions, MozjsParser, ParserTrait, PythonParser,
+        RustParser, check_func_space, check_func_space_with_options,
+    };
+
+    use super::{FuncSpace, metrics_from_file, metrics_with_options};
+
+    // Returns the qualified names of a space and its subspaces in preorder
+    fn qualified_names(space: &FuncSpace) -> Vec<Option<&str>> {
+        std::iter::once(space.qualified_name.as_deref())
+            .chain(space.spaces.iter().flat_map(qualified_names))
+            .collect()
+    }
 
     #[test]
     fn c_scope_resolution_operator() {
@@ -399,4 +1321,278 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn metrics_errors() {
+        let path = Path::new("foo.rs");
+        let parser = RustParser::new(b"fn f() {}".to_vec(), path, None).unwrap();
+        let options = MetricsOptions {
+            timeout: Some(Duration::ZERO),
+            ..MetricsOptions::default()
+        };
+        assert!(matches!(
+            metrics_with_options(&parser, path, &options),
+            Err(Error::Timeout(_))
+        ));
+
+        let options = MetricsOptions::default();
+        assert!(matches!(
+            metrics_from_file(Path::new("Cargo.toml"), &options),
+            Err(Error::UnknownLanguage(_))
+        ));
+        assert!(matches!(
+            metrics_from_file(Path::new("foo.rs"), &options),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn space_ranges() {
+        check_func_space::<RustParser, _>(
+            "impl A {
+                 fn f() {}
+             }",
+            "foo.rs",
+            |func_space| {
+                let f = &func_space.spaces[0].spaces[0];
+                assert_eq!(
+                    (f.start_column, f.end_column, f.start_byte, f.end_byte),
+                    (18, 27, 26, 35)
+                );
+                insta::assert_json_snapshot!(
+                    f.name_range,
+                    @r###"
+                    {
+                      "start_line": 2,
+                      "end_line": 2,
+                      "start_column": 21,
+                      "end_column": 22,
+                      "start_byte": 29,
+                      "end_byte": 30
+                    }
+                    "###
+                );
+                // The unit ends on its last line of code
+                assert_eq!((func_space.end_line, func_space.end_column), (3, 15));
+            },
+        );
+    }
+
+    #[test]
+    fn space_name_outside_of_the_space() {
+        check_func_space::<MozjsParser, _>(
+            "var o = {
+               foo: function() {
+                 return 1;
+               }
+             };",
+            "foo.js",
+            |func_space| {
+                let foo = &func_space.spaces[0];
+                assert_eq!((foo.start_line, foo.start_column), (2, 21));
+                insta::assert_json_snapshot!(
+                    foo.name_range,
+                    @r###"
+                    {
+                      "start_line": 2,
+                      "end_line": 2,
+                      "start_column": 16,
+                      "end_column": 19,
+                      "start_byte": 25,
+                      "end_byte": 28
+                    }
+                    "###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_qualified_names() {
+        check_func_space::<RustParser, _>(
+            "mod a {
+                 impl Foo {
+                     fn new() -> Self {
+                         let f = |x| x;
+                         let g = |x| x;
+                         Foo
+                     }
+                 }
+             }
+             impl Display for Bar {
+                 fn fmt(&self) {}
+             }
+             impl fmt::Debug for Bar {
+                 fn fmt(&self) {}
+             }",
+            "foo.rs",
+            |func_space| {
+                assert_eq!(
+                    qualified_names(&func_space),
+                    [
+                        Some("crate::foo"),
+                        Some("crate::foo::a::Foo"),
+                        Some("crate::foo::a::Foo::new"),
+                        Some("crate::foo::a::Foo::new::<anonymous>"),
+                        Some("crate::foo::a::Foo::new::<anonymous>#2"),
+                        Some("crate::foo::<Bar as Display>"),
+                        Some("crate::foo::<Bar as Display>::fmt"),
+                        Some("crate::foo::<Bar as fmt::Debug>"),
+                        Some("crate::foo::<Bar as fmt::Debug>::fmt")
+                    ]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn rust_module_names() {
+        let options = MetricsOptions {
+            root: Some(PathBuf::from("/project")),
+            ..MetricsOptions::default()
+        };
+        for (path, module) in [
+            ("/project/src/lib.rs", "crate"),
+            ("/project/src/main.rs", "crate"),
+            ("/project/src/a/mod.rs", "crate::a"),
+            ("/project/src/a/b.rs", "crate::a::b"),
+            ("/elsewhere/a/b.rs", "crate::b"),
+        ] {
+            check_func_space_with_options::<RustParser, _>(
+                "fn f() {}",
+                path,
+                &options,
+                |func_space| {
+                    assert_eq!(func_space.qualified_name.as_deref(), Some(module));
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn java_qualified_names() {
+        check_func_space::<JavaParser, _>(
+            "package org.foo;
+
+             class A {
+                 void f() {}
+                 void f(int x) {}
+                 class B {
+                     B() {}
+                 }
+             }",
+            "foo.java",
+            |func_space| {
+                assert_eq!(
+                    qualified_names(&func_space),
+                    [
+                        Some("org.foo"),
+                        Some("org.foo.A"),
+                        Some("org.foo.A.f"),
+                        Some("org.foo.A.f#2"),
+                        Some("org.foo.A.B"),
+                        Some("org.foo.A.B.B")
+                    ]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn python_qualified_names() {
+        check_func_space::<PythonParser, _>(
+            "class A:
+                 def f(self):
+                     def g():
+                         pass",
+            "foo.py",
+            |func_space| {
+                assert_eq!(
+                    qualified_names(&func_space),
+                    [
+                        Some("foo"),
+                        Some("foo.A"),
+                        Some("foo.A.f"),
+                        Some("foo.A.f.g")
+                    ]
+                );
+            },
+        );
+
+        let options = MetricsOptions {
+            root: Some(PathBuf::from("project")),
+            ..MetricsOptions::default()
+        };
+        for (path, module) in [
+            ("project/pkg/__init__.py", "pkg"),
+            ("project/pkg/foo.py", "pkg.foo"),
+        ] {
+            check_func_space_with_options::<PythonParser, _>(
+                "pass",
+                path,
+                &options,
+                |func_space| {
+                    assert_eq!(func_space.qualified_name.as_deref(), Some(module));
+                },
+            );
+        }
+    }
+
+    #[test]
+    fn cpp_qualified_names() {
+        check_func_space::<CppParser, _>(
+            "namespace a {
+                 class A {
+                     void f() {}
+                     void f(int x) {}
+                 };
+             }
+             void a::A::g() {}",
+            "foo.cpp",
+            |func_space| {
+                assert_eq!(
+                    qualified_names(&func_space),
+                    [
+                        None,
+                        Some("a"),
+                        Some("a::A"),
+                        Some("a::A::f"),
+                        Some("a::A::f#2"),
+                        Some("a::A::g")
+                    ]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn javascript_qualified_names() {
+        check_func_space::<MozjsParser, _>(
+            "class A {
+                 f() {
+                     return function() {};
+                 }
+             }
+             var o = {
+                 g: function() {},
+                 p: {
+                     h: function() {}
+                 }
+             };",
+            "foo.js",
+            |func_space| {
+                assert_eq!(
+                    qualified_names(&func_space),
+                    [
+                        Some("foo"),
+                        Some("foo.A"),
+                        Some("foo.A.f"),
+                        Some("foo.A.f.<anonymous>"),
+                        Some("foo.o.g"),
+                        Some("foo.o.p.h")
+                    ]
+                );
+            },
+        );
+    }
 }
@@ -0,0 +1,207 @@
+use serde::Serialize;
+use serde::ser::{SerializeStruct, Serializer};
+
+use crate::node::Node;
+
+/// The kind of a syntax error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyntaxErrorKind {
+    /// Some code which could not be parsed, an `ERROR` node
+    Error,
+    /// A node inserted by the parser to recover from an error, a `MISSING` node
+    Missing,
+}
+
+/// A syntax error found in a code.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SyntaxError {
+    /// The kind of the error
+    pub kind: SyntaxErrorKind,
+    /// The first line of the error
+    pub start_line: usize,
+    /// The column of the first character of the error
+    pub start_column: usize,
+    /// The last line of the error
+    pub end_line: usize,
+    /// The column following the last character of the error
+    pub end_column: usize,
+}
+
+impl SyntaxError {
+    fn new(node: &Node, kind: SyntaxErrorKind) -> Self {
+        let (start_row, start_column) = node.start_position();
+        let (end_row, end_column) = node.end_position();
+        Self {
+            kind,
+            start_line: start_row + 1,
+            start_column: start_column + 1,
+            end_line: end_row + 1,
+            end_column: end_column + 1,
+        }
+    }
+}
+
+/// The syntax errors of a space.
+///
+/// Only the errors directly contained in a space are listed, the ones of
+/// its subspaces are stored in the subspaces. The number of errors and
+/// the error ratio include the subspaces.
+#[derive(Clone, Debug, Default)]
+pub struct SyntaxErrors {
+    /// The `ERROR` and `MISSING` nodes directly contained in the space
+    pub errors: Vec<SyntaxError>,
+    count: usize,
+    nodes: usize,
+    error_nodes: usize,
+}
+
+impl Serialize for SyntaxErrors {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut st = serializer.serialize_struct("syntax_errors", 3)?;
+        st.serialize_field("count", &self.count())?;
+        st.serialize_field("ratio", &self.ratio())?;
+        st.serialize_field("errors", &self.errors)?;
+        st.end()
+    }
+}
+
+impl SyntaxErrors {
+    /// Merges the syntax errors of a subspace into the ones of its parent
+    pub fn merge(&mut self, other: &SyntaxErrors) {
+        self.count += other.count;
+        self.nodes += other.nodes;
+        self.error_nodes += other.error_nodes;
+    }
+
+    /// Returns the number of syntax errors in a space and its subspaces
+    #[inline(always)]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Checks whether a space or one of its subspaces contains syntax errors
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the ratio of the nodes of a space, subspaces included,
+    /// which are syntax errors or are contained in an `ERROR` node
+    pub fn ratio(&self) -> f64 {
+        if self.nodes == 0 {
+            0.
+        } else {
+            self.error_nodes as f64 / self.nodes as f64
+        }
+    }
+
+    // Counts a node of the space, recording it if it is an error
+    pub(crate) fn compute(&mut self, node: &Node, in_error: bool) {
+        self.nodes += 1;
+        if in_error || node.is_missing() {
+            self.error_nodes += 1;
+        }
+        let kind = if node.is_error() {
+            SyntaxErrorKind::Error
+        } else if node.is_missing() {
+            SyntaxErrorKind::Missing
+        } else {
+            return;
+        };
+        self.errors.push(SyntaxError::new(node, kind));
+        self.count += 1;
+    }
+
+    // Moves the errors by `delta` lines
+    pub(crate) fn shift_lines(&mut self, delta: isize) {
+        for error in self.errors.iter_mut() {
+            error.start_line = error.start_line.saturating_add_signed(delta);
+            error.end_line = error.end_line.saturating_add_signed(delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::{MetricsOptions, ParserTrait, RustParser, check_func_space, metrics_with_options};
+
+    #[test]
+    fn syntax_errors_per_space() {
+        check_func_space::<RustParser, _>(
+            "fn f() {
+                 let a = ;
+             }
+
+             fn g(b: u32 {
+                 b + 1
+             }
+
+             fn h() {}",
+            "foo.rs",
+            |func_space| {
+                assert_eq!(func_space.syntax_errors.count(), 2);
+                assert!(func_space.spaces[2].syntax_errors.is_empty());
+                insta::assert_json_snapshot!(
+                    func_space.spaces[0].syntax_errors,
+                    @r###"
+                    {
+                      "count": 1,
+                      "ratio": 0.13333333333333333,
+                      "errors": [
+                        {
+                          "kind": "error",
+                          "start_line": 2,
+                          "start_column": 24,
+                          "end_line": 2,
+                          "end_column": 25
+                        }
+                      ]
+                    }
+                    "###
+                );
+                insta::assert_json_snapshot!(
+                    func_space.spaces[1].syntax_errors.errors,
+                    @r###"
+                    [
+                      {
+                        "kind": "missing",
+                        "start_line": 5,
+                        "start_column": 25,
+                        "end_line": 5,
+                        "end_column": 25
+                      }
+                    ]
+                    "###
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn syntax_errors_excluded() {
+        let source_code = "fn f() {\n    let a = ;\n    if a {}\n}\n\nfn g(b: bool) -> u32 {\n    if b { 1 } else { 0 }\n}\n";
+        let path = Path::new("foo.rs");
+        let parser = RustParser::new(source_code.into(), path, None).unwrap();
+
+        let space = metrics_with_options(&parser, path, &MetricsOptions::default()).unwrap();
+        assert_eq!(space.metrics.nom.functions_sum(), 2.);
+        assert_eq!(space.metrics.cyclomatic.cyclomatic_sum(), 5.);
+
+        let options = MetricsOptions {
+            exclude_syntax_errors: true,
+            ..MetricsOptions::default()
+        };
+        let space = metrics_with_options(&parser, path, &options).unwrap();
+        // Only the metrics of g and of the unit are aggregated
+        assert_eq!(space.metrics.nom.functions_sum(), 1.);
+        assert_eq!(space.metrics.cyclomatic.cyclomatic_sum(), 3.);
+        assert_eq!(space.spaces.len(), 2);
+        assert_eq!(space.syntax_errors.count(), 1);
+    }
+}
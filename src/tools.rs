@@ -121,12 +121,8 @@ pub fn write_file(path: &Path, data: &[u8]) -> std::io::Result<()> {
 /// get_language_for_file(&path).unwrap();
 /// ```
 pub fn get_language_for_file(path: &Path) -> Option<LANG> {
-    if let Some(ext) = path.extension() {
-        let ext = ext.to_str().unwrap().to_lowercase();
-        get_from_ext(&ext)
-    } else {
-        None
-    }
+    let ext = path.extension()?.to_str()?.to_lowercase();
+    get_from_ext(&ext)
 }
 
 fn mode_to_str(mode: &[u8]) -> Option<String> {
@@ -384,13 +380,39 @@ pub(crate) fn check_func_space<T: crate::ParserTrait, F: Fn(crate::FuncSpace)>(
     filename: &str,
     check: F,
 ) {
+    check_func_space_with_options::<T, _>(
+        source,
+        filename,
+        &crate::MetricsOptions::default(),
+        check,
+    )
+}
+
+#[cfg(test)]
+pub(crate) fn check_func_space_with_options<T: crate::ParserTrait, F: Fn(crate::FuncSpace)>(
+    source: &str,
+    filename: &str,
+    options: &crate::MetricsOptions,
+    check: F,
+) {
+    let (parser, path) = trimmed_parser::<T>(source, filename);
+    let func_space = crate::metrics_with_options(&parser, &path, options).unwrap();
+
+    check(func_space)
+}
+
+// Parses a code without its leading and trailing blank lines
+#[cfg(test)]
+pub(crate) fn trimmed_parser<T: crate::ParserTrait>(
+    source: &str,
+    filename: &str,
+) -> (T, std::path::PathBuf) {
     let path = std::path::PathBuf::from(filename);
     let mut trimmed_bytes = source.trim_end().trim_matches('\n').as_bytes().to_vec();
     trimmed_bytes.push(b'\n');
-    let parser = T::new(trimmed_bytes, &path, None);
-    let func_space = crate::metrics(&parser, &path).unwrap();
+    let parser = T::new(trimmed_bytes, &path, None).unwrap();
 
-    check(func_space)
+    (parser, path)
 }
 
 #[cfg(test)]
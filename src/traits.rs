@@ -3,15 +3,24 @@ use std::sync::Arc;
 
 use crate::abc::Abc;
 use crate::alterator::Alterator;
+use crate::booleans::Booleans;
 use crate::checker::Checker;
 use crate::cognitive::Cognitive;
 use crate::cyclomatic::Cyclomatic;
+use crate::doc_coverage::DocCoverage;
+use crate::document::InputEdit;
+use crate::error::Error;
+use crate::exceptions::Exceptions;
 use crate::exit::Exit;
 use crate::getter::Getter;
 use crate::halstead::Halstead;
+use crate::imports::Imports;
 use crate::langs::*;
+use crate::literals::Literals;
 use crate::loc::Loc;
+use crate::markers::Markers;
 use crate::mi::Mi;
+use crate::naming::NamingRules;
 use crate::nargs::NArgs;
 use crate::node::Node;
 use crate::nom::Nom;
@@ -19,6 +28,9 @@ use crate::npa::Npa;
 use crate::npm::Npm;
 use crate::parser::Filter;
 use crate::preproc::PreprocResults;
+use crate::safety::Safety;
+use crate::statements::Statements;
+use crate::variables::Variables;
 use crate::wmc::Wmc;
 
 /// A trait for callback functions.
@@ -50,6 +62,16 @@ pub trait ParserTrait {
     type Cyclomatic: Cyclomatic;
     type Halstead: Halstead;
     type Loc: Loc;
+    type Markers: Markers;
+    type DocCoverage: DocCoverage;
+    type Exceptions: Exceptions;
+    type Literals: Literals;
+    type Safety: Safety;
+    type Booleans: Booleans;
+    type Imports: Imports;
+    type Variables: Variables;
+    type Statements: Statements;
+    type NamingRules: NamingRules;
     type Nom: Nom;
     type Mi: Mi;
     type NArgs: NArgs;
@@ -59,11 +81,15 @@ pub trait ParserTrait {
     type Npm: Npm;
     type Npa: Npa;
 
-    fn new(code: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Self;
+    fn new(code: Vec<u8>, path: &Path, pr: Option<Arc<PreprocResults>>) -> Result<Self, Error>
+    where
+        Self: Sized;
     fn get_language(&self) -> LANG;
     fn get_root(&self) -> Node;
     fn get_code(&self) -> &[u8];
     fn get_filters(&self, filters: &[String]) -> Filter;
+    fn edit(&mut self, edit: &InputEdit);
+    fn reparse(&mut self) -> Option<Vec<(usize, usize)>>;
 }
 
 pub(crate) trait Search<'a> {
@@ -19,7 +19,7 @@ struct Config {
     language: Option<LANG>,
 }
 
-fn act_on_file(path: PathBuf, cfg: &Config) -> std::io::Result<()> {
+fn act_on_file(path: PathBuf, cfg: &Config) -> Result<(), Error> {
     // Open file
     let source = if let Some(source) = read_file_with_eol(&path)? {
         source